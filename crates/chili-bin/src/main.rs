@@ -1,5 +1,6 @@
 mod completer;
 mod pipe;
+mod prompt;
 mod validator;
 
 use mimalloc::MiMalloc;
@@ -8,9 +9,11 @@ use mimalloc::MiMalloc;
 static GLOBAL: MiMalloc = MiMalloc;
 
 use crate::pipe::Pipe;
+use crate::prompt::ChiliPrompt;
 use crate::validator::ChiliValidator;
-use chili_core::EngineState;
+use chili_core::{EngineState, SpicyObj, check_source, explain, set_max_display_depth};
 use chili_op::{BUILT_IN_FN, LOG_FN};
+use chili_parser::Language;
 use clap::Parser;
 use env_logger::Target;
 use home::home_dir;
@@ -19,9 +22,13 @@ use log::{debug, error, info};
 use crate::completer::ChiliCompleter;
 use nu_ansi_term::{Color, Style};
 use reedline::{
-    ColumnarMenu, DefaultHinter, DefaultPrompt, DefaultPromptSegment, Emacs, ExternalPrinter,
-    FileBackedHistory, KeyCode, KeyModifiers, MenuBuilder, Reedline, ReedlineEvent, ReedlineMenu,
-    Signal, default_emacs_keybindings,
+    ColumnarMenu, DefaultHinter, Emacs, ExternalPrinter, FileBackedHistory, KeyCode, KeyModifiers,
+    MenuBuilder, Reedline, ReedlineEvent, ReedlineMenu, Signal, default_emacs_keybindings,
+};
+#[cfg(unix)]
+use signal_hook::{
+    consts::{SIGINT, SIGTERM},
+    iterator::Signals,
 };
 use std::fs::File;
 use std::io::IsTerminal;
@@ -40,6 +47,11 @@ struct Args {
     #[arg(index = 1)]
     src: Option<String>,
 
+    /// Optional directory of `*.chili` or `*.chi` source files, `*.pep` with pepper, to load in
+    /// name order before the source file
+    #[arg(long = "script-dir")]
+    script_dir: Option<String>,
+
     // Optional port number for IPC
     #[arg(short, long, default_value_t = 0)]
     port: i32,
@@ -72,6 +84,38 @@ struct Args {
     #[arg(short = 'm', long = "memory", default_value_t = 0.0)]
     memory_limit: f64,
 
+    /// Optional max nested function call depth (default: 0 for built-in limit)
+    #[arg(long = "max-depth", default_value_t = 0)]
+    max_depth: usize,
+
+    /// Optional max nested list and dict depth to display before "..." (default: 0 for built-in limit)
+    #[arg(long = "max-depth-display", default_value_t = 0)]
+    max_depth_display: usize,
+
+    /// Optional q IPC compression threshold in bytes (default: 1 MB, 0 to disable compression)
+    #[arg(long = "compress-threshold")]
+    compress_threshold: Option<usize>,
+
+    /// Optional PEM certificate chain for serving IPC over TLS, requires --tls-key
+    #[arg(long = "tls-cert")]
+    tls_cert: Option<String>,
+
+    /// Optional PEM private key for serving IPC over TLS, requires --tls-cert
+    #[arg(long = "tls-key")]
+    tls_key: Option<String>,
+
+    /// Optional seconds an IPC connection may stay idle before it is dropped (default: 0 for no timeout)
+    #[arg(long = "idle-timeout", default_value_t = 0)]
+    idle_timeout: u64,
+
+    /// Optional max inbound q IPC message size in bytes (default: 0 for no limit)
+    #[arg(long = "max-msg", default_value_t = 0)]
+    max_msg: usize,
+
+    /// Optional memory quota in GB for globals and in-flight results held by an IPC user, as user=GB (repeatable)
+    #[arg(long = "user-quota")]
+    user_quota: Vec<String>,
+
     /// Optional flag to enable lazy evaluation
     #[arg(short = 'L', long, default_value = "false")]
     lazy: bool,
@@ -80,6 +124,38 @@ struct Args {
     #[arg(short = 'P', long, default_value = "false")]
     pepper: bool,
 
+    /// Optional REPL prompt (default: "c ", or "p " with pepper syntax)
+    #[arg(long, env = "CHILI_PROMPT")]
+    prompt: Option<String>,
+
+    /// Optional REPL prompt while a multiline input is incomplete (default: "::: ")
+    #[arg(long = "continuation-prompt", env = "CHILI_CONTINUATION_PROMPT")]
+    continuation_prompt: Option<String>,
+
+    /// Parse the source file and report syntax and built-in arity errors without evaluating it
+    #[arg(long, default_value = "false")]
+    check: bool,
+
+    /// Print how each REPL input is grouped and the order its ops are evaluated in before evaluating it
+    #[arg(long, default_value = "false")]
+    explain: bool,
+
+    /// Evaluate a single expression, print the result and exit without entering the REPL
+    #[arg(long)]
+    eval: Option<String>,
+
+    /// Print REPL and --eval results as `text` or `json`
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    format: String,
+
+    /// Skip the startup banner, other output is unchanged
+    #[arg(long = "no-banner", default_value = "false")]
+    no_banner: bool,
+
+    /// Skip the startup banner and info logs, only warnings and errors are logged
+    #[arg(short, long, default_value = "false")]
+    quiet: bool,
+
     /// Skip the interactive REPL; run as headless daemon (auto-detected when stdin is not a TTY with --port)
     #[arg(long, default_value = "false")]
     headless: bool,
@@ -90,18 +166,28 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let log_level = log::LevelFilter::from_str(&args.log_level.to_lowercase())
         .unwrap_or(log::LevelFilter::Info);
+    let log_level = if args.quiet {
+        log_level.min(log::LevelFilter::Warn)
+    } else {
+        log_level
+    };
 
     let debug = log_level == log::LevelFilter::Debug;
 
     // Headless mode: explicit flag, or port requested with non-TTY stdin (daemon launch)
     let is_headless = args.headless || (args.port > 0 && !std::io::stdin().is_terminal());
 
-    // ExternalPrinter is only needed for the interactive REPL; skip in headless mode
-    let printer: Option<ExternalPrinter<String>> = if is_headless || !args.log_dir.is_empty() {
-        None
-    } else {
-        Some(ExternalPrinter::default())
-    };
+    let is_eval = args.eval.is_some();
+
+    let is_json = args.format == "json";
+
+    // ExternalPrinter is only needed for the interactive REPL; skip in headless, eval and check mode
+    let printer: Option<ExternalPrinter<String>> =
+        if is_headless || is_eval || args.check || !args.log_dir.is_empty() {
+            None
+        } else {
+            Some(ExternalPrinter::default())
+        };
 
     let target = if !args.log_dir.is_empty() {
         let log_dir = PathBuf::from(&args.log_dir);
@@ -138,7 +224,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .target(target)
         .init();
 
-    if !is_headless {
+    // `--version` is handled by clap and exits before getting here
+    if !is_headless && !is_eval && !args.check && !args.no_banner && !args.quiet {
         if args.pepper {
             println!(
                 "\x1b[1;32m\
@@ -193,6 +280,57 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         state.set_interval(args.interval);
     }
 
+    if args.max_depth > 0 {
+        state.set_max_depth(args.max_depth);
+    }
+
+    if args.max_depth_display > 0 {
+        set_max_display_depth(args.max_depth_display);
+    }
+
+    if args.idle_timeout > 0 {
+        state.set_idle_timeout(args.idle_timeout);
+    }
+
+    if args.max_msg > 0 {
+        state.set_max_msg_bytes(args.max_msg);
+    }
+
+    for quota in &args.user_quota {
+        let parsed = quota
+            .split_once('=')
+            .and_then(|(user, gb)| Some((user, gb.parse::<f64>().ok()?)));
+        match parsed {
+            Some((user, gb)) if !user.is_empty() && gb > 0.0 => state.set_user_quota(user, gb),
+            _ => {
+                eprintln!(
+                    "\x1b[1;91minvalid --user-quota '{}', expected user=GB\x1b[0m",
+                    quota
+                );
+                exit(1);
+            }
+        }
+    }
+
+    if let Some(threshold) = args.compress_threshold {
+        state.set_compression(threshold);
+    }
+
+    match (&args.tls_cert, &args.tls_key) {
+        (Some(cert), Some(key)) => {
+            if let Err(e) = state.set_tls(cert, key) {
+                eprintln!("\x1b[1;91m{}\x1b[0m", e);
+                exit(1);
+            }
+            info!("TLS is enabled for IPC connections");
+        }
+        (None, None) => {}
+        _ => {
+            eprintln!("\x1b[1;91m--tls-cert and --tls-key must be supplied together\x1b[0m");
+            exit(1);
+        }
+    }
+
     if debug {
         info!("Debug mode is enabled");
     }
@@ -223,6 +361,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    if args.check {
+        exit(check_src(&state, args.src.as_deref()));
+    }
+
+    if let Some(ref expr) = args.eval {
+        exit(eval_expr(&state, expr, args.pepper, is_json));
+    }
+
     let arc_state = Arc::new(state);
     arc_state.set_arc_self(Arc::clone(&arc_state)).unwrap();
 
@@ -236,6 +382,39 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         });
     }
 
+    // registered before any script runs, so a stop request during startup waits for the hooks
+    #[cfg(unix)]
+    let headless_signals = if is_headless {
+        Some(Signals::new([SIGTERM, SIGINT])?)
+    } else {
+        None
+    };
+
+    if let Some(ref dir) = args.script_dir {
+        let paths = match script_dir_paths(dir, args.pepper) {
+            Ok(paths) => paths,
+            Err(e) => {
+                eprintln!(
+                    "\x1b[1;91mfailed to read script dir '{}', {}\x1b[0m",
+                    dir, e
+                );
+                exit(1);
+            }
+        };
+        for path in paths {
+            match arc_state.import_source_path("", &path) {
+                Ok(_) => info!("loaded '{}'", path),
+                Err(e) => {
+                    error!("failed to load '{}'", path);
+                    eprintln!("\x1b[1;91m{}\x1b[0m", e);
+                    if !debug {
+                        exit(1);
+                    }
+                }
+            }
+        }
+    }
+
     if let Some(ref src) = args.src
         && let Err(e) = arc_state.import_source_path("", src)
     {
@@ -253,24 +432,33 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "headless mode: REPL disabled, IPC server active on port {}",
             args.port
         );
-        // Park the main thread indefinitely; the IPC server thread handles all traffic.
-        // A process supervisor (systemd, supervisord) can send SIGTERM to stop.
+        // Block the main thread until a process supervisor (systemd, supervisord) sends
+        // SIGTERM or SIGINT; the IPC server thread handles all traffic meanwhile.
+        #[cfg(unix)]
+        if let Some(mut signals) = headless_signals {
+            if let Some(signal) = signals.forever().next() {
+                info!("received signal {}, exiting ...", signal);
+            }
+            arc_state.shutdown();
+        }
         // Loop to guard against spurious wakeups from thread::park().
+        #[cfg(not(unix))]
         loop {
             std::thread::park();
         }
     } else {
         let completer = ChiliCompleter::new(&arc_state);
 
-        let prompt_prefix = if args.pepper {
-            "p ".to_owned()
-        } else {
-            "c ".to_owned()
+        let prompt_prefix = match args.prompt {
+            Some(ref prompt) => prompt.clone(),
+            None if args.pepper => "p ".to_owned(),
+            None => "c ".to_owned(),
         };
-        let prompt = DefaultPrompt::new(
-            DefaultPromptSegment::Basic(prompt_prefix),
-            DefaultPromptSegment::Empty,
-        );
+        let continuation_prompt = args
+            .continuation_prompt
+            .clone()
+            .unwrap_or_else(|| "::: ".to_owned());
+        let prompt = ChiliPrompt::new(prompt_prefix, continuation_prompt);
 
         let home = home_dir().unwrap_or_else(|| PathBuf::from("."));
         let history_path = if args.pepper {
@@ -316,6 +504,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         let state_input = Arc::clone(&arc_state);
         let src_path = if args.pepper { "repl.pep" } else { "repl.chi" };
+        let lang = if args.pepper {
+            Language::Pepper
+        } else {
+            Language::Chili
+        };
+        let explain_input = args.explain;
         let rl_handle = thread::spawn(move || {
             let state = state_input;
             loop {
@@ -336,12 +530,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 continue;
                             }
                         };
+                        if explain_input {
+                            for line in explain(lang, &nodes) {
+                                println!("\x1b[1;90m{}\x1b[0m", line);
+                            }
+                        }
                         let state = state.clone();
                         let handle = thread::spawn(move || {
                             match state.eval_ast(nodes.clone(), src_path, &line) {
                                 Ok(any) => {
-                                    println!("\x1b[1;90m{:?}\x1b[0m", start.elapsed());
-                                    println!("{}", any);
+                                    // keep stdout parseable as json, timing goes to stderr
+                                    if is_json {
+                                        eprintln!("\x1b[1;90m{:?}\x1b[0m", start.elapsed());
+                                    } else {
+                                        println!("\x1b[1;90m{:?}\x1b[0m", start.elapsed());
+                                    }
+                                    print_result(&any, is_json);
                                 }
                                 Err(e) => eprintln!("\x1b[1;91m{}\x1b[0m", e),
                             }
@@ -377,12 +581,93 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         match rl_handle.join() {
             Ok(_) => {
                 info!("Exiting ...");
+                arc_state.shutdown();
             }
             Err(e) => {
                 error!("Exiting ... error {:?}", e);
+                arc_state.shutdown();
                 exit(1);
             }
         }
     }
     Ok(())
 }
+
+/// Print an evaluated result, as compact JSON with `--format json`.
+fn print_result(any: &SpicyObj, is_json: bool) {
+    if is_json {
+        println!("{}", any.to_json());
+    } else {
+        println!("{}", any);
+    }
+}
+
+/// Run `--eval` on a single expression, printing the result; returns the process exit code.
+fn eval_expr(state: &EngineState, expr: &str, pepper: bool, is_json: bool) -> i32 {
+    let src_path = if pepper { "eval.pep" } else { "eval.chi" };
+    match state
+        .parse("", expr)
+        .and_then(|nodes| state.eval_ast(nodes, src_path, expr))
+    {
+        Ok(any) => {
+            print_result(&any, is_json);
+            0
+        }
+        Err(e) => {
+            eprintln!("\x1b[1;91m{}\x1b[0m", e);
+            1
+        }
+    }
+}
+
+/// Absolute paths of the `.chili` and `.chi` files, or `.pep` files with pepper syntax, in `dir`
+/// sorted by name.
+fn script_dir_paths(dir: &str, pepper: bool) -> std::io::Result<Vec<String>> {
+    let exts: &[&str] = if pepper { &["pep"] } else { &["chili", "chi"] };
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(std::fs::canonicalize(dir)?)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    paths.retain(|p| {
+        p.is_file()
+            && p.extension()
+                .is_some_and(|e| exts.iter().any(|ext| e == *ext))
+    });
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect())
+}
+
+/// Run `--check` on the source file, printing diagnostics; returns the process exit code.
+fn check_src(state: &EngineState, src: Option<&str>) -> i32 {
+    let Some(src) = src else {
+        eprintln!("--check requires a source file");
+        return 1;
+    };
+    let diagnostics = std::fs::read_to_string(src)
+        .map_err(|e| chili_core::SpicyError::EvalErr(format!("failed to read '{}', {}", src, e)))
+        .and_then(|source| check_source(state, src, &source));
+    let diagnostics = match diagnostics {
+        Ok(SpicyObj::MixedList(diagnostics)) => diagnostics,
+        Ok(_) => vec![],
+        Err(e) => {
+            eprintln!("\x1b[1;91m{}\x1b[0m", e);
+            return 1;
+        }
+    };
+    for diagnostic in &diagnostics {
+        let d = diagnostic.dict().unwrap();
+        let field = |key: &str| d.get(key).and_then(|v| v.str().ok()).unwrap_or_default();
+        match d.get("pos").and_then(|p| p.to_i64().ok()) {
+            Some(pos) => eprintln!("{}:{}: {}: {}", src, pos, field("kind"), field("msg")),
+            None => eprintln!("{}: {}: {}", src, field("kind"), field("msg")),
+        }
+    }
+    if diagnostics.is_empty() {
+        println!("{}: ok", src);
+        0
+    } else {
+        1
+    }
+}
@@ -0,0 +1,61 @@
+use std::borrow::Cow;
+
+use reedline::{DefaultPrompt, DefaultPromptSegment, Prompt, PromptEditMode, PromptHistorySearch};
+
+// reedline's DefaultPrompt, with a configurable multiline continuation indicator
+pub(crate) struct ChiliPrompt {
+    prompt: DefaultPrompt,
+    continuation: String,
+}
+
+impl ChiliPrompt {
+    pub(crate) fn new(prefix: String, continuation: String) -> Self {
+        ChiliPrompt {
+            prompt: DefaultPrompt::new(
+                DefaultPromptSegment::Basic(prefix),
+                DefaultPromptSegment::Empty,
+            ),
+            continuation,
+        }
+    }
+}
+
+impl Prompt for ChiliPrompt {
+    fn render_prompt_left(&self) -> Cow<'_, str> {
+        self.prompt.render_prompt_left()
+    }
+
+    fn render_prompt_right(&self) -> Cow<'_, str> {
+        self.prompt.render_prompt_right()
+    }
+
+    fn render_prompt_indicator(&self, edit_mode: PromptEditMode) -> Cow<'_, str> {
+        self.prompt.render_prompt_indicator(edit_mode)
+    }
+
+    fn render_prompt_multiline_indicator(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.continuation)
+    }
+
+    fn render_prompt_history_search_indicator(
+        &self,
+        history_search: PromptHistorySearch,
+    ) -> Cow<'_, str> {
+        self.prompt
+            .render_prompt_history_search_indicator(history_search)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reedline::Prompt;
+
+    use super::ChiliPrompt;
+
+    #[test]
+    fn configured_prompts_are_rendered() {
+        let prompt = ChiliPrompt::new("quant> ".to_owned(), ".. ".to_owned());
+        assert_eq!(prompt.render_prompt_left(), "quant> ");
+        assert_eq!(prompt.render_prompt_multiline_indicator(), ".. ");
+    }
+}
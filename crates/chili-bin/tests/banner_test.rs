@@ -0,0 +1,58 @@
+use std::process::Command;
+
+// runs a script that exits straight away, so only the startup output is left
+fn run_exiting_src(flags: &[&str]) -> String {
+    let path = std::env::temp_dir().join(format!("chili_banner_test_{}.chi", std::process::id()));
+    std::fs::write(&path, "exit(0);\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_chili"))
+        .args(flags)
+        .arg(&path)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn no_banner_suppresses_banner() {
+    assert!(run_exiting_src(&[]).contains('█'));
+    for flags in [
+        vec!["--no-banner"],
+        vec!["--no-banner", "--pepper"],
+        vec!["--quiet"],
+    ] {
+        let stdout = run_exiting_src(&flags);
+        assert!(!stdout.contains('█'), "{:?}", flags);
+    }
+}
+
+#[test]
+fn check_prints_only_diagnostics() {
+    let path = std::env::temp_dir().join(format!("chili_check_test_{}.chi", std::process::id()));
+    std::fs::write(&path, "a: 1;\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_chili"))
+        .arg("--check")
+        .arg(&path)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}: ok\n", path.display())
+    );
+}
+
+#[test]
+fn version_prints_only_version() {
+    let output = Command::new(env!("CARGO_BIN_EXE_chili"))
+        .arg("--version")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("chili {}\n", env!("CARGO_PKG_VERSION"))
+    );
+}
@@ -0,0 +1,66 @@
+#![cfg(unix)]
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+fn wait_for_file(path: &Path, timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        if path.exists() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
+
+#[test]
+fn sigterm_runs_exit_hooks() {
+    let dir = std::env::temp_dir().join(format!("chili_headless_test_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let ready = dir.join("ready.csv");
+    let hook = dir.join("hook.csv");
+    let src = dir.join("main.chi");
+    std::fs::write(
+        &src,
+        format!(
+            "on_exit(function() {{ write(\"{}\", ([]a: 1)) }});\nwrite(\"{}\", ([]a: 1));\n",
+            hook.display(),
+            ready.display()
+        ),
+    )
+    .unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_chili"))
+        .arg("--headless")
+        .arg("--no-banner")
+        .arg(&src)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    assert!(wait_for_file(&ready, Duration::from_secs(10)));
+
+    let kill = Command::new("kill")
+        .arg("-TERM")
+        .arg(child.id().to_string())
+        .status()
+        .unwrap();
+    assert!(kill.success());
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        if start.elapsed() > Duration::from_secs(10) {
+            let _ = child.kill();
+            panic!("headless chili did not exit on SIGTERM");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    let hook_ran = hook.exists();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(status.success(), "{:?}", status);
+    assert!(hook_ran);
+}
@@ -0,0 +1,35 @@
+use std::process::Command;
+
+#[test]
+fn script_dir_loads_files_in_name_order() {
+    let dir = std::env::temp_dir().join(format!("chili_script_dir_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.chili"), "f: function(x) { x + 1 };\n").unwrap();
+    // exits with f(6) so the test sees the first file's definition
+    std::fs::write(dir.join("b.chi"), "exit(f(6));\n").unwrap();
+    std::fs::write(dir.join("c.txt"), "not a source file").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_chili"))
+        .arg("--no-banner")
+        .arg("--script-dir")
+        .arg(&dir)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(output.status.code(), Some(7), "{:?}", output);
+}
+
+#[test]
+fn script_dir_exits_on_failure() {
+    let dir = std::env::temp_dir().join(format!("chili_script_dir_fail_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.chi"), "exit(g(6));\n").unwrap();
+    std::fs::write(dir.join("b.chi"), "g: function(x) { x + 1 };\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_chili"))
+        .arg("--no-banner")
+        .arg("--script-dir")
+        .arg(&dir)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+}
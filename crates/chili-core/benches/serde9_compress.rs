@@ -0,0 +1,56 @@
+//! IPC frame compression benchmark.
+//!
+//! Serializes a frame mixing a constant column with a high-cardinality column,
+//! once with compression requested and once without. Compression is decided
+//! per column, so only the constant column is compressed and the "random"
+//! variant should cost about the same as the uncompressed path.
+
+use std::time::Duration;
+
+use chili_core::{SpicyObj, serde9};
+use criterion::{Criterion, criterion_group, criterion_main};
+use polars::prelude::{DataFrame, NamedFrom, Series};
+use std::hint::black_box;
+
+const ROWS: usize = 500_000;
+
+fn random_i64s(n: usize) -> Vec<i64> {
+    (0..n as u64)
+        .map(|i| {
+            i.wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407) as i64
+        })
+        .collect()
+}
+
+fn make_frames() -> (SpicyObj, SpicyObj) {
+    let constant = Series::new("constant".into(), vec![1i64; ROWS]);
+    let random = Series::new("random".into(), random_i64s(ROWS));
+    let mixed = DataFrame::new(ROWS, vec![constant.into(), random.clone().into()]).unwrap();
+    (
+        SpicyObj::DataFrame(mixed),
+        SpicyObj::DataFrame(random.into_frame()),
+    )
+}
+
+fn bench_serialize(c: &mut Criterion) {
+    let (mixed, random) = make_frames();
+
+    let mut group = c.benchmark_group("serde9_compress");
+    group.sample_size(20);
+    group.measurement_time(Duration::from_secs(10));
+
+    for (name, obj) in [("mixed", &mixed), ("random", &random)] {
+        group.bench_function(format!("{name}_compress"), |b| {
+            b.iter(|| black_box(serde9::serialize(obj, true).unwrap()));
+        });
+        group.bench_function(format!("{name}_raw"), |b| {
+            b.iter(|| black_box(serde9::serialize(obj, false).unwrap()));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_serialize);
+criterion_main!(benches);
@@ -103,6 +103,14 @@ pub enum QueryOp {
 }
 
 impl QueryOp {
+    pub fn as_str(&self) -> &str {
+        match self {
+            QueryOp::Select => "select",
+            QueryOp::Update => "update",
+            QueryOp::Delete => "delete",
+        }
+    }
+
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "select" => Some(QueryOp::Select),
@@ -141,6 +149,111 @@ impl AstNode {
     }
 }
 
+fn sexpr(head: &str, nodes: &[&AstNode]) -> String {
+    let mut parts = vec![head.to_owned()];
+    parts.extend(nodes.iter().map(|n| n.to_sexpr()));
+    format!("({})", parts.join(" "))
+}
+
+fn sexpr_block(head: &str, nodes: &[AstNode]) -> String {
+    sexpr(head, &nodes.iter().collect::<Vec<_>>())
+}
+
+impl AstNode {
+    /// Render the node as an s-expression, `(op lhs rhs)` for operators and `(f args..)`
+    /// for calls, so nesting shows how the source was grouped. Non-atom literals only
+    /// show their type, e.g. `<i64s>`.
+    pub fn to_sexpr(&self) -> String {
+        match self {
+            AstNode::SpicyObj(SpicyObj::Fn(f)) if f.is_built_in_fn() => f.fn_body.clone(),
+            AstNode::SpicyObj(SpicyObj::Fn(f)) => {
+                let head = format!("fn [{}]", f.params.join(" "));
+                sexpr_block(&head, &f.nodes)
+            }
+            AstNode::SpicyObj(obj) if obj.is_atom() => obj.to_string(),
+            AstNode::SpicyObj(obj) => format!("<{}>", obj.get_type_name()),
+            AstNode::ShortCircuit {
+                op,
+                left_cond,
+                right_cond,
+            } => sexpr(op, &[left_cond.as_ref(), right_cond.as_ref()]),
+            AstNode::UnaryExp { op, exp } => format!("({} {})", op.to_sexpr(), exp.to_sexpr()),
+            AstNode::BinaryExp { op, lhs, rhs } => {
+                format!("({} {} {})", op.to_sexpr(), lhs.to_sexpr(), rhs.to_sexpr())
+            }
+            AstNode::AssignmentExp { id, exp } => format!("(: {} {})", id, exp.to_sexpr()),
+            AstNode::IndexAssignmentExp { id, indices, exp } => format!(
+                "(: {} {} {})",
+                id,
+                sexpr_block("index", indices),
+                exp.to_sexpr()
+            ),
+            AstNode::Id { name, .. } => name.clone(),
+            AstNode::FnCall { f, args, .. } => sexpr_block(&f.to_sexpr(), args),
+            AstNode::If {
+                cond,
+                nodes,
+                else_nodes,
+            } => format!(
+                "(if {} {} {})",
+                cond.to_sexpr(),
+                sexpr_block("do", nodes),
+                else_nodes.to_sexpr()
+            ),
+            AstNode::While { cond, nodes } => {
+                format!("(while {} {})", cond.to_sexpr(), sexpr_block("do", nodes))
+            }
+            AstNode::IfElse { nodes } => sexpr_block("cond", nodes),
+            AstNode::Try {
+                tries,
+                err_id,
+                catches,
+            } => format!(
+                "(try {} {} {})",
+                sexpr_block("do", tries),
+                err_id,
+                sexpr_block("do", catches)
+            ),
+            AstNode::Return(exp) => sexpr("return", &[exp.as_ref()]),
+            AstNode::Raise(exp) => sexpr("raise", &[exp.as_ref()]),
+            AstNode::DataFrame(nodes) => sexpr_block("table", nodes),
+            AstNode::Matrix(nodes) => sexpr_block("matrix", nodes),
+            AstNode::Dict { keys, values } => {
+                let pairs: Vec<String> = keys
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(k, v)| format!("{}: {}", k, v.to_sexpr()))
+                    .collect();
+                format!("(dict {})", pairs.join(" "))
+            }
+            AstNode::List(nodes) => sexpr_block("list", nodes),
+            AstNode::ColExp { name, exp } => format!("({}: {})", name, exp.to_sexpr()),
+            AstNode::Query {
+                op,
+                op_exp,
+                by_exp,
+                from_exp,
+                where_exp,
+                limited_exp,
+            } => {
+                let mut parts = vec![sexpr_block(op.as_str(), op_exp)];
+                if !by_exp.is_empty() {
+                    parts.push(sexpr_block("by", by_exp));
+                }
+                parts.push(sexpr("from", &[from_exp.as_ref()]));
+                if !where_exp.is_empty() {
+                    parts.push(sexpr_block("where", where_exp));
+                }
+                if let Some(exp) = limited_exp {
+                    parts.push(sexpr("limit", &[exp.as_ref()]));
+                }
+                format!("(query {})", parts.join(" "))
+            }
+            AstNode::DelayedArg => "::".to_owned(),
+        }
+    }
+}
+
 impl Display for AstNode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
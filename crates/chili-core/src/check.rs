@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+use std::fs;
+
+use chili_parser::Language;
+use indexmap::IndexMap;
+
+use crate::ast_node::AstNode;
+use crate::{EngineState, SpicyError, SpicyObj, SpicyResult, Stack, parse};
+
+struct CallSite {
+    name: String,
+    pos: usize,
+    argc: usize,
+    is_empty_call: bool,
+    lang: Language,
+}
+
+fn walk(nodes: &[AstNode], calls: &mut Vec<CallSite>, bound: &mut HashSet<String>) {
+    nodes.iter().for_each(|n| collect(n, calls, bound))
+}
+
+// names bound by the script itself, either assigned or as lambda params, shadow built-in fns
+fn collect(node: &AstNode, calls: &mut Vec<CallSite>, bound: &mut HashSet<String>) {
+    match node {
+        AstNode::SpicyObj(SpicyObj::Fn(f)) if !f.is_built_in_fn() => {
+            bound.extend(f.params.iter().cloned());
+            walk(&f.nodes, calls, bound);
+        }
+        AstNode::SpicyObj(_) | AstNode::Id { .. } | AstNode::DelayedArg => {}
+        AstNode::ShortCircuit {
+            left_cond,
+            right_cond,
+            ..
+        } => {
+            collect(left_cond, calls, bound);
+            collect(right_cond, calls, bound);
+        }
+        AstNode::UnaryExp { op, exp } => {
+            collect(op, calls, bound);
+            collect(exp, calls, bound);
+        }
+        AstNode::BinaryExp { op, lhs, rhs } => {
+            collect(op, calls, bound);
+            collect(lhs, calls, bound);
+            collect(rhs, calls, bound);
+        }
+        AstNode::AssignmentExp { id, exp } => {
+            bound.insert(id.to_owned());
+            collect(exp, calls, bound);
+        }
+        AstNode::IndexAssignmentExp { id, indices, exp } => {
+            bound.insert(id.to_owned());
+            walk(indices, calls, bound);
+            collect(exp, calls, bound);
+        }
+        AstNode::FnCall { lang, pos, f, args } => {
+            if let AstNode::Id { name, .. } = f.as_ref() {
+                calls.push(CallSite {
+                    name: name.to_owned(),
+                    pos: pos.pos,
+                    argc: args.len(),
+                    is_empty_call: args.len() == 1 && matches!(args[0], AstNode::DelayedArg),
+                    lang: *lang,
+                });
+            } else {
+                collect(f, calls, bound);
+            }
+            walk(args, calls, bound);
+        }
+        AstNode::If {
+            cond,
+            nodes,
+            else_nodes,
+        } => {
+            collect(cond, calls, bound);
+            walk(nodes, calls, bound);
+            collect(else_nodes, calls, bound);
+        }
+        AstNode::While { cond, nodes } => {
+            collect(cond, calls, bound);
+            walk(nodes, calls, bound);
+        }
+        AstNode::IfElse { nodes }
+        | AstNode::DataFrame(nodes)
+        | AstNode::Matrix(nodes)
+        | AstNode::List(nodes) => walk(nodes, calls, bound),
+        AstNode::Try {
+            tries,
+            err_id,
+            catches,
+        } => {
+            bound.insert(err_id.to_owned());
+            walk(tries, calls, bound);
+            walk(catches, calls, bound);
+        }
+        AstNode::Return(exp) | AstNode::Raise(exp) => collect(exp, calls, bound),
+        AstNode::Dict { values, .. } => walk(values, calls, bound),
+        AstNode::ColExp { exp, .. } => collect(exp, calls, bound),
+        AstNode::Query {
+            op_exp,
+            by_exp,
+            from_exp,
+            where_exp,
+            limited_exp,
+            ..
+        } => {
+            walk(op_exp, calls, bound);
+            walk(by_exp, calls, bound);
+            collect(from_exp, calls, bound);
+            walk(where_exp, calls, bound);
+            if let Some(exp) = limited_exp {
+                collect(exp, calls, bound);
+            }
+        }
+    }
+}
+
+fn diagnostic(kind: &str, pos: Option<usize>, msg: String) -> SpicyObj {
+    SpicyObj::Dict(IndexMap::from([
+        ("kind".to_owned(), SpicyObj::Symbol(kind.to_owned())),
+        (
+            "pos".to_owned(),
+            pos.map_or(SpicyObj::Null, |p| SpicyObj::I64(p as i64)),
+        ),
+        ("msg".to_owned(), SpicyObj::String(msg)),
+    ]))
+}
+
+/// Parse `source` and check calls to built-in fns for arity mismatches without
+/// evaluating anything. Returns a list of `{kind, pos, msg}` diagnostics, where
+/// `pos` is the byte offset into `source`, empty when the source is clean.
+/// `path` picks the syntax by its `.chi` extension like `import`.
+pub fn check_source(state: &EngineState, path: &str, source: &str) -> SpicyResult<SpicyObj> {
+    let nodes = match parse(source, 0, path) {
+        Ok(nodes) => nodes,
+        Err(e) => {
+            return Ok(SpicyObj::MixedList(vec![diagnostic(
+                "syntax",
+                None,
+                e.to_string(),
+            )]));
+        }
+    };
+    let mut calls = Vec::new();
+    let mut bound = HashSet::new();
+    walk(&nodes, &mut calls, &mut bound);
+
+    let mut diagnostics = Vec::new();
+    for call in calls {
+        if bound.contains(&call.name) {
+            continue;
+        }
+        let Ok(SpicyObj::Fn(f)) = state.get_var(&call.name) else {
+            continue;
+        };
+        if !f.is_built_in_fn() || (f.arg_num == 0 && call.is_empty_call) {
+            continue;
+        }
+        // pepper allows fewer args to project the fn
+        let is_mismatched = match call.lang {
+            Language::Chili => call.argc != f.arg_num,
+            Language::Pepper => call.argc > f.arg_num,
+        };
+        if is_mismatched {
+            diagnostics.push(diagnostic(
+                "arity",
+                Some(call.pos),
+                format!(
+                    "'{}' expects {} arg(s), got {}",
+                    call.name, f.arg_num, call.argc
+                ),
+            ));
+        }
+    }
+    Ok(SpicyObj::MixedList(diagnostics))
+}
+
+// source string, or file path as symbol
+pub fn check(state: &EngineState, _stack: &mut Stack, args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    match args[0] {
+        SpicyObj::String(source) => {
+            let path = if state.is_repl_use_chili_syntax() {
+                "check.chi"
+            } else {
+                "check.pep"
+            };
+            check_source(state, path, source)
+        }
+        SpicyObj::Symbol(path) => {
+            let source = fs::read_to_string(path)
+                .map_err(|e| SpicyError::EvalErr(format!("failed to read '{}', {}", path, e)))?;
+            check_source(state, path, &source)
+        }
+        arg => Err(SpicyError::EvalErr(format!(
+            "expect source string or file path symbol, got '{}'",
+            arg.get_type_name()
+        ))),
+    }
+}
@@ -3,3 +3,5 @@ pub const UNIX_EPOCH_DAY: i32 = 719_163;
 pub const NS_IN_DAY: i64 = 86_400_000_000_000;
 pub const NS_IN_MS: i64 = 1_000_000;
 pub const MS_IN_DAY: i64 = 86_400_000;
+// ns from 1970-01-01 to 2000-01-01, the epoch of q temporals
+pub const NS_DIFF: i64 = 946_684_800_000_000_000;
@@ -45,14 +45,20 @@ use crate::{
     job::{self, Job},
     obj::SpicyObj,
     par_df::{DFType, PartitionedDataFrame},
-    parse, read_chili_ipc_msg, serde6, serde9,
+    parse, read_chili_ipc_msg, serde9,
     side_effect_fn::SIDE_EFFECT_FN,
+    stack::EvalBudget,
+    tls::IpcStream,
     utils::{
         self, MessageType, convert_list_to_df, handle_chili_conn, handle_q_conn, read_q_msg,
         read_q_table_name, send_auth, unpack_socket,
     },
 };
 
+#[cfg(feature = "ipc")]
+use crate::serde6;
+#[cfg(feature = "ipc")]
+use crate::tls::{self, TlsStream};
 use crate::{
     ast_node::AstNode,
     errors::{SpicyError, SpicyResult},
@@ -135,6 +141,18 @@ const PARSE_CACHE_CAPACITY: usize = 256;
 /// Handle numbers must be in the range 0..MAX_HANDLE_NUM.
 const MAX_HANDLE_NUM: usize = 1024;
 
+/// Default nested user fn call depth before eval errors out instead of
+/// overflowing the native stack.
+const DEFAULT_MAX_DEPTH: usize = 37;
+
+/// Total wall-clock budget shared by all exit hooks; hooks still pending when
+/// it runs out are skipped so a hung callback cannot block shutdown.
+pub const EXIT_HOOK_BUDGET: Duration = Duration::from_secs(5);
+
+/// Smallest q IPC compression threshold, shorter messages never compress well and
+/// the compressor needs room for its header.
+const MIN_IPC_COMPRESS_THRESHOLD: usize = 2000;
+
 pub struct EngineState {
     debug: bool,
     vars: RwLock<HashMap<String, SpicyObj>>,
@@ -160,6 +178,8 @@ pub struct EngineState {
     interval: u64,
     /// Memory limit in MB. 0.0 means unlimited.
     memory_limit: f64,
+    /// Max nested user fn call depth; exceeding it raises an eval error.
+    max_depth: usize,
     /// Optional name of a function `(user; handle; query) -> query'` run on
     /// inbound IPC requests before evaluation. Its return value replaces the
     /// query; a hook error denies the request. `None` skips the hook. Only IPC
@@ -174,6 +194,36 @@ pub struct EngineState {
     jobs_deactivate_on_error: RwLock<bool>,
     /// Max outbound frames queued per Publishing subscriber; `0` disables shedding.
     subscriber_queue_max: std::sync::atomic::AtomicI64,
+    /// Callbacks registered via `on_exit`, run in registration order on shutdown.
+    exit_hooks: Mutex<Vec<SpicyObj>>,
+    /// Outbound q IPC messages of at least this many bytes are compressed; `0` disables
+    /// compression. Read on every send so it can be tuned at runtime.
+    ipc_compress_threshold: std::sync::atomic::AtomicUsize,
+    /// When set, accepted IPC connections complete a TLS handshake before auth.
+    #[cfg(feature = "ipc")]
+    tls_config: Option<Arc<rustls::ServerConfig>>,
+    /// Seconds an accepted IPC connection may stay silent before it is dropped; `0` disables.
+    idle_timeout: u64,
+    /// Inbound q IPC messages declaring more bytes than this are rejected before allocating;
+    /// `0` disables the limit.
+    max_msg_bytes: usize,
+    /// Per-user cap in bytes on memory held by that user, the globals they assigned
+    /// plus the result in flight; users without an entry are not tracked.
+    user_quotas: HashMap<String, usize>,
+    /// Global variable name to the quota-tracked user who last assigned it.
+    var_owners: Mutex<HashMap<String, String>>,
+}
+
+// Approximate bytes held by an object, counted towards a user's memory quota.
+fn held_bytes(obj: &SpicyObj) -> usize {
+    match obj {
+        SpicyObj::Series(s) => s.estimated_size(),
+        SpicyObj::DataFrame(df) | SpicyObj::KeyedTable(df, _) => df.estimated_size(),
+        SpicyObj::Matrix(m) => m.len() * size_of::<f64>(),
+        SpicyObj::MixedList(l) => l.iter().map(held_bytes).sum(),
+        SpicyObj::Dict(d) => d.values().map(held_bytes).sum(),
+        _ => 0,
+    }
 }
 
 impl Default for EngineState {
@@ -231,10 +281,24 @@ impl EngineState {
             repl_lang: Language::Chili,
             interval: 0,
             memory_limit: 0.0,
+            max_depth: DEFAULT_MAX_DEPTH,
             pre_eval_hook: RwLock::new(None),
             post_eval_hook: RwLock::new(None),
             jobs_deactivate_on_error: RwLock::new(false),
             subscriber_queue_max: std::sync::atomic::AtomicI64::new(0),
+            exit_hooks: Mutex::new(Vec::new()),
+            #[cfg(feature = "ipc")]
+            ipc_compress_threshold: std::sync::atomic::AtomicUsize::new(
+                serde6::IPC_COMPRESS_THRESHOLD,
+            ),
+            #[cfg(not(feature = "ipc"))]
+            ipc_compress_threshold: std::sync::atomic::AtomicUsize::new(0),
+            #[cfg(feature = "ipc")]
+            tls_config: None,
+            idle_timeout: 0,
+            max_msg_bytes: 0,
+            user_quotas: HashMap::new(),
+            var_owners: Mutex::new(HashMap::new()),
         }
     }
 
@@ -284,6 +348,131 @@ impl EngineState {
         self.memory_limit
     }
 
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Serve IPC over TLS with a PEM certificate chain and private key.
+    #[cfg(feature = "ipc")]
+    pub fn set_tls(&mut self, cert_path: &str, key_path: &str) -> SpicyResult<()> {
+        self.tls_config = Some(tls::load_server_config(cert_path, key_path)?);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ipc"))]
+    pub fn set_tls(&mut self, _cert_path: &str, _key_path: &str) -> SpicyResult<()> {
+        Err(SpicyError::Err("tls is not enabled".to_string()))
+    }
+
+    #[cfg(feature = "ipc")]
+    pub fn is_tls_enabled(&self) -> bool {
+        self.tls_config.is_some()
+    }
+
+    #[cfg(not(feature = "ipc"))]
+    pub fn is_tls_enabled(&self) -> bool {
+        false
+    }
+
+    pub fn set_idle_timeout(&mut self, idle_timeout: u64) {
+        self.idle_timeout = idle_timeout;
+    }
+
+    pub fn get_idle_timeout(&self) -> u64 {
+        self.idle_timeout
+    }
+
+    pub fn set_max_msg_bytes(&mut self, max_msg_bytes: usize) {
+        self.max_msg_bytes = max_msg_bytes;
+    }
+
+    pub fn get_max_msg_bytes(&self) -> usize {
+        self.max_msg_bytes
+    }
+
+    /// Set the memory quota in GB for globals and in-flight results held by `user`.
+    pub fn set_user_quota(&mut self, user: &str, quota: f64) {
+        let bytes = (quota * 1024.0 * 1024.0 * 1024.0) as usize;
+        self.user_quotas.insert(user.to_owned(), bytes);
+    }
+
+    pub fn get_user_quota(&self, user: &str) -> Option<usize> {
+        self.user_quotas.get(user).copied()
+    }
+
+    /// Record `user` as the owner of global `id`, so it counts towards their quota.
+    pub fn claim_var(&self, user: &str, id: &str) {
+        if self.user_quotas.is_empty() {
+            return;
+        }
+        let mut var_owners = self.var_owners.lock();
+        if self.user_quotas.contains_key(user) {
+            var_owners.insert(id.to_owned(), user.to_owned());
+        } else {
+            var_owners.remove(id);
+        }
+    }
+
+    /// Approximate bytes of the globals `user` assigned.
+    pub fn get_user_bytes(&self, user: &str) -> usize {
+        let var_owners = self.var_owners.lock();
+        let vars = self.vars.read();
+        var_owners
+            .iter()
+            .filter(|(_, owner)| owner.as_str() == user)
+            .filter_map(|(id, _)| vars.get(id))
+            .map(held_bytes)
+            .sum()
+    }
+
+    fn check_user_quota(&self, stack: &Stack, result: Option<&SpicyObj>) -> SpicyResult<()> {
+        if let Some(quota) = self.get_user_quota(&stack.user) {
+            let used = self.get_user_bytes(&stack.user) + result.map(held_bytes).unwrap_or(0);
+            if used > quota {
+                return Err(SpicyError::Err(format!(
+                    "memory quota exceeded for user '{}', holding {} bytes, quota is {}",
+                    stack.user, used, quota
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the q IPC compression threshold in bytes, `0` disables compression.
+    pub fn set_compression(&self, threshold: usize) {
+        let threshold = if threshold > 0 && threshold < MIN_IPC_COMPRESS_THRESHOLD {
+            warn!(
+                "compression threshold {} bytes is below minimum {} bytes, rounding up to {} bytes",
+                threshold, MIN_IPC_COMPRESS_THRESHOLD, MIN_IPC_COMPRESS_THRESHOLD
+            );
+            MIN_IPC_COMPRESS_THRESHOLD
+        } else {
+            threshold
+        };
+        self.ipc_compress_threshold
+            .store(threshold, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn get_compression(&self) -> usize {
+        self.ipc_compress_threshold
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Compress a serialized q IPC message if it reaches the configured threshold.
+    pub fn compress_q_msg(&self, v8: Vec<u8>) -> Vec<u8> {
+        match self.get_compression() {
+            0 => v8,
+            #[cfg(feature = "ipc")]
+            threshold => serde6::compress_with_max_size(v8, threshold),
+            #[cfg(not(feature = "ipc"))]
+            _ => v8,
+        }
+    }
+
     pub fn register_fn(&self, map: &LazyLock<HashMap<String, Func>>) {
         let mut vars = self.vars.write();
         map.iter().for_each(|(k, v)| {
@@ -298,9 +487,60 @@ impl EngineState {
     }
 
     pub fn shutdown(&self) {
+        self.run_exit_hooks(EXIT_HOOK_BUDGET);
         self.handle.write().clear();
     }
 
+    /// Register a callback to run on shutdown; returns the number of hooks.
+    pub fn add_exit_hook(&self, f: SpicyObj) -> usize {
+        let mut hooks = self.exit_hooks.lock();
+        hooks.push(f);
+        hooks.len()
+    }
+
+    /// Run and drain the exit hooks, each with no args. Hook errors are logged
+    /// and ignored. When the engine is shared via `set_arc_self`, hooks run on a
+    /// worker thread that is abandoned once `budget` elapses; otherwise they run
+    /// inline and hooks not yet started when `budget` elapses are skipped.
+    pub fn run_exit_hooks(&self, budget: Duration) {
+        let hooks = std::mem::take(&mut *self.exit_hooks.lock());
+        if hooks.is_empty() {
+            return;
+        }
+        let arc_self = self.arc_self.read().clone();
+        match arc_self {
+            Some(state) => {
+                let (tx, rx) = std::sync::mpsc::channel();
+                thread::spawn(move || {
+                    state.eval_exit_hooks(&hooks, budget);
+                    let _ = tx.send(());
+                });
+                if rx.recv_timeout(budget).is_err() {
+                    warn!("exit hooks exceeded budget of {:?}, exiting anyway", budget);
+                }
+            }
+            None => self.eval_exit_hooks(&hooks, budget),
+        }
+    }
+
+    fn eval_exit_hooks(&self, hooks: &[SpicyObj], budget: Duration) {
+        let start = std::time::Instant::now();
+        let mut stack = Stack::new(None, 0, 0, &self.user);
+        for (i, f) in hooks.iter().enumerate() {
+            if start.elapsed() >= budget {
+                warn!(
+                    "exit hooks exceeded budget of {:?}, skipping {} hook(s)",
+                    budget,
+                    hooks.len() - i
+                );
+                return;
+            }
+            if let Err(e) = crate::eval::eval_call(self, &mut stack, f, &Vec::new(), &None, "") {
+                warn!("exit hook #{} failed (ignored): {}", i, e);
+            }
+        }
+    }
+
     pub fn get_displayed_vars(&self) -> SpicyResult<HashMap<String, String>> {
         let mut vars = HashMap::new();
         for (key, obj) in self.vars.read().iter() {
@@ -339,6 +579,7 @@ impl EngineState {
     }
 
     pub fn del_var(&self, id: &str) -> SpicyResult<SpicyObj> {
+        self.var_owners.lock().remove(id);
         let mut vars = self.vars.write();
         Ok(vars.remove(id).unwrap_or(SpicyObj::Null))
     }
@@ -399,14 +640,22 @@ impl EngineState {
     }
 
     /// Evaluate `query` through the pre-eval hook when one is set, then fire the
-    /// post-eval hook. With no hooks, this is equivalent to `eval`.
+    /// post-eval hook. Users over their memory quota are rejected before evaluation,
+    /// and a result that would take them over it is dropped with an error. With no
+    /// hooks or quotas, this is equivalent to `eval`.
     pub fn eval_with_pre_hook(
         &self,
         stack: &mut Stack,
         query: &SpicyObj,
         src: &str,
     ) -> SpicyResult<SpicyObj> {
-        let result = self.eval_with_pre_hook_inner(stack, query, src);
+        let result = self
+            .check_user_quota(stack, None)
+            .and_then(|_| self.eval_with_pre_hook_inner(stack, query, src))
+            .and_then(|obj| {
+                self.check_user_quota(stack, Some(&obj))?;
+                Ok(obj)
+            });
         self.fire_post_eval_hook(stack, query, &result, src);
         result
     }
@@ -659,6 +908,45 @@ impl EngineState {
             .map_err(|e| SpicyError::EvalErr(e.to_string()))
     }
 
+    /// Point-in-time copy of user variables and partitioned table paths, built-in fns excluded.
+    ///
+    /// Both read guards are taken together, `vars` before `par_df`, and held only while cloning
+    /// entries out; the dicts are sorted and built after the locks are released. Nothing else
+    /// takes `vars` while holding `par_df`, so the fixed order cannot deadlock.
+    pub fn snapshot(&self) -> SpicyResult<SpicyObj> {
+        let (mut vars, mut par_df) = {
+            let var_map = self.vars.read();
+            let par_df_map = self.par_df.read();
+            let vars: Vec<(String, SpicyObj)> = var_map
+                .iter()
+                .filter(|(_, v)| !v.fn_().is_ok_and(|f| f.is_built_in_fn()))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            let par_df: Vec<(String, String)> = par_df_map
+                .iter()
+                .map(|(k, v)| (k.clone(), v.path.clone()))
+                .collect();
+            (vars, par_df)
+        };
+        vars.sort_by(|a, b| a.0.cmp(&b.0));
+        par_df.sort();
+        let mut snapshot = IndexMap::new();
+        snapshot.insert(
+            "vars".to_owned(),
+            SpicyObj::Dict(vars.into_iter().collect()),
+        );
+        snapshot.insert(
+            "partitioned".to_owned(),
+            SpicyObj::Dict(
+                par_df
+                    .into_iter()
+                    .map(|(k, p)| (k, SpicyObj::String(p)))
+                    .collect(),
+            ),
+        );
+        Ok(SpicyObj::Dict(snapshot))
+    }
+
     pub fn replay_q_msgs_log(
         &self,
         file: &str,
@@ -735,7 +1023,7 @@ impl EngineState {
 
             if is_sub_all || table_names.contains(&table_name.as_str()) {
                 if count >= tick_count {
-                    let list = serde6::deserialize(&msg, &mut 0, false)?;
+                    let list = utils::deserialize_q(&msg, &mut 0)?;
                     let res = self.eval(&mut Stack::with_handle(handle), &list, "");
                     if res.is_err() {
                         let err = res.err().unwrap();
@@ -909,6 +1197,9 @@ impl EngineState {
         let (ipc_type, socket, version) = match uri.split_once("://") {
             Some((schema, path)) => {
                 if schema == "q" {
+                    if !cfg!(feature = "ipc") {
+                        return Err(SpicyError::EvalErr("q ipc is not enabled".to_owned()));
+                    }
                     (IpcType::Q, path, 6)
                 } else if schema == "chili" {
                     (IpcType::Chili, path, 9)
@@ -1030,6 +1321,57 @@ impl EngineState {
         }
     }
 
+    pub fn get_ipc_version(&self, handle_num: &i64) -> SpicyResult<SpicyObj> {
+        match self.handle.read().get(handle_num) {
+            Some(h) => Ok(SpicyObj::I64(match h.ipc_type {
+                IpcType::Q => 6,
+                IpcType::Chili => 9,
+            })),
+            None => Err(SpicyError::InvalidHandleErr(*handle_num)),
+        }
+    }
+
+    /// Pin the IPC version of an outgoing handle, reconnecting it when the version
+    /// changes. The remote has to speak the pinned version, e.g. a chili process
+    /// accepts both 6 and 9, while a q process only accepts 6.
+    pub fn set_ipc_version(&self, handle_num: &i64, version: i64) -> SpicyResult<SpicyObj> {
+        let ipc_type = match version {
+            6 => IpcType::Q,
+            9 => IpcType::Chili,
+            _ => {
+                return Err(SpicyError::EvalErr(format!(
+                    "expect ipc version 6 or 9, got {}",
+                    version
+                )));
+            }
+        };
+        let prev_uri = {
+            let mut handles = self.handle.write();
+            let h = handles
+                .get_mut(handle_num)
+                .ok_or(SpicyError::InvalidHandleErr(*handle_num))?;
+            if h.conn_type != ConnType::Outgoing {
+                return Err(SpicyError::EvalErr(format!(
+                    "requires an outgoing connection to set ipc version, got {:?}",
+                    h.conn_type
+                )));
+            }
+            if h.ipc_type == ipc_type {
+                return Ok(SpicyObj::Null);
+            }
+            let (_, socket) = h.uri.split_once("://").unwrap_or_default();
+            let uri = format!("{}://{}", ipc_type, socket);
+            std::mem::replace(&mut h.uri, uri)
+        };
+        if let Err(e) = self.open_handle("", *handle_num) {
+            if let Some(h) = self.handle.write().get_mut(handle_num) {
+                h.uri = prev_uri;
+            }
+            return Err(e);
+        }
+        Ok(SpicyObj::Null)
+    }
+
     pub fn exists_handle(&self, handle_num: &i64) -> SpicyResult<SpicyObj> {
         Ok(SpicyObj::Boolean(
             self.handle.read().contains_key(handle_num),
@@ -1257,8 +1599,12 @@ impl EngineState {
                     match msg {
                         SpicyObj::Symbol(_) | SpicyObj::String(_) | SpicyObj::MixedList(_) => {
                             if *ipc_type == IpcType::Q {
-                                let v8 = serde6::serialize(msg)?;
-                                let v8 = if !*is_local { serde6::compress(v8) } else { v8 };
+                                let v8 = utils::serialize_q(msg)?;
+                                let v8 = if !*is_local {
+                                    self.compress_q_msg(v8)
+                                } else {
+                                    v8
+                                };
                                 if let Err(e) = utils::write_q_ipc_msg(rw, &v8, MessageType::Sync) {
                                     *conn_type = ConnType::Disconnected;
                                     return Err(SpicyError::Err(e.to_string()));
@@ -1267,9 +1613,20 @@ impl EngineState {
                                 let mut header = [0u8; 8];
                                 rw.read_exact(&mut header)
                                     .map_err(|e| SpicyError::Err(e.to_string()))?;
-                                let (message_type, len, compression_mode) =
+                                let (message_type, len, compression_mode, order) =
                                     utils::decode_header6(&header);
-                                let any = read_q_msg(rw, len - 8, compression_mode)?;
+                                let any = read_q_msg(
+                                    rw,
+                                    len.saturating_sub(8),
+                                    compression_mode,
+                                    order,
+                                    self.max_msg_bytes,
+                                )
+                                .inspect_err(|e| {
+                                    if let SpicyError::DeserializationErr(_) = e {
+                                        *conn_type = ConnType::Disconnected;
+                                    }
+                                })?;
                                 if message_type == MessageType::Response {
                                     Ok(any)
                                 } else {
@@ -1416,8 +1773,12 @@ impl EngineState {
                 match msg {
                     SpicyObj::Symbol(_) | SpicyObj::String(_) | SpicyObj::MixedList(_) => {
                         if *ipc_type == IpcType::Q {
-                            let v8 = serde6::serialize(msg)?;
-                            let v8 = if !*is_local { serde6::compress(v8) } else { v8 };
+                            let v8 = utils::serialize_q(msg)?;
+                            let v8 = if !*is_local {
+                                self.compress_q_msg(v8)
+                            } else {
+                                v8
+                            };
                             if let Err(e) = utils::write_q_ipc_msg(rw, &v8, MessageType::Async) {
                                 disconnected = true;
                                 return Err(SpicyError::Err(e.to_string()));
@@ -1484,8 +1845,12 @@ impl EngineState {
         let result: SpicyResult<SpicyObj> = (|| match msg {
             SpicyObj::Symbol(_) | SpicyObj::String(_) | SpicyObj::MixedList(_) => {
                 if *ipc_type == IpcType::Q {
-                    let v8 = serde6::serialize(msg)?;
-                    let v8 = if !*is_local { serde6::compress(v8) } else { v8 };
+                    let v8 = utils::serialize_q(msg)?;
+                    let v8 = if !*is_local {
+                        self.compress_q_msg(v8)
+                    } else {
+                        v8
+                    };
                     if let Err(e) = utils::write_q_ipc_msg(rw, &v8, MessageType::Async) {
                         disconnected = true;
                         return Err(SpicyError::Err(e.to_string()));
@@ -1823,6 +2188,13 @@ impl EngineState {
         Ok(())
     }
 
+    pub fn is_publishing_handle(&self, h: &i64) -> bool {
+        self.handle
+            .read()
+            .get(h)
+            .is_some_and(|handle| handle.conn_type == ConnType::Publishing)
+    }
+
     pub fn handle_subscriber(&self, h: &i64) -> SpicyResult<()> {
         let queue_max = self
             .subscriber_queue_max
@@ -2036,6 +2408,31 @@ impl EngineState {
         Ok(obj)
     }
 
+    /// Parse and evaluate `source` in the REPL syntax, returning the value of the last
+    /// expression. This is the entry point for embedding the engine in a rust program.
+    pub fn eval_str(&self, source: &str) -> SpicyResult<SpicyObj> {
+        let nodes = self.parse("", source)?;
+        self.eval_ast(nodes, "", source)
+    }
+
+    /// Like `eval_str`, but aborts once `budget` is exceeded. Nested fn calls share the
+    /// budget; the depth guard still applies.
+    pub fn eval_with_budget(&self, source: &str, budget: EvalBudget) -> SpicyResult<SpicyObj> {
+        let mut stack = Stack::new(None, 0, 0, "");
+        stack.budget = Some(budget);
+        self.eval_str_on(&mut stack, source)
+    }
+
+    // evaluates in `stack`, so its handle, user and budget apply
+    pub(crate) fn eval_str_on(&self, stack: &mut Stack, source: &str) -> SpicyResult<SpicyObj> {
+        let nodes = self.parse("", source)?;
+        let mut obj = SpicyObj::Null;
+        for node in nodes {
+            obj = eval_by_node(self, stack, &node, source, None)?;
+        }
+        Ok(obj)
+    }
+
     pub fn import_source_path(&self, relative_src_path: &str, path: &str) -> SpicyResult<SpicyObj> {
         #[cfg(target_os = "windows")]
         let full_path = {
@@ -2753,7 +3150,7 @@ impl EngineState {
         Ok(())
     }
 
-    pub fn validate_auth_token(&self, stream: &mut TcpStream, users: &[String]) -> AuthInfo {
+    pub fn validate_auth_token(&self, stream: &mut IpcStream, users: &[String]) -> AuthInfo {
         let mut default_auth = AuthInfo {
             username: String::from("anonymous"),
             is_authenticated: false,
@@ -2942,139 +3339,169 @@ impl EngineState {
     }
 
     /// Accept incoming connections on `listener` until the process exits.
+    ///
+    /// Each connection is set up on its own thread, so a peer that stalls the TLS
+    /// handshake or auth never holds up the ones behind it.
     pub fn run_accept_loop(self: &Arc<Self>, listener: TcpListener, users: Vec<String>) {
+        let users = Arc::new(users);
         for stream in listener.incoming() {
-            let state_tcp = Arc::clone(self);
-            let mut stream = match stream {
+            let stream = match stream {
                 Ok(s) => s,
                 Err(e) => {
                     info!("accept failed, skipping: {}", e);
                     continue;
                 }
             };
-            let auth_info = state_tcp.validate_auth_token(&mut stream, &users);
-            if !auth_info.is_authenticated {
-                info!(
-                    "{}@{} failed to authenticate, disconnecting...",
-                    auth_info.username,
-                    stream
-                        .peer_addr()
-                        .map(|a| a.to_string())
-                        .unwrap_or_else(|_| "<unknown>".into())
-                );
-                let _ = stream.shutdown(std::net::Shutdown::Both);
-                continue;
-            }
+            let state_tcp = Arc::clone(self);
+            let users = Arc::clone(&users);
+            thread::spawn(move || state_tcp.serve_conn(stream, &users));
+        }
+    }
+
+    fn serve_conn(self: &Arc<Self>, stream: TcpStream, users: &[String]) {
+        // handshake before auth, so credentials never cross the wire in cleartext
+        #[cfg(feature = "ipc")]
+        let mut stream = match &self.tls_config {
+            Some(config) => match TlsStream::accept(Arc::clone(config), stream) {
+                Ok(s) => IpcStream::Tls(s),
+                Err(e) => {
+                    info!("tls handshake failed, dropping connection: {}", e);
+                    return;
+                }
+            },
+            None => IpcStream::Tcp(stream),
+        };
+        #[cfg(not(feature = "ipc"))]
+        let mut stream = IpcStream::Tcp(stream);
+        let auth_info = self.validate_auth_token(&mut stream, users);
+        if !auth_info.is_authenticated {
             info!(
-                "{}@{} connected",
+                "{}@{} failed to authenticate, disconnecting...",
                 auth_info.username,
                 stream
                     .peer_addr()
                     .map(|a| a.to_string())
                     .unwrap_or_else(|_| "<unknown>".into())
             );
-            let version_byte = if auth_info.version <= 6 { 6u8 } else { 9u8 };
-            if let Err(e) = stream.write_all(&[version_byte]) {
-                info!("version-byte write failed, dropping connection: {}", e);
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+            return;
+        }
+        info!(
+            "{}@{} connected",
+            auth_info.username,
+            stream
+                .peer_addr()
+                .map(|a| a.to_string())
+                .unwrap_or_else(|_| "<unknown>".into())
+        );
+        let version_byte = if auth_info.version <= 6 { 6u8 } else { 9u8 };
+        if let Err(e) = stream.write_all(&[version_byte]) {
+            info!("version-byte write failed, dropping connection: {}", e);
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+            return;
+        }
+        // if not set, small package will be pending for 40ms
+        if let Err(e) = stream.set_nodelay(true) {
+            // Non-fatal — connection still usable, just slower.
+            info!("set_nodelay failed (continuing): {}", e);
+        }
+        // the timeout applies per read, so it only fires once the peer has sent
+        // nothing for that long, never during a slow but active transfer
+        if self.idle_timeout > 0
+            && let Err(e) = stream.set_read_timeout(Some(Duration::from_secs(self.idle_timeout)))
+        {
+            info!("set_read_timeout failed (continuing): {}", e);
+        }
+        let peer_addr = match stream.peer_addr() {
+            Ok(a) => a.to_string(),
+            Err(e) => {
+                info!("peer_addr failed, dropping connection: {}", e);
                 let _ = stream.shutdown(std::net::Shutdown::Both);
-                continue;
+                return;
             }
-            // if not set, small package will be pending for 40ms
-            if let Err(e) = stream.set_nodelay(true) {
-                // Non-fatal — connection still usable, just slower.
-                info!("set_nodelay failed (continuing): {}", e);
+        };
+        let ipc_type = match IpcType::from_u8(auth_info.version) {
+            Some(t) => t,
+            None => {
+                // Defensive — validate_auth_token already gates `version < 3`,
+                // so reaching here means the caller passed a value outside
+                // the IpcType enum range. Log and drop rather than abort
+                // the listener (Sprint 22 MC-1).
+                info!(
+                    "unsupported ipc version {}, dropping connection from {}",
+                    auth_info.version, peer_addr
+                );
+                let _ = stream.shutdown(std::net::Shutdown::Both);
+                return;
             }
-            let peer_addr = match stream.peer_addr() {
-                Ok(a) => a.to_string(),
-                Err(e) => {
-                    info!("peer_addr failed, dropping connection: {}", e);
-                    let _ = stream.shutdown(std::net::Shutdown::Both);
-                    continue;
-                }
-            };
-            let ipc_type = match IpcType::from_u8(auth_info.version) {
-                Some(t) => t,
-                None => {
-                    // Defensive — validate_auth_token already gates `version < 3`,
-                    // so reaching here means the caller passed a value outside
-                    // the IpcType enum range. Log and drop rather than abort
-                    // the listener (Sprint 22 MC-1).
-                    info!(
-                        "unsupported ipc version {}, dropping connection from {}",
-                        auth_info.version, peer_addr
-                    );
-                    let _ = stream.shutdown(std::net::Shutdown::Both);
-                    continue;
-                }
-            };
-            let cloned_stream = match stream.try_clone() {
-                Ok(s) => s,
-                Err(e) => {
-                    info!("stream try_clone failed, dropping connection: {}", e);
-                    let _ = stream.shutdown(std::net::Shutdown::Both);
-                    continue;
-                }
-            };
-            let subscriber_queue_max = state_tcp
-                .subscriber_queue_max
-                .load(std::sync::atomic::Ordering::Relaxed);
-            let shutdown_dup = if subscriber_queue_max > 0 {
-                stream.try_clone().ok()
-            } else {
-                None
-            };
-            let h = match state_tcp.set_handle(
-                Some(Box::new(cloned_stream)),
-                &peer_addr,
-                &format!("{}://{}", ipc_type, peer_addr,),
-                false,
-                ipc_type,
-                ConnType::Incoming,
-                0,
-            ) {
-                Ok(h) => h,
-                Err(e) => {
-                    info!("set_handle failed, dropping connection: {}", e);
-                    let _ = stream.shutdown(std::net::Shutdown::Both);
-                    continue;
-                }
-            };
-            let h_i64 = match h.to_i64() {
-                Ok(i) => i,
-                Err(e) => {
-                    // set_handle returns a SpicyObj::I64 by contract; if this
-                    // ever fails it's a logic bug, not a network error. Log
-                    // and drop the connection rather than panicking the listener.
-                    info!("handle slot returned non-i64 (logic bug): {}", e);
-                    let _ = stream.shutdown(std::net::Shutdown::Both);
-                    continue;
-                }
-            };
-            if let Some(s) = shutdown_dup {
-                state_tcp.set_shutdown_handle(&h_i64, s);
+        };
+        let cloned_stream = match stream.try_clone() {
+            Ok(s) => s,
+            Err(e) => {
+                info!("stream try_clone failed, dropping connection: {}", e);
+                let _ = stream.shutdown(std::net::Shutdown::Both);
+                return;
             }
-            if auth_info.version <= 6 {
-                let mut stream = Box::new(stream);
-                thread::spawn(move || {
-                    utils::handle_q_conn(
-                        &mut stream,
-                        peer_addr.starts_with("127.0.0.1"),
-                        h_i64,
-                        state_tcp,
-                        &auth_info.username,
-                    )
-                });
-            } else {
-                thread::spawn(move || {
-                    utils::handle_chili_conn(
-                        &mut stream,
-                        peer_addr.starts_with("127.0.0.1"),
-                        h_i64,
-                        state_tcp,
-                        &auth_info.username,
-                    )
-                });
+        };
+        let subscriber_queue_max = self
+            .subscriber_queue_max
+            .load(std::sync::atomic::Ordering::Relaxed);
+        let shutdown_dup = if subscriber_queue_max > 0 {
+            stream.tcp().try_clone().ok()
+        } else {
+            None
+        };
+        let h = match self.set_handle(
+            Some(Box::new(cloned_stream)),
+            &peer_addr,
+            &format!("{}://{}", ipc_type, peer_addr,),
+            false,
+            ipc_type,
+            ConnType::Incoming,
+            0,
+        ) {
+            Ok(h) => h,
+            Err(e) => {
+                info!("set_handle failed, dropping connection: {}", e);
+                let _ = stream.shutdown(std::net::Shutdown::Both);
+                return;
             }
+        };
+        let h_i64 = match h.to_i64() {
+            Ok(i) => i,
+            Err(e) => {
+                // set_handle returns a SpicyObj::I64 by contract; if this
+                // ever fails it's a logic bug, not a network error. Log
+                // and drop the connection rather than panicking the listener.
+                info!("handle slot returned non-i64 (logic bug): {}", e);
+                let _ = stream.shutdown(std::net::Shutdown::Both);
+                return;
+            }
+        };
+        if let Some(s) = shutdown_dup {
+            self.set_shutdown_handle(&h_i64, s);
+        }
+        let is_local = peer_addr.starts_with("127.0.0.1");
+        if auth_info.version <= 6 {
+            let mut stream = Box::new(stream);
+            utils::handle_q_conn(
+                &mut stream,
+                is_local,
+                h_i64,
+                Arc::clone(self),
+                &auth_info.username,
+            );
+            // the handle keeps a dup of the socket, close it for the peer too
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+        } else {
+            utils::handle_chili_conn(
+                &mut stream,
+                is_local,
+                h_i64,
+                Arc::clone(self),
+                &auth_info.username,
+            );
+            let _ = stream.shutdown(std::net::Shutdown::Both);
         }
     }
 
@@ -3101,12 +3528,13 @@ impl EngineState {
     /// `memory_limit` is 0.0.
     ///
     /// The monitor checks every 3 seconds. If memory exceeds the limit
-    /// for two consecutive checks, the process exits.
+    /// for two consecutive checks, the exit hooks run and the process exits.
     pub fn start_memory_monitor(&self) {
         if self.memory_limit <= 0.0 {
             return;
         }
         let memory_limit = self.memory_limit;
+        let arc_self = self.arc_self.read().clone();
         let pid = sysinfo::get_current_pid().expect("Failed to get current pid");
         thread::spawn(move || {
             let mut sys = sysinfo::System::new();
@@ -3121,6 +3549,9 @@ impl EngineState {
                                 "memory usage {:>6.2} MB exceeded limit {:>6.2} MB, exiting",
                                 memory_usage_mb, memory_limit
                             );
+                            if let Some(state) = &arc_self {
+                                state.shutdown();
+                            }
                             process::exit(1);
                         } else {
                             warn!(
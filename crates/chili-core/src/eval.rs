@@ -52,9 +52,9 @@ pub fn eval_fn_call(
                 stack.h,
                 &stack.user,
             );
-            // TODO: review this limit
-            if new_stack.stack_layer >= 37 {
-                return Err(SpicyError::EvalErr("Stack overflow reached".to_owned()));
+            new_stack.budget = stack.budget.clone();
+            if new_stack.stack_layer >= state.get_max_depth() {
+                return Err(SpicyError::Err("max eval depth exceeded".to_owned()));
             }
 
             for (i, param) in func.params.iter().enumerate() {
@@ -106,6 +106,24 @@ pub fn eval_by_node(
     node: &AstNode,
     src: &str,
     columns: Option<&Vec<String>>,
+) -> SpicyResult<SpicyObj> {
+    match stack.budget.clone() {
+        None => eval_node(state, stack, node, src, columns),
+        Some(budget) => {
+            budget.check_time()?;
+            let obj = eval_node(state, stack, node, src, columns)?;
+            budget.check_obj(&obj)?;
+            Ok(obj)
+        }
+    }
+}
+
+fn eval_node(
+    state: &EngineState,
+    stack: &mut Stack,
+    node: &AstNode,
+    src: &str,
+    columns: Option<&Vec<String>>,
 ) -> SpicyResult<SpicyObj> {
     match node {
         AstNode::UnaryExp { op: f, exp } => {
@@ -126,6 +144,7 @@ pub fn eval_by_node(
                 stack.set_var(id, obj.clone());
             } else {
                 state.set_var(id, obj.clone())?;
+                state.claim_var(&stack.user, id);
             }
             Ok(obj)
         }
@@ -142,6 +161,7 @@ pub fn eval_by_node(
                 stack.set_var(id, obj);
             } else {
                 state.set_var(id, obj)?;
+                state.claim_var(&stack.user, id);
             }
             Ok(SpicyObj::Null)
         }
@@ -503,7 +523,7 @@ pub fn eval_for_ide(
         .map_err(|e| SpicyError::EvalErr(e.to_string()))?;
     let obj = state.eval_ast(ast, "", src)?;
     match obj {
-        SpicyObj::DataFrame(df) => {
+        SpicyObj::DataFrame(df) | SpicyObj::KeyedTable(df, _) => {
             let df = df.slice(0, limit);
             Ok(SpicyObj::DataFrame(df))
         }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use polars::lazy::dsl::col;
 use polars::lazy::frame::IntoLazy;
 use polars::prelude::Expr;
@@ -317,6 +319,9 @@ pub fn eval_fn_query(
     } else {
         // update by => with_columns col("abc").over(partition_by);
         if *query_op == QueryOp::Select {
+            // output columns are the by columns followed by the selections, each in the order
+            // written, so explicitly named columns must not collide
+            check_select_names(&group_by_exprs, &op_exprs)?;
             if group_by.size() > 0 {
                 if op.size() == 0 {
                     lf = lf.group_by_stable(group_by_exprs).agg(&[col("*").last()]);
@@ -394,6 +399,24 @@ pub fn eval_fn_query(
     }
 }
 
+// only aliased and plain column exprs have a name known before collecting
+fn check_select_names(group_by_exprs: &[Expr], op_exprs: &[Expr]) -> SpicyResult<()> {
+    let mut names: HashSet<&str> = HashSet::new();
+    for expr in group_by_exprs.iter().chain(op_exprs.iter()) {
+        let name = match expr {
+            Expr::Alias(_, name) | Expr::Column(name) => name.as_str(),
+            _ => continue,
+        };
+        if !names.insert(name) {
+            return Err(SpicyError::EvalErr(format!(
+                "duplicate column name '{}' in select, rename with 'name: expr' or as(expr, name)",
+                name
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn get_dataframe(state: &EngineState, args: &SpicyObj) -> SpicyResult<SpicyObj> {
     match args {
         SpicyObj::DataFrame(_) => Ok(args.clone()),
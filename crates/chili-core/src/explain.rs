@@ -0,0 +1,95 @@
+use chili_parser::Language;
+
+use crate::ast_node::AstNode;
+use crate::obj::SpicyObj;
+
+// mirrors eval_by_node, operands are evaluated before the op, lhs before rhs
+fn collect_steps(node: &AstNode, steps: &mut Vec<String>) {
+    match node {
+        AstNode::SpicyObj(_) | AstNode::Id { .. } | AstNode::DelayedArg => {}
+        AstNode::UnaryExp { exp, .. } => {
+            collect_steps(exp, steps);
+            steps.push(node.to_sexpr());
+        }
+        AstNode::BinaryExp { lhs, rhs, .. } => {
+            collect_steps(lhs, steps);
+            collect_steps(rhs, steps);
+            steps.push(node.to_sexpr());
+        }
+        AstNode::ShortCircuit {
+            left_cond,
+            right_cond,
+            ..
+        } => {
+            collect_steps(left_cond, steps);
+            collect_steps(right_cond, steps);
+            steps.push(node.to_sexpr());
+        }
+        AstNode::FnCall { f, args, .. } => {
+            collect_steps(f, steps);
+            args.iter().for_each(|a| collect_steps(a, steps));
+            steps.push(node.to_sexpr());
+        }
+        AstNode::AssignmentExp { exp, .. } | AstNode::ColExp { exp, .. } => {
+            collect_steps(exp, steps);
+            steps.push(node.to_sexpr());
+        }
+        AstNode::IndexAssignmentExp { indices, exp, .. } => {
+            collect_steps(exp, steps);
+            indices.iter().for_each(|i| collect_steps(i, steps));
+            steps.push(node.to_sexpr());
+        }
+        AstNode::Return(exp) | AstNode::Raise(exp) => {
+            collect_steps(exp, steps);
+            steps.push(node.to_sexpr());
+        }
+        AstNode::DataFrame(nodes) | AstNode::Matrix(nodes) | AstNode::List(nodes) => {
+            nodes.iter().for_each(|n| collect_steps(n, steps))
+        }
+        AstNode::Dict { values, .. } => values.iter().for_each(|n| collect_steps(n, steps)),
+        // branches and queries run conditionally or inside polars, show them as one step
+        AstNode::If { .. }
+        | AstNode::While { .. }
+        | AstNode::IfElse { .. }
+        | AstNode::Try { .. }
+        | AstNode::Query { .. } => steps.push(node.to_sexpr()),
+    }
+}
+
+/// The evaluation order of each parsed statement, operands before the op that consumes them.
+/// Lambda bodies are not expanded, they run when called.
+pub fn eval_order(node: &AstNode) -> Vec<String> {
+    let mut steps = Vec::new();
+    if !matches!(node, AstNode::SpicyObj(SpicyObj::Fn(_))) {
+        collect_steps(node, &mut steps);
+    }
+    steps
+}
+
+/// Describe how `nodes` are grouped and evaluated for `--explain`, one line per statement
+/// followed by its numbered steps.
+pub fn explain(lang: Language, nodes: &[AstNode]) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{} binary ops have no precedence and group {}",
+        if lang == Language::Chili {
+            "chili"
+        } else {
+            "pepper"
+        },
+        if lang.is_right_to_left() {
+            "right to left"
+        } else {
+            "left to right"
+        }
+    )];
+    for node in nodes {
+        lines.push(node.to_sexpr());
+        lines.extend(
+            eval_order(node)
+                .into_iter()
+                .enumerate()
+                .map(|(i, step)| format!("  {}. {}", i + 1, step)),
+        );
+    }
+    lines
+}
@@ -2,32 +2,41 @@ mod arg_type;
 mod ast_node;
 mod authinfo;
 mod broker;
+mod check;
 mod engine_state;
 mod errors;
 mod eval;
 mod eval_query;
+mod explain;
 mod func;
 mod io;
 mod job;
 mod obj;
 mod par_df;
 mod parser;
+#[cfg(feature = "ipc")]
 pub mod serde6;
 pub mod serde9;
 mod side_effect_fn;
 mod stack;
+mod tls;
 pub mod utils;
 pub use arg_type::ArgType;
+pub use check::check_source;
 pub use engine_state::EngineState;
 pub use engine_state::ReadWrite;
 pub use engine_state::{ConnType, IpcType, SubFilter, Subscriber};
 pub use errors::{SpicyError, SpicyResult};
 pub use eval::at;
+pub use explain::{eval_order, explain};
 pub use func::{Func, FuncType};
 pub use job::{Job, get_local_now_ns};
-pub use obj::SpicyObj;
+pub use obj::{SpicyObj, get_max_display_depth, set_max_display_depth};
 pub use parser::parse;
-pub use stack::Stack;
+pub use stack::{EvalBudget, Stack};
+pub use tls::IpcStream;
+#[cfg(feature = "ipc")]
+pub use tls::{TlsStream, load_server_config};
 pub use utils::{MessageType, read_chili_ipc_msg, read_q_msg, write_chili_ipc_msg};
 pub mod constant;
 pub use arg_type::validate_args;
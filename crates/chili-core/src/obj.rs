@@ -2,7 +2,7 @@ use crate::constant::{NS_IN_DAY, NS_IN_MS, UNIX_EPOCH_DAY};
 use crate::errors::SpicyResult;
 use crate::par_df::PartitionedDataFrame;
 use crate::{errors::SpicyError, func::Func};
-use chrono::{DateTime, Datelike, NaiveDate};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeDelta};
 use indexmap::IndexMap;
 use ndarray::ArcArray2;
 use polars::datatypes::{AnyValue, DataType, PolarsNumericType, TimeUnit};
@@ -16,8 +16,27 @@ use polars_arrow::array::{FixedSizeListArray, ValueSize};
 use rayon::iter::ParallelIterator;
 use std::fmt;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{fmt::Display, str::FromStr};
 
+const DEFAULT_MAX_DISPLAY_DEPTH: usize = 3;
+
+static MAX_DISPLAY_DEPTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_DISPLAY_DEPTH);
+
+/// Set how many levels of nested lists and dicts are displayed before `...`.
+pub fn set_max_display_depth(depth: usize) {
+    MAX_DISPLAY_DEPTH.store(depth, Ordering::Relaxed);
+}
+
+pub fn get_max_display_depth() -> usize {
+    MAX_DISPLAY_DEPTH.load(Ordering::Relaxed)
+}
+
+enum DisplayPiece<'a> {
+    Obj(&'a SpicyObj, usize),
+    Text(String),
+}
+
 #[derive(Clone)]
 pub enum SpicyObj {
     Boolean(bool),  // -1
@@ -45,6 +64,7 @@ pub enum SpicyObj {
     MixedList(Vec<SpicyObj>),         // 90
     Dict(IndexMap<String, SpicyObj>), // 91 -> skip Dataframe
     DataFrame(DataFrame),             // 92 -> Arrow IPC
+    KeyedTable(DataFrame, usize),     // 99 -> leading columns are keys, q keyed table
     LazyFrame(LazyFrame),
 
     Fn(Func), // -102 => string
@@ -79,6 +99,7 @@ impl PartialEq for SpicyObj {
             (SpicyObj::MixedList(a), SpicyObj::MixedList(b)) => a == b,
             (SpicyObj::Dict(a), SpicyObj::Dict(b)) => a == b,
             (SpicyObj::DataFrame(a), SpicyObj::DataFrame(b)) => a == b,
+            (SpicyObj::KeyedTable(a, n), SpicyObj::KeyedTable(b, m)) => n == m && a == b,
             (SpicyObj::Fn(a), SpicyObj::Fn(b)) => a == b,
             (SpicyObj::Err(a), SpicyObj::Err(b)) => a == b,
             (SpicyObj::Return(a), SpicyObj::Return(b)) => a == b,
@@ -114,6 +135,9 @@ impl Debug for SpicyObj {
             SpicyObj::MixedList(spicy_objs) => write!(f, "MixedList({:?})", spicy_objs),
             SpicyObj::Dict(index_map) => write!(f, "Dict({:?})", index_map),
             SpicyObj::DataFrame(data_frame) => write!(f, "DataFrame({:?})", data_frame),
+            SpicyObj::KeyedTable(data_frame, keys) => {
+                write!(f, "KeyedTable({:?}, {:?})", data_frame, keys)
+            }
             SpicyObj::Fn(func) => write!(f, "Fn({:?})", func),
             SpicyObj::Err(err) => write!(f, "Err({:?})", err),
             SpicyObj::Return(spicy_obj) => write!(f, "Return({:?})", spicy_obj),
@@ -160,6 +184,8 @@ impl SpicyObj {
                 }
                 Ok(length)
             }
+            // 99 + key table + value table
+            SpicyObj::KeyedTable(df, _) => Ok(SpicyObj::DataFrame(df.clone()).q6_len()? + 16),
             SpicyObj::Null => Ok(2),
             SpicyObj::Dict(dict) => {
                 let mut length = 13;
@@ -922,6 +948,7 @@ impl SpicyObj {
             SpicyObj::Dict(_) => 91,
             SpicyObj::DataFrame(_) => 92,
             SpicyObj::Matrix(_) => 94,
+            SpicyObj::KeyedTable(_, _) => 99,
             SpicyObj::Null => 0,
             SpicyObj::Fn(_) => -102,
             SpicyObj::Err(_) => 128,
@@ -1207,6 +1234,7 @@ impl SpicyObj {
             SpicyObj::Matrix(_) => "matrix".to_owned(),
             SpicyObj::Dict(_) => "dict".to_owned(),
             SpicyObj::DataFrame(_) => "df".to_owned(),
+            SpicyObj::KeyedTable(_, _) => "keyed".to_owned(),
             SpicyObj::LazyFrame(_) => "lf".to_owned(),
             SpicyObj::Fn(_) => "fn".to_owned(),
             SpicyObj::Err(_) => "err".to_owned(),
@@ -1234,7 +1262,11 @@ impl SpicyObj {
         } else {
             time
         };
+        // hh:mm is minute precision, seconds default to 0
         let v: Vec<&str> = time.split(":").collect();
+        if v.len() != 2 && v.len() != 3 {
+            return Err(err());
+        }
         let hh = v[0].parse::<i64>().map_err(|_| err())?;
         if hh > 23 {
             return Err(err());
@@ -1243,7 +1275,10 @@ impl SpicyObj {
         if mm > 59 {
             return Err(err());
         }
-        let ss = v[2].parse::<i64>().map_err(|_| err())?;
+        let ss = match v.get(2) {
+            Some(ss) => ss.parse::<i64>().map_err(|_| err())?,
+            None => 0,
+        };
         if ss > 59 {
             return Err(err());
         }
@@ -1367,6 +1402,164 @@ impl SpicyObj {
             _ => format!("{}", self),
         }
     }
+
+    // nested lists and dicts render inline up to the max display depth, walked with an
+    // explicit stack so pathological nesting can't overflow
+    pub fn to_nested_string(&self) -> String {
+        let max_depth = get_max_display_depth();
+        let mut output = String::new();
+        let mut stack = vec![DisplayPiece::Obj(self, 1)];
+        while let Some(piece) = stack.pop() {
+            let (obj, depth) = match piece {
+                DisplayPiece::Text(s) => {
+                    output.push_str(&s);
+                    continue;
+                }
+                DisplayPiece::Obj(obj, depth) => (obj, depth),
+            };
+            match obj {
+                SpicyObj::MixedList(_) | SpicyObj::Dict(_) if depth > max_depth => {
+                    output.push_str("...")
+                }
+                SpicyObj::MixedList(l) if !l.is_empty() => {
+                    output.push('(');
+                    stack.push(DisplayPiece::Text(")".to_owned()));
+                    if l.len() > 10 {
+                        stack.push(DisplayPiece::Text("; ...".to_owned()));
+                    }
+                    for (i, obj) in l.iter().take(10).enumerate().rev() {
+                        stack.push(DisplayPiece::Obj(obj, depth + 1));
+                        if i > 0 {
+                            stack.push(DisplayPiece::Text("; ".to_owned()));
+                        }
+                    }
+                }
+                SpicyObj::Dict(d) if !d.is_empty() => {
+                    output.push('{');
+                    stack.push(DisplayPiece::Text("}".to_owned()));
+                    if d.len() > 10 {
+                        stack.push(DisplayPiece::Text(", ...".to_owned()));
+                    }
+                    for (i, (key, obj)) in d.iter().take(10).enumerate().rev() {
+                        stack.push(DisplayPiece::Obj(obj, depth + 1));
+                        if i > 0 {
+                            stack.push(DisplayPiece::Text(format!(", {}: ", key)));
+                        } else {
+                            stack.push(DisplayPiece::Text(format!("{}: ", key)));
+                        }
+                    }
+                }
+                SpicyObj::MixedList(_) => output.push_str("()"),
+                SpicyObj::Dict(_) => output.push_str("()!()"),
+                _ => output.push_str(&obj.to_short_string()),
+            }
+        }
+        output
+    }
+
+    /// JSON value of the object, temporal atoms as ISO 8601 strings and frames as row objects.
+    /// Functions, exprs and lazy frames fall back to their display string.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+        match self {
+            SpicyObj::Boolean(v) => Value::Bool(*v),
+            SpicyObj::U8(v) => Value::from(*v),
+            SpicyObj::I16(v) => Value::from(*v),
+            SpicyObj::I32(v) => Value::from(*v),
+            SpicyObj::I64(v) => Value::from(*v),
+            // NaN and +/-inf have no JSON number
+            SpicyObj::F32(v) => Value::from(*v as f64),
+            SpicyObj::F64(v) => Value::from(*v),
+            SpicyObj::Date(v) => match v
+                .checked_add(UNIX_EPOCH_DAY)
+                .and_then(NaiveDate::from_num_days_from_ce_opt)
+            {
+                Some(d) => Value::String(d.format("%Y-%m-%d").to_string()),
+                None => Value::Null,
+            },
+            SpicyObj::Time(v) => match NaiveTime::from_num_seconds_from_midnight_opt(
+                v.div_euclid(1_000_000_000) as u32,
+                v.rem_euclid(1_000_000_000) as u32,
+            ) {
+                Some(t) if *v < NS_IN_DAY => Value::String(t.format("%H:%M:%S%.9f").to_string()),
+                _ => Value::Null,
+            },
+            SpicyObj::Datetime(v) => match DateTime::from_timestamp_millis(*v) {
+                Some(t) => Value::String(t.format("%Y-%m-%dT%H:%M:%S%.3f").to_string()),
+                None => Value::Null,
+            },
+            SpicyObj::Timestamp(v) => Value::String(
+                DateTime::from_timestamp_nanos(*v)
+                    .format("%Y-%m-%dT%H:%M:%S%.9f")
+                    .to_string(),
+            ),
+            SpicyObj::Duration(v) => Value::String(TimeDelta::nanoseconds(*v).to_string()),
+            SpicyObj::String(v) | SpicyObj::Symbol(v) => Value::String(v.clone()),
+            SpicyObj::Null | SpicyObj::DelayedArg => Value::Null,
+            SpicyObj::Series(s) => Value::Array(
+                (0..s.len())
+                    .map(|i| s.get(i).map(any_value_to_json).unwrap_or(Value::Null))
+                    .collect(),
+            ),
+            SpicyObj::Matrix(m) => Value::Array(
+                m.rows()
+                    .into_iter()
+                    .map(|row| row.iter().map(|v| Value::from(*v)).collect())
+                    .collect(),
+            ),
+            SpicyObj::MixedList(l) => Value::Array(l.iter().map(|obj| obj.to_json()).collect()),
+            SpicyObj::Dict(d) => Value::Object(
+                d.iter()
+                    .map(|(k, obj)| (k.clone(), obj.to_json()))
+                    .collect(),
+            ),
+            SpicyObj::DataFrame(df) | SpicyObj::KeyedTable(df, _) => Value::Array(
+                (0..df.height())
+                    .map(|i| {
+                        Value::Object(
+                            df.columns()
+                                .iter()
+                                .map(|c| {
+                                    let v = c.get(i).map(any_value_to_json).unwrap_or(Value::Null);
+                                    (c.name().to_string(), v)
+                                })
+                                .collect(),
+                        )
+                    })
+                    .collect(),
+            ),
+            SpicyObj::Return(obj) => obj.to_json(),
+            _ => Value::String(self.to_string()),
+        }
+    }
+}
+
+// column cells skip from_any_value, which has no atom for unsigned, i8 and
+// micro/milli second temporal values
+fn any_value_to_json(v: AnyValue) -> serde_json::Value {
+    use serde_json::Value;
+    match v {
+        AnyValue::Null => Value::Null,
+        AnyValue::UInt16(v) => Value::from(v),
+        AnyValue::UInt32(v) => Value::from(v),
+        AnyValue::UInt64(v) => Value::from(v),
+        AnyValue::Int8(v) => Value::from(v),
+        AnyValue::Datetime(v, TimeUnit::Microseconds, _) => {
+            match DateTime::from_timestamp_micros(v) {
+                Some(t) => Value::String(t.format("%Y-%m-%dT%H:%M:%S%.6f").to_string()),
+                None => Value::Null,
+            }
+        }
+        AnyValue::Duration(v, unit) => {
+            let delta = match unit {
+                TimeUnit::Nanoseconds => Some(TimeDelta::nanoseconds(v)),
+                TimeUnit::Microseconds => Some(TimeDelta::microseconds(v)),
+                TimeUnit::Milliseconds => TimeDelta::try_milliseconds(v),
+            };
+            delta.map_or(Value::Null, |d| Value::String(d.to_string()))
+        }
+        v => SpicyObj::from_any_value(v).to_json(),
+    }
 }
 
 macro_rules! impl_cast {
@@ -1504,22 +1697,22 @@ impl Display for SpicyObj {
                 } else if l.len() <= 10 && !l.is_empty() {
                     output.push_str("(\n");
                     for obj in &l[0..(l.len() - 1)] {
-                        output.push_str(&format!("  {}; \n", obj.to_short_string()))
+                        output.push_str(&format!("  {}; \n", obj.to_nested_string()))
                     }
                     if let Some(obj) = l.last() {
-                        output.push_str(&format!("  {}\n", obj.to_short_string()));
+                        output.push_str(&format!("  {}\n", obj.to_nested_string()));
                     }
                     output.push(')');
                 } else {
                     output.push_str("(\n");
                     for obj in l.iter().take(5) {
-                        output.push_str(&format!("  {}; \n", obj.to_short_string()))
+                        output.push_str(&format!("  {}; \n", obj.to_nested_string()))
                     }
                     output.push_str(" ...; \n");
                     for obj in l.iter().skip(l.len() - 5).take(4) {
-                        output.push_str(&format!("  {}; \n", obj.to_short_string()))
+                        output.push_str(&format!("  {}; \n", obj.to_nested_string()))
                     }
-                    output.push_str(&format!("  {}\n", l.last().unwrap().to_short_string()));
+                    output.push_str(&format!("  {}\n", l.last().unwrap().to_nested_string()));
                     output.push(')');
                 }
                 output
@@ -1539,19 +1732,19 @@ impl Display for SpicyObj {
                             output.push_str(&format!(
                                 "{:width$} | {}",
                                 key,
-                                value.to_short_string()
+                                value.to_nested_string()
                             ))
                         } else {
                             output.push_str(&format!(
                                 "{:width$} | {}\n",
                                 key,
-                                value.to_short_string()
+                                value.to_nested_string()
                             ))
                         }
                     }
                 } else {
                     for (key, value) in d.iter().take(5) {
-                        output.push_str(&format!("{:width$} | {}\n", key, value.to_short_string()))
+                        output.push_str(&format!("{:width$} | {}\n", key, value.to_nested_string()))
                     }
                     output.push_str("...\n");
                     for (i, (key, value)) in d.iter().skip(d.len() - 5).enumerate() {
@@ -1559,13 +1752,13 @@ impl Display for SpicyObj {
                             output.push_str(&format!(
                                 "{:width$} | {}",
                                 key,
-                                value.to_short_string()
+                                value.to_nested_string()
                             ))
                         } else {
                             output.push_str(&format!(
                                 "{:width$} | {}\n",
                                 key,
-                                value.to_short_string()
+                                value.to_nested_string()
                             ))
                         }
                     }
@@ -1578,6 +1771,10 @@ impl Display for SpicyObj {
                     .unwrap_or("failed to describe plan".to_owned())
             ),
             SpicyObj::DataFrame(df) => format!("{}", df),
+            SpicyObj::KeyedTable(df, keys) => {
+                let names = df.get_column_names_str();
+                format!("keys: {}\n{}", names[..*keys].join(", "), df)
+            }
             SpicyObj::Fn(fn_) => {
                 format!("{}", fn_)
             }
@@ -1624,6 +1821,154 @@ impl TryFrom<SpicyObj> for DataFrame {
     }
 }
 
+// conversions for passing data in and out of an embedded engine, a mismatched type is an error
+macro_rules! impl_from {
+    ($ty:ty, $enum:ident) => {
+        impl From<$ty> for SpicyObj {
+            fn from(v: $ty) -> Self {
+                SpicyObj::$enum(v)
+            }
+        }
+    };
+}
+
+impl_from!(bool, Boolean);
+impl_from!(i64, I64);
+impl_from!(f64, F64);
+impl_from!(String, String);
+impl_from!(Series, Series);
+impl_from!(DataFrame, DataFrame);
+impl_from!(Vec<SpicyObj>, MixedList);
+
+impl From<&str> for SpicyObj {
+    fn from(v: &str) -> Self {
+        SpicyObj::String(v.to_owned())
+    }
+}
+
+macro_rules! impl_from_vec {
+    ($ty:ty) => {
+        impl From<Vec<$ty>> for SpicyObj {
+            fn from(v: Vec<$ty>) -> Self {
+                SpicyObj::Series(Series::new("".into(), v))
+            }
+        }
+    };
+}
+
+impl_from_vec!(bool);
+impl_from_vec!(i64);
+impl_from_vec!(f64);
+impl_from_vec!(String);
+
+impl TryFrom<SpicyObj> for bool {
+    type Error = SpicyError;
+
+    fn try_from(other: SpicyObj) -> Result<Self, Self::Error> {
+        match other {
+            SpicyObj::Boolean(v) => Ok(v),
+            obj => Err(SpicyError::MismatchedTypeErr(
+                "bool".to_owned(),
+                obj.get_type_name(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<SpicyObj> for i64 {
+    type Error = SpicyError;
+
+    fn try_from(other: SpicyObj) -> Result<Self, Self::Error> {
+        if other.is_integer() {
+            other.to_i64()
+        } else {
+            Err(SpicyError::MismatchedTypeErr(
+                "i64".to_owned(),
+                other.get_type_name(),
+            ))
+        }
+    }
+}
+
+impl TryFrom<SpicyObj> for f64 {
+    type Error = SpicyError;
+
+    fn try_from(other: SpicyObj) -> Result<Self, Self::Error> {
+        if other.is_integer() || other.is_float() {
+            other.to_f64()
+        } else {
+            Err(SpicyError::MismatchedTypeErr(
+                "f64".to_owned(),
+                other.get_type_name(),
+            ))
+        }
+    }
+}
+
+impl TryFrom<SpicyObj> for String {
+    type Error = SpicyError;
+
+    fn try_from(other: SpicyObj) -> Result<Self, Self::Error> {
+        match other {
+            SpicyObj::String(s) | SpicyObj::Symbol(s) => Ok(s),
+            obj => Err(SpicyError::MismatchedTypeErr(
+                "str".to_owned(),
+                obj.get_type_name(),
+            )),
+        }
+    }
+}
+
+// from a series without nulls, or a list of atoms that each convert
+macro_rules! impl_try_from_vec {
+    ($ty:ty, $ty_str:literal, $is_dtype:ident, $dtype:expr, $series_fn:ident) => {
+        impl TryFrom<SpicyObj> for Vec<$ty> {
+            type Error = SpicyError;
+
+            fn try_from(other: SpicyObj) -> Result<Self, Self::Error> {
+                let err = |obj: &SpicyObj| {
+                    SpicyError::MismatchedTypeErr($ty_str.to_owned(), obj.get_type_name())
+                };
+                match other {
+                    SpicyObj::Series(ref s) if s.dtype().$is_dtype() && s.null_count() == 0 => {
+                        let s = s.strict_cast(&$dtype).map_err(|_| err(&other))?;
+                        Ok(s.$series_fn().unwrap().into_no_null_iter().collect())
+                    }
+                    SpicyObj::MixedList(l) => l.into_iter().map(<$ty>::try_from).collect(),
+                    obj => Err(err(&obj)),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_vec!(bool, "bools", is_bool, DataType::Boolean, bool);
+impl_try_from_vec!(i64, "i64s", is_integer, DataType::Int64, i64);
+impl_try_from_vec!(f64, "f64s", is_primitive_numeric, DataType::Float64, f64);
+
+impl TryFrom<SpicyObj> for Vec<String> {
+    type Error = SpicyError;
+
+    fn try_from(other: SpicyObj) -> Result<Self, Self::Error> {
+        match other {
+            SpicyObj::Series(ref s)
+                if (s.dtype().is_string() || s.dtype().is_categorical()) && s.null_count() == 0 =>
+            {
+                Ok(other
+                    .to_str_vec()?
+                    .into_iter()
+                    .map(|s| s.to_owned())
+                    .collect())
+            }
+            SpicyObj::MixedList(l) => l.into_iter().map(String::try_from).collect(),
+            obj => Err(SpicyError::MismatchedTypeErr(
+                "strs".to_owned(),
+                obj.get_type_name(),
+            )),
+        }
+    }
+}
+
 pub fn get_series_len(series: &Series) -> Result<usize, SpicyError> {
     let length = series.len();
     let data_type = series.dtype();
@@ -1692,17 +2037,8 @@ pub fn get_series_len(series: &Series) -> Result<usize, SpicyError> {
                 )),
             }
         }
-        PolarsDataType::Binary => {
-            let array = series.binary().unwrap();
-            let is_16_fixed_binary = array.iter().any(|v| 16 == v.unwrap_or(&[]).len());
-            if is_16_fixed_binary {
-                Ok(16 * length + 6)
-            } else {
-                Err(SpicyError::Err(
-                    "Only support 16 fixed size binary as guid".to_string(),
-                ))
-            }
-        }
+        // guid, lengths other than 16 are rejected on serialize
+        PolarsDataType::Binary | PolarsDataType::BinaryOffset => Ok(16 * length + 6),
         // to symbol
         PolarsDataType::Categorical(_, _) => {
             let cat = series.cat32().unwrap();
@@ -1719,6 +2055,17 @@ pub fn get_series_len(series: &Series) -> Result<usize, SpicyError> {
 #[cfg(test)]
 mod tests {
     use crate::obj::SpicyObj;
+    #[test]
+    fn display_deeply_nested_list() {
+        let mut obj = SpicyObj::I64(1);
+        for _ in 0..50 {
+            obj = SpicyObj::MixedList(vec![obj, SpicyObj::I64(2)]);
+        }
+        let output = format!("{}", obj);
+        assert!(output.contains("((...; 2); 2); 2"));
+        assert!(output.len() < 100);
+    }
+
     #[test]
     fn parse_time() {
         assert_eq!(
@@ -1745,7 +2092,13 @@ mod tests {
             SpicyObj::parse_time("23:59:59.000123").unwrap(),
             SpicyObj::Time(86399000123000)
         );
-        assert!(SpicyObj::parse_time("24:59:59.123456789").is_err())
+        assert_eq!(
+            SpicyObj::parse_time("09:30").unwrap(),
+            SpicyObj::Time(34200000000000)
+        );
+        assert!(SpicyObj::parse_time("24:59:59.123456789").is_err());
+        assert!(SpicyObj::parse_time("09:60").is_err());
+        assert!(SpicyObj::parse_time("09").is_err())
     }
 
     #[test]
@@ -1764,4 +2117,121 @@ mod tests {
         );
         assert!(SpicyObj::parse_duration("100D23:60:59.123456789").is_err())
     }
+
+    #[test]
+    fn convert_rust_types() {
+        use polars::prelude::{DataFrame, NamedFrom, Series};
+
+        assert_eq!(SpicyObj::from(true), SpicyObj::Boolean(true));
+        assert_eq!(SpicyObj::from(1i64), SpicyObj::I64(1));
+        assert_eq!(SpicyObj::from(1.5f64), SpicyObj::F64(1.5));
+        assert_eq!(SpicyObj::from("a"), SpicyObj::String("a".to_owned()));
+        assert_eq!(
+            SpicyObj::from(vec![1i64, 2]),
+            SpicyObj::Series(Series::new("".into(), [1i64, 2]))
+        );
+        let df = DataFrame::new(1, vec![Series::new("a".into(), [1i64]).into()]).unwrap();
+        assert_eq!(SpicyObj::from(df.clone()), SpicyObj::DataFrame(df));
+
+        assert_eq!(i64::try_from(SpicyObj::I32(3)).unwrap(), 3);
+        assert_eq!(f64::try_from(SpicyObj::I64(3)).unwrap(), 3.0);
+        assert!(bool::try_from(SpicyObj::Boolean(true)).unwrap());
+        assert_eq!(
+            String::try_from(SpicyObj::Symbol("a".to_owned())).unwrap(),
+            "a"
+        );
+        let v: Vec<i64> = SpicyObj::Series(Series::new("".into(), [1i32, 2]))
+            .try_into()
+            .unwrap();
+        assert_eq!(v, vec![1, 2]);
+        let v: Vec<f64> = SpicyObj::MixedList(vec![SpicyObj::I64(1), SpicyObj::F64(2.5)])
+            .try_into()
+            .unwrap();
+        assert_eq!(v, vec![1.0, 2.5]);
+        let v: Vec<String> = SpicyObj::from(vec!["a".to_owned(), "b".to_owned()])
+            .try_into()
+            .unwrap();
+        assert_eq!(v, vec!["a", "b"]);
+
+        assert!(i64::try_from(SpicyObj::F64(1.0)).is_err());
+        assert!(String::try_from(SpicyObj::I64(1)).is_err());
+        assert!(
+            Vec::<i64>::try_from(SpicyObj::Series(Series::new("".into(), [Some(1i64), None])))
+                .is_err()
+        );
+        assert!(
+            Vec::<i64>::try_from(SpicyObj::Series(Series::new("".into(), [1u64, u64::MAX])))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn to_json() {
+        use indexmap::IndexMap;
+        use polars::prelude::{NamedFrom, Series};
+        use serde_json::json;
+
+        let d = SpicyObj::Dict(IndexMap::from([
+            ("a".to_owned(), SpicyObj::I64(1)),
+            ("b".to_owned(), SpicyObj::Symbol("x".to_owned())),
+            ("c".to_owned(), SpicyObj::Date(19797)),
+            ("d".to_owned(), SpicyObj::Null),
+        ]));
+        assert_eq!(
+            d.to_json(),
+            json!({"a": 1, "b": "x", "c": "2024-03-15", "d": null})
+        );
+
+        let s = SpicyObj::Series(Series::new("".into(), [Some(1.5), None, Some(f64::NAN)]));
+        assert_eq!(s.to_json(), json!([1.5, null, null]));
+
+        let l = SpicyObj::MixedList(vec![
+            SpicyObj::Boolean(true),
+            SpicyObj::MixedList(vec![
+                SpicyObj::String("s".to_owned()),
+                SpicyObj::Timestamp(1_710_495_000_123_456_789),
+            ]),
+            SpicyObj::Time(34_200_500_000_000),
+            SpicyObj::Series(Series::new("".into(), [1i64, 2])),
+        ]);
+        assert_eq!(
+            l.to_json(),
+            json!([
+                true,
+                ["s", "2024-03-15T09:30:00.123456789"],
+                "09:30:00.500000000",
+                [1, 2]
+            ])
+        );
+
+        let df = SpicyObj::DataFrame(
+            DataFrame::new(
+                1,
+                vec![
+                    Series::new("u".into(), [u32::MAX]).into(),
+                    Series::new("i".into(), [-1i8]).into(),
+                    Series::new("t".into(), [1_710_495_000_123_456i64])
+                        .cast(&DataType::Datetime(TimeUnit::Microseconds, None))
+                        .unwrap()
+                        .into(),
+                    Series::new("d".into(), [1_500i64])
+                        .cast(&DataType::Duration(TimeUnit::Milliseconds))
+                        .unwrap()
+                        .into(),
+                ],
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            df.to_json(),
+            json!([{
+                "u": u32::MAX,
+                "i": -1,
+                "t": "2024-03-15T09:30:00.123456",
+                "d": "PT1.5S"
+            }])
+        );
+
+        assert_eq!(SpicyObj::Date(i32::MAX).to_json(), json!(null));
+    }
 }
@@ -676,7 +676,7 @@ pub fn parse(
 
     let mut lang = Language::Chili;
 
-    let (program, errs) = if source_path.ends_with(".chi") {
+    let (program, errs) = if source_path.ends_with(".chi") || source_path.ends_with(".chili") {
         Expr::parser_chili()
             .parse(
                 tokens
@@ -7,9 +7,8 @@ use polars::datatypes::{DataType as PolarsDataType, TimeUnit as PolarTimeUnit};
 use polars::prelude::{Categories, Column, DataFrame};
 use polars::series::{IntoSeries, Series};
 use polars_arrow::array::{
-    Array, BinaryViewArray, BooleanArray, FixedSizeBinaryArray, FixedSizeListArray, Float32Array,
-    Float64Array, Int16Array, Int32Array, Int64Array, ListArray, PrimitiveArray, UInt8Array,
-    Utf8ViewArray,
+    Array, BooleanArray, FixedSizeBinaryArray, FixedSizeListArray, Float32Array, Float64Array,
+    Int16Array, Int32Array, Int64Array, ListArray, PrimitiveArray, UInt8Array, Utf8ViewArray,
 };
 use polars_arrow::bitmap::Bitmap;
 use polars_arrow::datatypes::{ArrowDataType, Field, TimeUnit};
@@ -20,10 +19,12 @@ use polars_buffer::buffer::Buffer;
 use rayon::iter::IntoParallelIterator;
 use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use std::cmp::min;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 // time difference between chrono and q types
-pub const NS_DIFF: i64 = 946684800000000000;
+pub use crate::constant::NS_DIFF;
 pub const MS_DIFF: i64 = 946684800000;
 const NS_PER_DAY: i64 = 86400000000000;
 const MS_PER_DAY: f64 = 86400000.0;
@@ -57,77 +58,252 @@ const K_TYPE_NAME: [&str; 20] = [
 
 use crate::errors::SpicyError;
 use crate::obj::get_series_len;
+use crate::utils::read_bytes;
+pub use crate::utils::{ByteOrder, read_u32, read_u64};
+
+const DEFAULT_MAX_NESTED_DEPTH: usize = 64;
+
+static MAX_NESTED_DEPTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_NESTED_DEPTH);
+
+/// Set how many levels of nested lists, dicts and keyed tables a q ipc message may have.
+pub fn set_max_nested_depth(depth: usize) {
+    MAX_NESTED_DEPTH.store(depth, Ordering::Relaxed);
+}
+
+pub fn get_max_nested_depth() -> usize {
+    MAX_NESTED_DEPTH.load(Ordering::Relaxed)
+}
+
+// a peer controls the nesting, so stop before the recursion can exhaust the stack
+fn check_nested_depth(depth: usize, pos: usize) -> Result<(), SpicyError> {
+    let max_depth = get_max_nested_depth();
+    if depth > max_depth {
+        Err(SpicyError::DeserializationErr(format!(
+            "nested depth exceeds max {} at position {}",
+            max_depth, pos
+        )))
+    } else {
+        Ok(())
+    }
+}
 
 pub const K_TYPE_SIZE: [usize; 20] = [0, 1, 16, 0, 1, 2, 4, 8, 4, 8, 1, 0, 8, 4, 4, 8, 8, 4, 4, 4];
 
+fn read_u8(vec: &[u8], pos: usize) -> Result<u8, SpicyError> {
+    vec.get(pos).copied().ok_or_else(|| {
+        SpicyError::DeserializationErr(format!(
+            "expect 1 byte at position {}, got buffer of {} bytes",
+            pos,
+            vec.len()
+        ))
+    })
+}
+
+// end of `length` values of `k_size` bytes starting at `pos`, checked against the buffer
+// so that a corrupt length header errors instead of overflowing or slicing out of bounds
+fn array_end(vec: &[u8], pos: usize, k_size: usize, length: usize) -> Result<usize, SpicyError> {
+    k_size
+        .checked_mul(length)
+        .and_then(|size| size.checked_add(pos))
+        .filter(|end| *end <= vec.len())
+        .ok_or_else(|| {
+            SpicyError::DeserializationErr(format!(
+                "expect {} values of {} bytes at position {}, got buffer of {} bytes",
+                length,
+                k_size,
+                pos,
+                vec.len()
+            ))
+        })
+}
+
+fn skip_null_terminated(vec: &[u8], pos: &mut usize) -> Result<(), SpicyError> {
+    match vec[(*pos).min(vec.len())..].iter().position(|b| *b == 0) {
+        Some(i) => {
+            *pos += i + 1;
+            Ok(())
+        }
+        None => Err(SpicyError::DeserializationErr(
+            "unterminated symbol at end of buffer".to_owned(),
+        )),
+    }
+}
+
+// guid and char values are byte sequences, no need to swap
+fn swap_values(vec: &mut [u8], pos: &mut usize, k: usize, length: usize) -> Result<(), SpicyError> {
+    let size = K_TYPE_SIZE[k];
+    let end = *pos + size * length;
+    if end > vec.len() {
+        return Err(SpicyError::DeserializationErr(format!(
+            "expect {} of {} bytes, got buffer of {} bytes",
+            K_TYPE_NAME[k],
+            end,
+            vec.len()
+        )));
+    }
+    if size > 1 && k != 2 {
+        vec[*pos..end]
+            .chunks_exact_mut(size)
+            .for_each(|v| v.reverse());
+    }
+    *pos = end;
+    Ok(())
+}
+
+// reverse each multi-byte value of a big endian encoded object in place,
+// so that the little endian decoding below reads it as is
+fn swap_to_little_endian(vec: &mut [u8], pos: &mut usize, depth: usize) -> Result<(), SpicyError> {
+    check_nested_depth(depth, *pos)?;
+    let k_type = *vec.get(*pos).ok_or_else(|| {
+        SpicyError::DeserializationErr("unexpected end of big endian buffer".to_owned())
+    })?;
+    *pos += 1;
+    match k_type {
+        237..=255 => match 256 - k_type as usize {
+            11 => skip_null_terminated(vec, pos),
+            k => swap_values(vec, pos, k, 1),
+        },
+        0..=19 => {
+            // attribute
+            *pos += 1;
+            let length = read_u32(vec, *pos, ByteOrder::Big)?;
+            vec[*pos..*pos + 4].copy_from_slice(&length.to_le_bytes());
+            *pos += 4;
+            match k_type {
+                0 => (0..length).try_for_each(|_| swap_to_little_endian(vec, pos, depth + 1)),
+                11 => (0..length).try_for_each(|_| skip_null_terminated(vec, pos)),
+                k => swap_values(vec, pos, k as usize, length as usize),
+            }
+        }
+        98 => {
+            *pos += 1;
+            swap_to_little_endian(vec, pos, depth + 1)
+        }
+        99 | 127 => {
+            swap_to_little_endian(vec, pos, depth + 1)?;
+            swap_to_little_endian(vec, pos, depth + 1)
+        }
+        101 => {
+            *pos += 1;
+            Ok(())
+        }
+        128 => skip_null_terminated(vec, pos),
+        _ => Err(SpicyError::NotSupportedKTypeErr(k_type)),
+    }
+}
+
+/// Deserialize an object encoded in the given byte order. Big endian input is
+/// converted to little endian first, which costs one copy of the buffer.
+pub fn deserialize_with_order(
+    vec: &[u8],
+    pos: &mut usize,
+    is_column: bool,
+    order: ByteOrder,
+) -> Result<SpicyObj, SpicyError> {
+    match order {
+        ByteOrder::Little => deserialize(vec, pos, is_column),
+        ByteOrder::Big => {
+            let mut le_vec = vec.to_vec();
+            let mut start_pos = *pos;
+            swap_to_little_endian(&mut le_vec, &mut start_pos, 0)?;
+            deserialize(&le_vec, pos, is_column)
+        }
+    }
+}
+
 pub fn deserialize(vec: &[u8], pos: &mut usize, is_column: bool) -> Result<SpicyObj, SpicyError> {
-    let k_type = vec[*pos];
+    deserialize_nested(vec, pos, is_column, 0)
+}
+
+fn deserialize_nested(
+    vec: &[u8],
+    pos: &mut usize,
+    is_column: bool,
+    depth: usize,
+) -> Result<SpicyObj, SpicyError> {
+    check_nested_depth(depth, *pos)?;
+    let k_type = read_u8(vec, *pos)?;
     *pos += 1;
     let start_pos = *pos;
     match k_type {
         237..=255 => match k_type {
             255 => {
                 *pos += 1;
-                Ok(SpicyObj::Boolean(vec[start_pos] == 1))
+                Ok(SpicyObj::Boolean(read_u8(vec, start_pos)? == 1))
             }
             252 => {
                 *pos += 1;
-                Ok(SpicyObj::U8(vec[start_pos]))
+                Ok(SpicyObj::U8(read_u8(vec, start_pos)?))
             }
             251 => {
                 *pos += 2;
-                Ok(SpicyObj::I16(i16::from_le_bytes(
-                    vec[start_pos..start_pos + 2].try_into().unwrap(),
-                )))
+                Ok(SpicyObj::I16(i16::from_le_bytes(read_bytes(
+                    vec,
+                    start_pos,
+                    ByteOrder::Little,
+                )?)))
             }
             250 => {
                 *pos += 4;
-                Ok(SpicyObj::I32(i32::from_le_bytes(
-                    vec[start_pos..start_pos + 4].try_into().unwrap(),
-                )))
+                Ok(SpicyObj::I32(i32::from_le_bytes(read_bytes(
+                    vec,
+                    start_pos,
+                    ByteOrder::Little,
+                )?)))
             }
             249 => {
                 *pos += 8;
-                Ok(SpicyObj::I64(i64::from_le_bytes(
-                    vec[start_pos..start_pos + 8].try_into().unwrap(),
-                )))
+                Ok(SpicyObj::I64(i64::from_le_bytes(read_bytes(
+                    vec,
+                    start_pos,
+                    ByteOrder::Little,
+                )?)))
             }
             248 => {
                 *pos += 4;
-                Ok(SpicyObj::F32(f32::from_le_bytes(
-                    vec[start_pos..start_pos + 4].try_into().unwrap(),
-                )))
+                Ok(SpicyObj::F32(f32::from_le_bytes(read_bytes(
+                    vec,
+                    start_pos,
+                    ByteOrder::Little,
+                )?)))
             }
             247 => {
                 *pos += 8;
-                Ok(SpicyObj::F64(f64::from_le_bytes(
-                    vec[start_pos..start_pos + 8].try_into().unwrap(),
-                )))
+                Ok(SpicyObj::F64(f64::from_le_bytes(read_bytes(
+                    vec,
+                    start_pos,
+                    ByteOrder::Little,
+                )?)))
             }
             246 => {
                 *pos += 1;
-                Ok(SpicyObj::String(vec[start_pos].to_string()))
+                Ok(SpicyObj::String(read_u8(vec, start_pos)?.to_string()))
             }
             245 => {
                 let mut eod_pos = *pos;
-                while eod_pos <= vec.len() && vec[eod_pos] != 0 {
+                while eod_pos < vec.len() && vec[eod_pos] != 0 {
                     eod_pos += 1;
                 }
+                if eod_pos >= vec.len() {
+                    return Err(SpicyError::DeserializationErr(
+                        "unterminated symbol at end of buffer".to_owned(),
+                    ));
+                }
                 *pos = eod_pos + 1;
-                Ok(SpicyObj::Symbol(
-                    String::from_utf8(vec[start_pos..eod_pos].to_vec()).unwrap(),
-                ))
+                String::from_utf8(vec[start_pos..eod_pos].to_vec())
+                    .map(SpicyObj::Symbol)
+                    .map_err(|e| SpicyError::DeserializationErr(e.to_string()))
             }
             // timestamp
             244 => {
-                let ns = i64::from_le_bytes(vec[*pos..*pos + 8].try_into().unwrap())
+                let ns = i64::from_le_bytes(read_bytes(vec, *pos, ByteOrder::Little)?)
                     .saturating_add(NS_DIFF);
                 *pos += 8;
                 Ok(SpicyObj::Timestamp(ns))
             }
             // month
             243 => {
-                let unit = i32::from_le_bytes(vec[*pos..*pos + 4].try_into().unwrap());
+                let unit = i32::from_le_bytes(read_bytes(vec, *pos, ByteOrder::Little)?);
                 let year;
                 let month;
                 if unit >= 0 {
@@ -138,36 +314,34 @@ pub fn deserialize(vec: &[u8], pos: &mut usize, is_column: bool) -> Result<Spicy
                     month = 12 + (unit - 11) % 12
                 }
                 *pos += 4;
-                Ok(SpicyObj::Date(
-                    NaiveDate::from_ymd_opt(year, month as u32, 1)
-                        .unwrap()
-                        .num_days_from_ce()
-                        + UNIX_EPOCH_DAY,
-                ))
+                let date = NaiveDate::from_ymd_opt(year, month as u32, 1).ok_or_else(|| {
+                    SpicyError::DeserializationErr(format!("month {} out of range", unit))
+                })?;
+                Ok(SpicyObj::Date(date.num_days_from_ce() + UNIX_EPOCH_DAY))
             }
             // date
             242 => {
-                let days = i32::from_le_bytes(vec[*pos..*pos + 4].try_into().unwrap())
+                let days = i32::from_le_bytes(read_bytes(vec, *pos, ByteOrder::Little)?)
                     .saturating_add(DAY_DIFF);
                 *pos += 4;
                 Ok(SpicyObj::Date(days))
             }
             // datetime
             241 => {
-                let unit = f64::from_le_bytes(vec[*pos..*pos + 8].try_into().unwrap());
+                let unit = f64::from_le_bytes(read_bytes(vec, *pos, ByteOrder::Little)?);
                 let ms = MS_DIFF + (unit * MS_IN_DAY as f64) as i64;
                 *pos += 8;
                 Ok(SpicyObj::Datetime(ms))
             }
             // timespan
             240 => {
-                let ns = i64::from_le_bytes(vec[*pos..*pos + 8].try_into().unwrap());
+                let ns = i64::from_le_bytes(read_bytes(vec, *pos, ByteOrder::Little)?);
                 *pos += 8;
                 Ok(SpicyObj::Duration(ns))
             }
             // time, second, minute
             237..=239 => {
-                let unit = i32::from_le_bytes(vec[*pos..*pos + 4].try_into().unwrap());
+                let unit = i32::from_le_bytes(read_bytes(vec, *pos, ByteOrder::Little)?);
                 let mut nanos: i64 = 0;
                 // ms
                 if k_type == 237 {
@@ -190,15 +364,22 @@ pub fn deserialize(vec: &[u8], pos: &mut usize, is_column: bool) -> Result<Spicy
                 Err(e) => {
                     if !is_column && k_type == 0 {
                         *pos += 1;
-                        let length =
-                            u32::from_le_bytes(vec[*pos..*pos + 4].try_into().unwrap()) as usize;
+                        let length = read_u32(vec, *pos, ByteOrder::Little)? as usize;
                         *pos += 4;
                         if length == 0 {
                             return Ok(SpicyObj::MixedList(Vec::new()));
+                        } else if length > vec.len() - *pos {
+                            // every item takes at least one byte
+                            return Err(SpicyError::DeserializationErr(format!(
+                                "expect list of {} items at position {}, got buffer of {} bytes",
+                                length,
+                                *pos,
+                                vec.len()
+                            )));
                         } else {
                             let mut res = Vec::with_capacity(length);
                             for _ in 0..length {
-                                res.push(deserialize(vec, pos, false)?);
+                                res.push(deserialize_nested(vec, pos, false, depth + 1)?);
                             }
                             return Ok(SpicyObj::MixedList(res));
                         }
@@ -219,23 +400,28 @@ pub fn deserialize(vec: &[u8], pos: &mut usize, is_column: bool) -> Result<Spicy
             }
         }
         99 => {
-            if vec[*pos] == 98 {
-                let mut key_df: DataFrame = deserialize(vec, pos, true)?.try_into()?;
-                let value_df: DataFrame = deserialize(vec, pos, true)?.try_into()?;
+            let value_type = read_u8(vec, *pos)?;
+            if value_type == 98 {
+                let mut key_df: DataFrame =
+                    deserialize_nested(vec, pos, true, depth + 1)?.try_into()?;
+                let value_df: DataFrame =
+                    deserialize_nested(vec, pos, true, depth + 1)?.try_into()?;
+                let key_width = key_df.width();
                 unsafe { key_df.hstack_mut_unchecked(value_df.columns()) };
-                Ok(SpicyObj::DataFrame(key_df))
-            } else if vec[*pos] == 11 {
+                Ok(SpicyObj::KeyedTable(key_df, key_width))
+            } else if value_type == 11 {
                 *pos += 1;
                 let end_pos = calculate_array_end_index(vec, *pos, 11)?;
                 let keys = deserialize_series(&vec[*pos..end_pos], 11, true)?;
                 *pos = end_pos;
-                if vec[end_pos] > 19 {
+                let value_type = read_u8(vec, end_pos)?;
+                if value_type > 19 {
                     return Err(SpicyError::Err(format!(
                         "Not support k type {:?} values in dictionary",
-                        vec[end_pos]
+                        value_type
                     )));
                 }
-                let values = deserialize(vec, pos, is_column)?;
+                let values = deserialize_nested(vec, pos, is_column, depth + 1)?;
                 let keys = Series::try_from(keys).unwrap();
                 match values {
                     SpicyObj::Series(s) => {
@@ -257,7 +443,7 @@ pub fn deserialize(vec: &[u8], pos: &mut usize, is_column: bool) -> Result<Spicy
             } else {
                 Err(SpicyError::Err(format!(
                     "Only support symbol keys dictionary or keyed table, got k type {:?}",
-                    vec[*pos]
+                    value_type
                 )))
             }
         }
@@ -274,12 +460,14 @@ pub fn deserialize(vec: &[u8], pos: &mut usize, is_column: bool) -> Result<Spicy
                     k
                 )));
             };
-            let symbols = symbols.str().unwrap();
+            let symbols = symbols
+                .str()
+                .map_err(|e| SpicyError::DeserializationErr(e.to_string()))?;
             *pos += 6;
             let mut k_types = vec![0u8; symbols.len()];
             let mut vectors: Vec<&[u8]> = Vec::with_capacity(symbols.len());
             for k_type in k_types.iter_mut().take(symbols.len()) {
-                *k_type = vec[*pos];
+                *k_type = read_u8(vec, *pos)?;
                 *pos += 1;
                 let end_pos = calculate_array_end_index(vec, *pos, *k_type)?;
                 vectors.push(&vec[*pos..end_pos]);
@@ -289,40 +477,76 @@ pub fn deserialize(vec: &[u8], pos: &mut usize, is_column: bool) -> Result<Spicy
             let mut columns: Vec<Column> = vectors
                 .par_iter()
                 .zip(k_types.clone())
-                .map(|(v, t)| deserialize_series(v, t, true).unwrap().try_into().unwrap())
-                .collect();
+                .map(|(v, t)| deserialize_series(v, t, true)?.try_into())
+                .collect::<Result<_, SpicyError>>()?;
 
-            columns.iter_mut().zip(symbols.iter()).for_each(|(c, n)| {
-                c.rename(n.unwrap_or("").into());
+            let names = dedup_column_names(symbols.iter().map(|n| n.unwrap_or("")));
+            columns.iter_mut().zip(names).for_each(|(c, n)| {
+                c.rename(n.into());
             });
             let height = columns.first().map(|c| c.len()).unwrap_or(0);
-            Ok(SpicyObj::DataFrame(
-                DataFrame::new(height, columns).unwrap(),
-            ))
+            DataFrame::new(height, columns)
+                .map(SpicyObj::DataFrame)
+                .map_err(|e| SpicyError::DeserializationErr(e.to_string()))
         }
         101 => {
             *pos += 1;
-            if vec[start_pos] == 0 {
-                Ok(SpicyObj::Null)
-            } else {
-                Err(SpicyError::NotSupportedKOperatorErr(vec[*pos]))
+            match read_u8(vec, start_pos)? {
+                0 => Ok(SpicyObj::Null),
+                op => Err(SpicyError::NotSupportedKOperatorErr(op)),
             }
         }
         // q error
         128 => {
             let mut eod_pos = *pos;
-            while eod_pos <= vec.len() && vec[eod_pos] != 0 {
+            while eod_pos < vec.len() && vec[eod_pos] != 0 {
                 eod_pos += 1;
             }
             *pos = eod_pos;
             Err(SpicyError::ServerErr(
-                String::from_utf8(vec[start_pos..eod_pos].to_vec()).unwrap(),
+                String::from_utf8_lossy(&vec[start_pos..eod_pos]).to_string(),
             ))
         }
         _ => Err(SpicyError::NotSupportedKTypeErr(k_type)),
     }
 }
 
+// q tables may carry column names chili can't hold in a frame, names
+// are otherwise preserved as is and can be referenced via quoting.
+// - empty name is replaced by its positional name, e.g. col01
+// - duplicate name gets a suffix of its occurrence, e.g. a, a_1, a_2
+fn dedup_column_names<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let names: Vec<String> = names
+        .enumerate()
+        .map(|(i, n)| {
+            if n.is_empty() {
+                format!("col{:02}", i)
+            } else {
+                n.to_owned()
+            }
+        })
+        .collect();
+    let mut seen: HashSet<String> = names.iter().cloned().collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut res = Vec::with_capacity(names.len());
+    for n in names.iter() {
+        let count = counts.entry(n.as_str()).or_insert(0);
+        if *count == 0 {
+            res.push(n.clone());
+        } else {
+            let mut new_name = format!("{}_{}", n, count);
+            while seen.contains(&new_name) {
+                *count += 1;
+                new_name = format!("{}_{}", n, count);
+            }
+            seen.insert(new_name.clone());
+            res.push(new_name);
+        }
+        *count += 1;
+    }
+    res
+}
+
 fn create_field(k_type: u8, name: &str) -> Result<Field, SpicyError> {
     let name = name.into();
     match k_type {
@@ -380,29 +604,34 @@ fn calculate_array_end_index(
     match k_type {
         0 => {
             pos += 1;
-            let length = u32::from_le_bytes(vec[pos..pos + 4].try_into().unwrap()) as usize;
+            let length = read_u32(vec, pos, ByteOrder::Little)? as usize;
             pos += 4;
             if length == 0 {
                 return Ok(pos);
             }
-            let sub_k_type = vec[pos];
+            let sub_k_type = read_u8(vec, pos)?;
             if sub_k_type > 19 {
                 return Err(SpicyError::NotSupportedKNestedListErr(sub_k_type));
             }
             let k_size = K_TYPE_SIZE[sub_k_type as usize];
             if let 1 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 12 = sub_k_type {
                 for _ in 0..length {
-                    let current_k_type = vec[pos];
+                    let current_k_type = read_u8(vec, pos)?;
                     if sub_k_type != current_k_type {
-                        return Err(SpicyError::NotSupportedKMixedListErr(sub_k_type, vec[pos]));
+                        return Err(SpicyError::NotSupportedKMixedListErr(
+                            sub_k_type,
+                            current_k_type,
+                        ));
                     }
                     pos += 2;
-                    let sub_length = i32::from_le_bytes(vec[pos..pos + 4].try_into().unwrap());
-                    if current_k_type == 0 && sub_length > 0 {
-                        return Err(SpicyError::NotSupportedKMixedListErr(sub_k_type, vec[pos]));
+                    let sub_length = i32::from_le_bytes(read_bytes(vec, pos, ByteOrder::Little)?);
+                    if sub_length < 0 {
+                        return Err(SpicyError::DeserializationErr(format!(
+                            "negative length {} of nested list at position {}",
+                            sub_length, pos
+                        )));
                     }
-                    pos += 4;
-                    pos += k_size * sub_length as usize;
+                    pos = array_end(vec, pos + 4, k_size, sub_length as usize)?;
                 }
                 Ok(pos)
             } else {
@@ -412,10 +641,16 @@ fn calculate_array_end_index(
         // symbol list
         11 => {
             pos += 1;
-            let length = u32::from_le_bytes(vec[pos..pos + 4].try_into().unwrap()) as usize;
+            let length = read_u32(vec, pos, ByteOrder::Little)? as usize;
             pos += 4;
             let mut i = 0;
             while i < length {
+                if pos >= vec.len() {
+                    return Err(SpicyError::DeserializationErr(format!(
+                        "unterminated symbol {} of {} at end of buffer",
+                        i, length
+                    )));
+                }
                 if vec[pos] == 0 {
                     i += 1;
                 }
@@ -424,13 +659,13 @@ fn calculate_array_end_index(
             Ok(pos)
         }
         _ => {
-            if k_type > 20 {
+            if k_type as usize >= K_TYPE_SIZE.len() {
                 Err(SpicyError::NotSupportedKListErr(k_type))
             } else if K_TYPE_SIZE[k_type as usize] > 0 {
                 pos += 1;
-                let length = u32::from_le_bytes(vec[pos..pos + 4].try_into().unwrap()) as usize;
+                let length = read_u32(vec, pos, ByteOrder::Little)? as usize;
                 let k_size = K_TYPE_SIZE[k_type as usize];
-                Ok(pos + 4 + k_size * length)
+                array_end(vec, pos + 4, k_size, length)
             } else {
                 Err(SpicyError::NotSupportedKListErr(k_type))
             }
@@ -530,12 +765,13 @@ fn deserialize_series(vec: &[u8], k_type: u8, as_column: bool) -> Result<SpicyOb
         10 => {
             if as_column {
                 let offsets: Vec<i64> = (0..=length as i64).collect();
-                array_box = Utf8Array::<i64>::new(
+                array_box = Utf8Array::<i64>::try_new(
                     ArrowDataType::LargeUtf8,
                     OffsetsBuffer::try_from(offsets).unwrap(),
                     Buffer::from(array_vec.to_vec()),
                     None,
                 )
+                .map_err(|e| SpicyError::DeserializationErr(e.to_string()))?
                 .boxed();
                 series = Series::from_arrow(name, array_box).unwrap();
                 Ok(SpicyObj::Series(series))
@@ -551,6 +787,12 @@ fn deserialize_series(vec: &[u8], k_type: u8, as_column: bool) -> Result<SpicyOb
             let mut i = 0;
             let mut start_pos = pos;
             while i < length {
+                if pos >= vec.len() {
+                    return Err(SpicyError::DeserializationErr(format!(
+                        "unterminated symbol {} of {} at end of buffer",
+                        i, length
+                    )));
+                }
                 if vec[pos] == 0 {
                     v8.write_all(&vec[start_pos..pos]).unwrap();
                     offsets[i + 1] = offsets[i] + (pos - start_pos) as i64;
@@ -559,12 +801,13 @@ fn deserialize_series(vec: &[u8], k_type: u8, as_column: bool) -> Result<SpicyOb
                 }
                 pos += 1;
             }
-            array_box = Utf8Array::<i64>::new(
+            array_box = Utf8Array::<i64>::try_new(
                 ArrowDataType::LargeUtf8,
                 OffsetsBuffer::try_from(offsets).unwrap(),
                 Buffer::from(v8),
                 None,
             )
+            .map_err(|e| SpicyError::DeserializationErr(e.to_string()))?
             .boxed();
             series = Series::from_arrow(name, array_box).unwrap();
             if as_column {
@@ -834,12 +1077,13 @@ fn deserialize_nested_array(vec: &[u8]) -> Result<SpicyObj, SpicyError> {
             ))
         }
         10 => {
-            let array_box = Utf8Array::<i64>::new(
+            let array_box = Utf8Array::<i64>::try_new(
                 ArrowDataType::LargeUtf8,
                 offsets_buf,
                 Buffer::from(v8),
                 None,
             )
+            .map_err(|e| SpicyError::DeserializationErr(e.to_string()))?
             .boxed();
             Ok(SpicyObj::Series(
                 Series::from_arrow(name.into(), array_box).unwrap(),
@@ -849,7 +1093,16 @@ fn deserialize_nested_array(vec: &[u8]) -> Result<SpicyObj, SpicyError> {
     }
 }
 
-pub fn decompress(vec: &[u8], de_vec: &mut [u8], start_pos: usize) {
+/// Decompress a q IPC message body into `de_vec`, sized by the uncompressed length
+/// it declares. Errors when the body is truncated or refers outside of `de_vec`.
+pub fn decompress(vec: &[u8], de_vec: &mut [u8], start_pos: usize) -> Result<(), SpicyError> {
+    let malformed = || {
+        SpicyError::DeserializationErr(format!(
+            "malformed compressed message of {} bytes, expected {} bytes uncompressed",
+            vec.len(),
+            de_vec.len()
+        ))
+    };
     let mut d_pos: usize = 0;
     // skip decompressed msg length
     let mut x_pos: usize = 4;
@@ -860,22 +1113,28 @@ pub fn decompress(vec: &[u8], de_vec: &mut [u8], start_pos: usize) {
     let mut i: u8 = 0;
     while d_pos < de_vec.len() {
         if i == 0 {
-            n = vec[c_pos];
+            n = *vec.get(c_pos).ok_or_else(malformed)?;
             c_pos += 1;
             i = 1;
         }
         let mut r: usize = 0;
         if n & i != 0 {
-            let s = x[vec[c_pos] as usize];
-            c_pos += 1;
-            r = vec[c_pos] as usize;
-            c_pos += 1;
+            let (s, len) = match vec.get(c_pos..c_pos + 2) {
+                Some(&[k, len]) => (x[k as usize], len as usize),
+                _ => return Err(malformed()),
+            };
+            c_pos += 2;
+            r = len;
+            // a back reference copies from bytes already written before `d_pos`
+            if s >= d_pos || d_pos + r + 2 > de_vec.len() {
+                return Err(malformed());
+            }
             for j in 0..r + 2 {
                 de_vec[d_pos + j] = de_vec[s + j]
             }
             d_pos += 2;
         } else {
-            de_vec[d_pos] = vec[c_pos];
+            de_vec[d_pos] = *vec.get(c_pos).ok_or_else(malformed)?;
             d_pos += 1;
             c_pos += 1;
         }
@@ -892,6 +1151,7 @@ pub fn decompress(vec: &[u8], de_vec: &mut [u8], start_pos: usize) {
         }
         i <<= 1
     }
+    Ok(())
 }
 
 pub fn compress_with_max_size(vec: Vec<u8>, max_size: usize) -> Vec<u8> {
@@ -1080,30 +1340,11 @@ pub fn serialize(args: &SpicyObj) -> Result<Vec<u8>, SpicyError> {
         }
         // to table
         SpicyObj::DataFrame(k) => {
-            vec = Vec::with_capacity(k_length);
-            let column_names = k.get_column_names();
-            let column_count = column_names.len() as i32;
-            vec.write_all(&[98, 0, 99, 11, 0]).unwrap();
-            vec.write_all(&column_count.to_le_bytes()).unwrap();
-            column_names.into_iter().for_each(|s| {
-                vec.write_all(s.as_bytes()).unwrap();
-                vec.write_all(&[0]).unwrap();
-            });
-            vec.write_all(&[0, 0]).unwrap();
-            let columns = k.columns();
-            vec.write_all(&column_count.to_le_bytes()).unwrap();
-            let vectors = columns
-                .into_par_iter()
-                .map(|s| {
-                    serialize_series(
-                        s.as_materialized_series(),
-                        get_series_len(s.as_materialized_series()).unwrap(),
-                    )
-                })
-                .collect::<Result<Vec<Vec<u8>>, SpicyError>>()?;
-            vectors.into_iter().for_each(|v| {
-                vec.write_all(&v).unwrap();
-            });
+            vec = serialize_df(k, k_length)?;
+        }
+        // to keyed table
+        SpicyObj::KeyedTable(k, keys) => {
+            vec = serialize_keyed_table(k, *keys)?;
         }
         // to (::)
         SpicyObj::Null => {
@@ -1144,6 +1385,62 @@ pub fn serialize(args: &SpicyObj) -> Result<Vec<u8>, SpicyError> {
     Ok(vec)
 }
 
+fn serialize_df(k: &DataFrame, k_length: usize) -> Result<Vec<u8>, SpicyError> {
+    let mut vec = Vec::with_capacity(k_length);
+    let column_names = k.get_column_names();
+    let column_count = column_names.len() as i32;
+    vec.write_all(&[98, 0, 99, 11, 0]).unwrap();
+    vec.write_all(&column_count.to_le_bytes()).unwrap();
+    column_names.into_iter().for_each(|s| {
+        vec.write_all(s.as_bytes()).unwrap();
+        vec.write_all(&[0]).unwrap();
+    });
+    vec.write_all(&[0, 0]).unwrap();
+    let columns = k.columns();
+    vec.write_all(&column_count.to_le_bytes()).unwrap();
+    let vectors = columns
+        .into_par_iter()
+        .map(|s| {
+            serialize_series(
+                s.as_materialized_series(),
+                get_series_len(s.as_materialized_series()).unwrap(),
+            )
+        })
+        .collect::<Result<Vec<Vec<u8>>, SpicyError>>()?;
+    vectors.into_iter().for_each(|v| {
+        vec.write_all(&v).unwrap();
+    });
+    Ok(vec)
+}
+
+/// Serialize `df` as a q keyed table, the leading `keys` columns make up the key table and
+/// the rest the value table, i.e. `99` followed by two `98` tables. Deserializing it hstacks them back.
+fn serialize_keyed_table(df: &DataFrame, keys: usize) -> Result<Vec<u8>, SpicyError> {
+    if keys == 0 {
+        return Err(SpicyError::Err(
+            "keyed table requires key columns".to_owned(),
+        ));
+    }
+    if keys >= df.width() {
+        return Err(SpicyError::Err(
+            "keyed table requires at least one value column".to_owned(),
+        ));
+    }
+    let key_df = df
+        .select_by_range(0..keys)
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+    let value_df = df
+        .select_by_range(keys..)
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+    let key_len = SpicyObj::DataFrame(key_df.clone()).q6_len()?;
+    let value_len = SpicyObj::DataFrame(value_df.clone()).q6_len()?;
+    let mut vec = Vec::with_capacity(1 + key_len + value_len);
+    vec.push(99);
+    vec.write_all(&serialize_df(&key_df, key_len)?).unwrap();
+    vec.write_all(&serialize_df(&value_df, value_len)?).unwrap();
+    Ok(vec)
+}
+
 fn serialize_series(series: &Series, k_length: usize) -> Result<Vec<u8>, SpicyError> {
     let mut vec: Vec<u8> = Vec::with_capacity(k_length);
     let k_length = series.len();
@@ -1805,27 +2102,39 @@ fn serialize_series(series: &Series, k_length: usize) -> Result<Vec<u8>, SpicyEr
                     vec.write_all(&v).unwrap();
                 });
         }
-        PolarsDataType::Binary => {
+        // guid, k null guid is all zero bytes, the same as deserialize reads it back
+        PolarsDataType::Binary | PolarsDataType::BinaryOffset => {
             vec.write_all(&[2, 0]).unwrap();
             vec.write_all(&(k_length as i32).to_le_bytes()).unwrap();
-            let array = series.binary().unwrap();
-            array.chunks().iter().for_each(|arr| {
-                let arr = &**arr;
-                let arr = unsafe { &*(arr as *const dyn Array as *const BinaryViewArray) };
-                arr.into_iter().for_each(|b| match b {
-                    Some(b) => {
+            let series = series
+                .cast(&PolarsDataType::Binary)
+                .map_err(|e| SpicyError::Err(e.to_string()))?;
+            for b in series.binary().unwrap().into_iter() {
+                match b {
+                    Some(b) if b.len() == 16 => {
                         vec.write_all(b).unwrap();
                     }
+                    Some(b) => {
+                        return Err(SpicyError::Err(format!(
+                            "Only support 16 fixed size binary as guid, got {} bytes",
+                            b.len()
+                        )));
+                    }
                     None => {
-                        vec.write_all(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])
-                            .unwrap();
+                        vec.write_all(&[0u8; 16]).unwrap();
                     }
-                });
-            });
+                }
+            }
         }
         PolarsDataType::Null if k_length == 0 => {
             vec.write_all(&[0, 0, 0, 0, 0, 0]).unwrap();
         }
+        // q has no 128-bit integer, refuse rather than truncate
+        PolarsDataType::Int128 => {
+            return Err(SpicyError::NotAbleToSerializeErr(
+                "i128 series to q, cast to i64 first".to_owned(),
+            ));
+        }
         _ => {
             return Err(SpicyError::NotSupportedSeriesTypeErr(
                 series.dtype().clone(),
@@ -1855,7 +2164,7 @@ mod tests {
         .to_vec();
         let length = u32::from_le_bytes(vec[0..4].try_into().unwrap());
         let mut de_vec = vec![0; (length - 8) as usize];
-        decompress(&vec, &mut de_vec, 4);
+        decompress(&vec, &mut de_vec, 4).unwrap();
         let mut expected_vec = [1u8; 2006].to_vec();
         expected_vec[1] = 0;
         expected_vec[2] = 208;
@@ -1865,6 +2174,39 @@ mod tests {
         assert_eq!(de_vec, expected_vec);
     }
 
+    #[test]
+    fn decompress_malformed_msg() {
+        let vec: Vec<u8> = [
+            222, 7, 0, 0, 0, 1, 0, 208, 7, 0, 0, 1, 1, 255, 0, 255, 0, 255, 0, 255, 0, 255, 0, 255,
+            0, 255, 0, 255, 0, 197,
+        ]
+        .to_vec();
+        let mut de_vec = vec![0; 2006];
+        for len in 0..vec.len() {
+            assert!(decompress(&vec[..len], &mut de_vec, 4).is_err(), "{}", len);
+        }
+        // back reference before anything is written
+        assert!(decompress(&[0, 0, 0, 0, 1, 0, 0], &mut de_vec, 4).is_err());
+
+        // random flips and truncations must not panic
+        let mut seed: u64 = 42;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize
+        };
+        for _ in 0..1000 {
+            let mut fuzz = vec.clone();
+            for _ in 0..1 + next() % 4 {
+                let i = next() % fuzz.len();
+                fuzz[i] = next() as u8;
+            }
+            fuzz.truncate(1 + next() % vec.len());
+            let _ = decompress(&fuzz, &mut de_vec, 4);
+        }
+    }
+
     #[test]
     fn compress_msg() {
         let mut vec = [0u8; 2014].to_vec();
@@ -1925,6 +2267,30 @@ mod tests {
         assert_eq!(vec, serialize(&SpicyObj::Series(expect)).unwrap());
     }
 
+    #[test]
+    fn serialize_and_deserialize_guid_list() {
+        let guid = [
+            90, 231, 150, 45, 73, 242, 64, 77, 90, 236, 247, 200, 171, 186, 226, 136,
+        ];
+        let series = Series::new("guid".into(), [Some(&guid[..]), None]);
+        let vec = serialize(&SpicyObj::Series(series)).unwrap();
+        assert_eq!(&vec[..6], &[2, 0, 2, 0, 0, 0]);
+        assert_eq!(&vec[6..22], &guid);
+        assert_eq!(&vec[22..], &[0u8; 16]);
+        let k = deserialize(&vec, &mut 0, false).unwrap();
+        let series: Series = k.try_into().unwrap();
+        let bytes: Vec<&[u8]> = series.binary().unwrap().into_no_null_iter().collect();
+        assert_eq!(bytes, vec![&guid[..], &[0u8; 16][..]]);
+
+        let offset = Series::new("guid".into(), [&guid[..]])
+            .cast(&PolarsDataType::BinaryOffset)
+            .unwrap();
+        assert_eq!(&serialize(&SpicyObj::Series(offset)).unwrap()[6..], &guid);
+
+        let short = Series::new("guid".into(), [&guid[..8]]);
+        assert!(serialize(&SpicyObj::Series(short)).is_err());
+    }
+
     #[test]
     fn deserialize_and_serialize_byte_list() {
         let vec = [4, 0, 2, 0, 0, 0, 0, 1].to_vec();
@@ -2072,6 +2438,51 @@ mod tests {
         assert_eq!(vec, serialize(&SpicyObj::Series(expect)).unwrap());
     }
 
+    #[test]
+    fn deserialize_truncated_symbol_list() {
+        let vec = [11, 0, 3, 0, 0, 0, 97, 0, 0, 97, 98].to_vec();
+        let err = deserialize(&vec, &mut 0, false).unwrap_err();
+        assert!(matches!(err, SpicyError::DeserializationErr(_)));
+
+        let vec = [245, 97, 98].to_vec();
+        let err = deserialize(&vec, &mut 0, false).unwrap_err();
+        assert!(matches!(err, SpicyError::DeserializationErr(_)));
+    }
+
+    #[test]
+    fn deserialize_table_with_truncated_column_names() {
+        let vec = [98, 0, 99, 11, 0, 2, 0, 0, 0, 97, 0, 98].to_vec();
+        let err = deserialize(&vec, &mut 0, false).unwrap_err();
+        assert!(matches!(err, SpicyError::DeserializationErr(_)));
+    }
+
+    #[test]
+    fn deserialize_truncated_or_oversized_headers() {
+        for vec in [
+            vec![],
+            // long atom
+            vec![249, 1, 2],
+            // long list of u32::MAX items
+            vec![7, 0, 255, 255, 255, 255, 1],
+            // mixed list of u32::MAX items
+            vec![0, 0, 255, 255, 255, 255, 249],
+            // nested long list of negative length
+            vec![0, 0, 1, 0, 0, 0, 7, 0, 255, 255, 255, 255],
+            // dict missing its values
+            vec![99, 11, 0, 1, 0, 0, 0, 97, 0],
+        ] {
+            let err = deserialize(&vec, &mut 0, false).unwrap_err();
+            assert!(
+                matches!(err, SpicyError::DeserializationErr(_)),
+                "{:?}",
+                vec
+            );
+        }
+
+        let err = deserialize(&[128, 97, 98], &mut 0, false).unwrap_err();
+        assert!(matches!(err, SpicyError::ServerErr(e) if e == "ab"));
+    }
+
     #[test]
     fn deserialize_and_serialize_string_list() {
         let vec = [
@@ -2560,6 +2971,63 @@ mod tests {
         assert_eq!(vec, serialize(&expect).unwrap());
     }
 
+    #[test]
+    fn deserialize_deeply_nested_list() {
+        // each level is a general list of one item, around a long 1
+        let nest = |depth: usize, order: ByteOrder| -> Vec<u8> {
+            let (list, long) = match order {
+                ByteOrder::Little => ([0, 0, 1, 0, 0, 0], 1i64.to_le_bytes()),
+                ByteOrder::Big => ([0, 0, 0, 0, 0, 1], 1i64.to_be_bytes()),
+            };
+            let mut vec = list.repeat(depth);
+            vec.push(249);
+            vec.extend(long);
+            vec
+        };
+        let expect = (0..3).fold(SpicyObj::I64(1), |k, _| SpicyObj::MixedList(vec![k]));
+        for order in [ByteOrder::Little, ByteOrder::Big] {
+            let k = deserialize_with_order(&nest(3, order), &mut 0, false, order).unwrap();
+            assert_eq!(k, expect);
+            let err = deserialize_with_order(&nest(100_000, order), &mut 0, false, order)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("nested depth exceeds max"), "{:?}", order);
+        }
+    }
+
+    #[test]
+    fn deserialize_big_endian_frame() {
+        // `upd`t,([]a:1 2j), encoded by a big endian q process
+        let header = [0, 1, 0, 0, 0, 0, 0, 61];
+        let vec = [
+            0, 0, 0, 0, 0, 3, 245, 117, 112, 100, 0, 245, 116, 0, 98, 0, 99, 11, 0, 0, 0, 0, 1, 97,
+            0, 0, 0, 0, 0, 0, 1, 7, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2,
+        ]
+        .to_vec();
+        let (message_type, len, compression_mode, order) = crate::utils::decode_header6(&header);
+        assert_eq!(message_type, crate::MessageType::Sync);
+        assert_eq!(len, 8 + vec.len());
+        assert_eq!(compression_mode, 0);
+        assert_eq!(order, ByteOrder::Big);
+        let k = deserialize_with_order(&vec, &mut 0, false, order).unwrap();
+        let expect = SpicyObj::MixedList(vec![
+            SpicyObj::Symbol("upd".to_owned()),
+            SpicyObj::Symbol("t".to_owned()),
+            SpicyObj::DataFrame(
+                DataFrame::new(2, vec![Series::new("a".into(), [1i64, 2].as_ref()).into()])
+                    .unwrap(),
+            ),
+        ]);
+        assert_eq!(k, expect);
+
+        let vec = [247, 64, 9, 33, 251, 84, 68, 45, 24];
+        assert_eq!(
+            deserialize_with_order(&vec, &mut 0, false, ByteOrder::Big).unwrap(),
+            SpicyObj::F64(std::f64::consts::PI)
+        );
+        assert!(deserialize_with_order(&vec[..5], &mut 0, false, ByteOrder::Big).is_err());
+    }
+
     #[test]
     fn deserialize_and_serialize_mixed_list_string_and_empty_list() {
         let vec = [
@@ -2584,14 +3052,27 @@ mod tests {
         ]
         .to_vec();
         let k = deserialize(&vec, &mut 0, false).unwrap();
-        let df: DataFrame = k.try_into().unwrap();
         let s0 = Series::new("a".into(), [1i64].as_ref());
         let s1 = Series::new("b".into(), [1.0f64].as_ref());
         let expect = DataFrame::new(1, vec![s0.into(), s1.into()]).unwrap();
-        assert_eq!(df, expect);
+        assert_eq!(k, SpicyObj::KeyedTable(expect, 1));
         assert_eq!(vec, serialize(&SpicyObj::DataFrame(expect)).unwrap());
     }
 
+    #[test]
+    fn deserialize_table_with_empty_and_duplicate_column_names() {
+        let vec = [
+            98, 0, 99, 11, 0, 3, 0, 0, 0, 0, 97, 0, 97, 0, 0, 0, 3, 0, 0, 0, 7, 0, 1, 0, 0, 0, 1,
+            0, 0, 0, 0, 0, 0, 0, 7, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 7, 0, 1, 0, 0, 0, 3, 0,
+            0, 0, 0, 0, 0, 0,
+        ]
+        .to_vec();
+        let k = deserialize(&vec, &mut 0, false).unwrap();
+        let df: DataFrame = k.try_into().unwrap();
+        assert_eq!(df.get_column_names_str(), vec!["col00", "a", "a_1"]);
+        assert_eq!(df.column("a_1").unwrap().i64().unwrap().get(0), Some(3));
+    }
+
     #[test]
     fn deserialize_keyed_table() {
         let vec = [
@@ -2601,11 +3082,38 @@ mod tests {
         ]
         .to_vec();
         let k = deserialize(&vec, &mut 0, false).unwrap();
-        let df: DataFrame = k.try_into().unwrap();
         let s0 = Series::new("a".into(), [1i64].as_ref());
         let s1 = Series::new("b".into(), [1.0f64].as_ref());
         let expect = DataFrame::new(1, vec![s0.into(), s1.into()]).unwrap();
-        assert_eq!(df, expect);
+        assert_eq!(k, SpicyObj::KeyedTable(expect, 1));
+    }
+
+    #[test]
+    fn serialize_and_deserialize_keyed_table() {
+        let s0 = Series::new("a".into(), [1i64, 2].as_ref());
+        let s1 = Series::new("b".into(), [1.0f64, 2.0].as_ref());
+        let s2 = Series::new("c".into(), [3i64, 4].as_ref());
+        let df = DataFrame::new(2, vec![s0.into(), s1.into(), s2.into()]).unwrap();
+        let keyed = SpicyObj::KeyedTable(df.clone(), 1);
+        let vec = serialize(&keyed).unwrap();
+        assert_eq!(vec[..3], [99, 98, 0]);
+        let k = deserialize(&vec, &mut 0, false).unwrap();
+        assert_eq!(k, keyed);
+        let two_keys = SpicyObj::KeyedTable(df.clone(), 2);
+        let vec2 = serialize(&two_keys).unwrap();
+        assert_eq!(deserialize(&vec2, &mut 0, false).unwrap(), two_keys);
+
+        // the key table comes first, right after the 99 type byte
+        let mut pos = 1;
+        let key_df: DataFrame = deserialize(&vec, &mut pos, true)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(key_df, df.select(["a"]).unwrap());
+        assert_eq!(vec[pos], 98);
+
+        assert!(serialize(&SpicyObj::KeyedTable(df.clone(), 0)).is_err());
+        assert!(serialize(&SpicyObj::KeyedTable(df, 3)).is_err());
     }
 
     #[test]
@@ -2701,6 +3209,13 @@ mod tests {
         assert_eq!(serialize(&k).unwrap(), [101, 0]);
     }
 
+    #[test]
+    fn serialize_i128_series_errors() {
+        let k = SpicyObj::Series(polars::prelude::Series::new("".into(), [1i128, 2]));
+        let err = serialize(&k).unwrap_err();
+        assert!(err.to_string().contains("i128"));
+    }
+
     #[test]
     fn deserialize_and_serialize_dict() {
         let vec = [
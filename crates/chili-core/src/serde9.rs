@@ -8,11 +8,15 @@ use chili_parser::Language;
 use indexmap::IndexMap;
 // use ndarray::ArcArray2;
 use polars::{
+    frame::DataFrame,
     io::{
         SerReader, SerWriter,
         ipc::{IpcCompression, IpcStreamReader, IpcStreamWriter},
     },
-    prelude::{ArrowDataType, ArrowTimeUnit, Categories, DataType, StringNameSpaceImpl, TimeUnit},
+    prelude::{
+        ArrowDataType, ArrowTimeUnit, Categories, ChunkCompareEq, Column, DataType,
+        StringNameSpaceImpl, TimeUnit,
+    },
     series::Series,
 };
 use polars_arrow::{
@@ -55,6 +59,15 @@ static IPC_COMPRESS_ESTIMATE_RATIO: LazyLock<usize> = LazyLock::new(|| {
     }
 });
 
+// rows sampled from each column to probe its compressibility
+const IPC_COMPRESS_SAMPLE_SIZE: usize = 4096;
+
+// a sampled column where at least 1/4 of the rows repeat the previous one is compressible
+const IPC_COMPRESS_REPEAT_RATIO: usize = 4;
+
+// an integer sample spanning fewer values than this leaves most bytes zero and compresses well
+const IPC_COMPRESS_NARROW_RANGE: f64 = 65536.0;
+
 static IPC_COMPRESSION: LazyLock<Option<IpcCompression>> = LazyLock::new(|| {
     let network_bandwidth = env::var("CHILI_NETWORK_BANDWIDTH")
         .unwrap_or_default()
@@ -398,12 +411,18 @@ pub fn deserialize(vec: &[u8], pos: &mut usize) -> SpicyResult<SpicyObj> {
         }
         // dataframe
         92 => {
+            let is_segmented = vec[*pos - 3] == 1;
             *pos += 4;
             let byte_len = u64::from_le_bytes(vec[*pos..*pos + 8].try_into().unwrap()) as usize;
             *pos += 8;
-            let df = IpcStreamReader::new(Cursor::new(take(vec, *pos, byte_len)?))
-                .finish()
-                .map_err(|e| SpicyError::Err(e.to_string()))?;
+            let bytes = take(vec, *pos, byte_len)?;
+            let df = if is_segmented {
+                deserialize_df_segments(bytes)?
+            } else {
+                IpcStreamReader::new(Cursor::new(bytes))
+                    .finish()
+                    .map_err(|e| SpicyError::Err(e.to_string()))?
+            };
             obj = SpicyObj::DataFrame(df);
             *pos += byte_len + PADDING[byte_len % 8].len();
         }
@@ -936,22 +955,31 @@ pub fn serialize(args: &SpicyObj, compress: bool) -> SpicyResult<Vec<Vec<u8>>> {
             }
         }
         SpicyObj::DataFrame(df) => {
-            let mut df = df.clone();
             let estimated_size = df.estimated_size();
-            let (compression, ratio) = if compress && estimated_size > IPC_COMPRESS_THRESHOLD {
-                (*IPC_COMPRESSION, *IPC_COMPRESS_ESTIMATE_RATIO)
+            let segments = if compress && estimated_size > IPC_COMPRESS_THRESHOLD {
+                split_df_by_compressibility(df)
             } else {
-                (None, 1009)
+                vec![(df.clone(), false)]
             };
-            let mut buf = Vec::with_capacity(1699 + (estimated_size * ratio / 1000));
-            IpcStreamWriter::new(&mut buf)
-                .with_compression(compression)
-                .finish(&mut df)
-                .map_err(|e| SpicyError::NotAbleToSerializeErr(e.to_string()))?;
-            buf.write_all(PADDING[buf.len() % 8]).unwrap();
-            let mut header = vec![code, 0, 0, 0, 0, 0, 0, 0];
-            header.extend_from_slice(&(buf.len() as u64).to_le_bytes());
-            Ok(vec![header, buf])
+            // a frame whose columns all agree keeps the single ipc stream layout
+            if segments.len() == 1 {
+                let (mut df, compressible) = segments.into_iter().next().unwrap();
+                let buf = serialize_df_segment(&mut df, compressible)?;
+                let mut header = vec![code, 0, 0, 0, 0, 0, 0, 0];
+                header.extend_from_slice(&(buf.len() as u64).to_le_bytes());
+                Ok(vec![header, buf])
+            } else {
+                let mut buf = Vec::with_capacity(estimated_size);
+                for (mut df, compressible) in segments {
+                    let segment = serialize_df_segment(&mut df, compressible)?;
+                    buf.write_all(&(segment.len() as u64).to_le_bytes())
+                        .unwrap();
+                    buf.write_all(&segment).unwrap();
+                }
+                let mut header = vec![code, 1, 0, 0, 0, 0, 0, 0];
+                header.extend_from_slice(&(buf.len() as u64).to_le_bytes());
+                Ok(vec![header, buf])
+            }
         }
         SpicyObj::Null => Ok(vec![vec![code, 0, 0, 0, 0, 0, 0, 0]]),
         SpicyObj::Fn(f) if f.part_args.is_none() => {
@@ -965,6 +993,8 @@ pub fn serialize(args: &SpicyObj, compress: bool) -> SpicyResult<Vec<Vec<u8>>> {
             buf.write_all(PADDING[fn_body.len() % 8]).unwrap();
             Ok(vec![buf])
         }
+        // key columns are a q notion, chili peers get the plain frame
+        SpicyObj::KeyedTable(df, _) => serialize(&SpicyObj::DataFrame(df.clone()), compress),
         _ => Err(SpicyError::NotAbleToSerializeErr(format!(
             "not support spicy obj type {}",
             args.get_type_name()
@@ -972,6 +1002,96 @@ pub fn serialize(args: &SpicyObj, compress: bool) -> SpicyResult<Vec<Vec<u8>>> {
     }
 }
 
+// Arrow IPC applies one codec to every buffer of a record batch, so consecutive columns
+// sharing a compressibility verdict are written as one ipc stream each. A frame where all
+// columns agree is a single stream, the same layout as before segmenting existed.
+fn split_df_by_compressibility(df: &DataFrame) -> Vec<(DataFrame, bool)> {
+    let mut segments: Vec<(Vec<Column>, bool)> = Vec::new();
+    for column in df.columns() {
+        let compressible = is_column_compressible(column.as_materialized_series());
+        match segments.last_mut() {
+            Some((columns, c)) if *c == compressible => columns.push(column.clone()),
+            _ => segments.push((vec![column.clone()], compressible)),
+        }
+    }
+    segments
+        .into_iter()
+        .map(|(columns, compressible)| {
+            (DataFrame::new(df.height(), columns).unwrap(), compressible)
+        })
+        .collect()
+}
+
+// Probe a head sample of the column without hashing: dictionary-like and text dtypes
+// compress well, numeric columns do when the sample repeats itself or spans a narrow range.
+fn is_column_compressible(series: &Series) -> bool {
+    match series.dtype() {
+        DataType::Boolean
+        | DataType::String
+        | DataType::Categorical(_, _)
+        | DataType::Enum(_, _)
+        | DataType::Null => return true,
+        dtype if !dtype.to_physical().is_primitive_numeric() => return false,
+        _ => {}
+    }
+    let sample = series.slice(0, IPC_COMPRESS_SAMPLE_SIZE).to_physical_repr();
+    if sample.len() < 2 {
+        return false;
+    }
+    let repeats = sample
+        .equal_missing(&sample.shift(1))
+        .map(|mask| mask.sum().unwrap_or(0) as usize)
+        .unwrap_or(0);
+    if repeats * IPC_COMPRESS_REPEAT_RATIO >= sample.len() {
+        return true;
+    }
+    if sample.dtype().is_integer() {
+        let min = sample.min::<f64>().ok().flatten();
+        let max = sample.max::<f64>().ok().flatten();
+        if let (Some(min), Some(max)) = (min, max) {
+            return max - min < IPC_COMPRESS_NARROW_RANGE;
+        }
+    }
+    false
+}
+
+fn serialize_df_segment(df: &mut DataFrame, compressible: bool) -> SpicyResult<Vec<u8>> {
+    let (compression, ratio) = if compressible {
+        (*IPC_COMPRESSION, *IPC_COMPRESS_ESTIMATE_RATIO)
+    } else {
+        (None, 1009)
+    };
+    let mut buf = Vec::with_capacity(1699 + (df.estimated_size() * ratio / 1000));
+    IpcStreamWriter::new(&mut buf)
+        .with_compression(compression)
+        .finish(df)
+        .map_err(|e| SpicyError::NotAbleToSerializeErr(e.to_string()))?;
+    buf.write_all(PADDING[buf.len() % 8]).unwrap();
+    Ok(buf)
+}
+
+fn deserialize_df_segments(bytes: &[u8]) -> SpicyResult<DataFrame> {
+    let mut pos = 0;
+    let mut df: Option<DataFrame> = None;
+    while pos < bytes.len() {
+        let segment_len = u64::from_le_bytes(take(bytes, pos, 8)?.try_into().unwrap()) as usize;
+        pos += 8;
+        let segment = IpcStreamReader::new(Cursor::new(take(bytes, pos, segment_len)?))
+            .finish()
+            .map_err(|e| SpicyError::Err(e.to_string()))?;
+        pos += segment_len;
+        df = Some(match df {
+            Some(mut df) => {
+                df.hstack_mut(segment.columns())
+                    .map_err(|e| SpicyError::DeserializationErr(e.to_string()))?;
+                df
+            }
+            None => segment,
+        });
+    }
+    df.ok_or_else(|| SpicyError::DeserializationErr("serde9: empty frame segments".to_owned()))
+}
+
 // byte_len, offset, length, v8
 fn serialize_bitmap(bitmap: &Bitmap) -> Vec<Vec<u8>> {
     let (v8, offset, length) = bitmap.as_slice();
@@ -987,11 +1107,14 @@ fn serialize_bitmap(bitmap: &Bitmap) -> Vec<Vec<u8>> {
 #[cfg(test)]
 mod tests {
     use crate::SpicyObj;
-    use crate::{serde9::deserialize, serde9::serialize, serde9::serialize_err};
+    use crate::{
+        serde9::deserialize, serde9::is_column_compressible, serde9::serialize,
+        serde9::serialize_err,
+    };
     use indexmap::IndexMap;
     use polars::prelude::{Categories, DataType, TimeUnit};
     // use ndarray::ArcArray2;
-    use polars::{df, prelude::NamedFrom, series::Series};
+    use polars::{df, frame::DataFrame, prelude::NamedFrom, series::Series};
 
     fn serialize_as_v8(args: &SpicyObj) -> Vec<u8> {
         serialize(args, false)
@@ -1455,4 +1578,59 @@ mod tests {
         assert_eq!(serialize_as_v8(&obj), v8);
         assert_eq!(deserialize(v8, &mut 0).unwrap(), obj);
     }
+
+    fn random_i64s(n: u64) -> Vec<i64> {
+        (0..n)
+            .map(|i| {
+                i.wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407) as i64
+            })
+            .collect()
+    }
+
+    #[test]
+    fn column_compressible_by_cardinality() {
+        let n = 200_000;
+        let low = Series::new(
+            "low".into(),
+            (0..n).map(|i| (i * 7919 % 5) as i64).collect::<Vec<_>>(),
+        );
+        let high = Series::new("high".into(), random_i64s(n));
+        assert!(is_column_compressible(&low));
+        assert!(is_column_compressible(&Series::new(
+            "constant".into(),
+            vec![1.5f64; n as usize]
+        )));
+        assert!(!is_column_compressible(&high));
+
+        // only the mixed frame is split into a compressed and a raw segment
+        let mixed = DataFrame::new(n as usize, vec![low.into(), high.clone().into()]).unwrap();
+        let v8 = serialize(&SpicyObj::DataFrame(mixed), true).unwrap();
+        assert_eq!(v8[0][1], 1);
+        let v8 = serialize(&SpicyObj::DataFrame(high.into_frame()), true).unwrap();
+        assert_eq!(v8[0][1], 0);
+    }
+
+    #[test]
+    fn serde_compressed_df() {
+        let n = 200_000;
+        let constant = Series::new("constant".into(), vec![1i64; n as usize]);
+        let random = Series::new("random".into(), random_i64s(n));
+        for df in [
+            DataFrame::new(
+                n as usize,
+                vec![constant.clone().into(), random.clone().into()],
+            )
+            .unwrap(),
+            random.clone().into_frame(),
+        ] {
+            let obj = SpicyObj::DataFrame(df);
+            let v8: Vec<u8> = serialize(&obj, true)
+                .unwrap()
+                .into_iter()
+                .flatten()
+                .collect();
+            assert_eq!(deserialize(&v8, &mut 0).unwrap(), obj);
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use log::{info, warn};
 use polars::prelude::{IntoLazy, SortMultipleOptions, SortOptions, col};
 use polars::series::ops::NullBehavior;
@@ -5,11 +6,13 @@ use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::collections::HashMap;
 use std::fs;
 use std::sync::LazyLock;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use crate::check::check;
 use crate::errors::{SpicyError, SpicyResult};
 use crate::eval::{eval_call, eval_fn_call, eval_for_console, eval_for_ide, eval_op};
 use crate::func::Func;
+use crate::stack::EvalBudget;
 use crate::utils::convert_list_to_df;
 use crate::{ArgType, EngineState, SpicyObj, Stack, eval_query, job, validate_args};
 
@@ -23,6 +26,55 @@ fn time_it(state: &EngineState, stack: &mut Stack, args: &[&SpicyObj]) -> SpicyR
     Ok(SpicyObj::F64(start.elapsed().as_secs_f64()))
 }
 
+// fn_args as for eval, or a no-arg fn, and times
+fn time_it_stats(
+    state: &EngineState,
+    stack: &mut Stack,
+    args: &[&SpicyObj],
+) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Any, ArgType::Int])?;
+    let times = args[1].to_i64()?;
+    if times <= 0 {
+        return Err(SpicyError::EvalErr(format!(
+            "timeit_stats requires a positive number of runs, got {}",
+            times
+        )));
+    }
+    // a string is parsed once up front, so the runs only time its evaluation
+    let nodes = match args[0] {
+        SpicyObj::String(s) => Some(
+            state
+                .parse("", s)
+                .map_err(|e| SpicyError::EvalErr(e.to_string()))?,
+        ),
+        _ => None,
+    };
+    let mut elapsed: Vec<i64> = Vec::with_capacity(times as usize);
+    for _ in 0..times {
+        let nodes = nodes.clone();
+        let start = Instant::now();
+        match (args[0], nodes) {
+            (SpicyObj::String(s), Some(nodes)) => state.eval_ast(nodes, "", s)?,
+            (SpicyObj::Fn(f), _) => eval_fn_call(state, stack, f, &vec![])?,
+            _ => eval_op(state, stack, &args[0..1])?,
+        };
+        elapsed.push(start.elapsed().as_nanos() as i64);
+    }
+    let total: i64 = elapsed.iter().sum();
+    Ok(SpicyObj::Dict(IndexMap::from([
+        ("runs".to_owned(), SpicyObj::I64(times)),
+        (
+            "min".to_owned(),
+            SpicyObj::Duration(*elapsed.iter().min().unwrap()),
+        ),
+        ("mean".to_owned(), SpicyObj::Duration(total / times)),
+        (
+            "max".to_owned(),
+            SpicyObj::Duration(*elapsed.iter().max().unwrap()),
+        ),
+    ])))
+}
+
 fn parallel(state: &EngineState, stack: &mut Stack, args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let f = args[0];
     let vec = args[1].as_vec()?;
@@ -76,6 +128,26 @@ fn exists_handle(
     state.exists_handle(&handle_num)
 }
 
+fn get_ipc_version(
+    state: &EngineState,
+    _stack: &mut Stack,
+    args: &[&SpicyObj],
+) -> SpicyResult<SpicyObj> {
+    let handle_num = args[0].to_i64()?;
+    state.get_ipc_version(&handle_num)
+}
+
+fn set_ipc_version(
+    state: &EngineState,
+    _stack: &mut Stack,
+    args: &[&SpicyObj],
+) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Int, ArgType::Int])?;
+    let handle_num = args[0].to_i64()?;
+    let version = args[1].to_i64()?;
+    state.set_ipc_version(&handle_num, version)
+}
+
 fn fsync_handle(
     state: &EngineState,
     _stack: &mut Stack,
@@ -100,6 +172,46 @@ fn exit(state: &EngineState, _stack: &mut Stack, args: &[&SpicyObj]) -> SpicyRes
     std::process::exit(exit_code as i32)
 }
 
+fn on_exit(state: &EngineState, _stack: &mut Stack, args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    if !args[0].is_fn() {
+        return Err(SpicyError::EvalErr(format!(
+            "on_exit requires a function, got '{}'",
+            args[0].get_type_name()
+        )));
+    }
+    Ok(SpicyObj::I64(state.add_exit_hook(args[0].clone()) as i64))
+}
+
+// source string, parsed with the repl syntax
+fn ast(state: &EngineState, _stack: &mut Stack, args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Str])?;
+    let path = if state.is_repl_use_chili_syntax() {
+        "ast.chi"
+    } else {
+        "ast.pep"
+    };
+    let nodes = state.parse(path, args[0].str()?)?;
+    let exprs: Vec<String> = nodes.iter().map(|n| n.to_sexpr()).collect();
+    Ok(SpicyObj::String(exprs.join("\n")))
+}
+
+fn set_compression(
+    state: &EngineState,
+    _stack: &mut Stack,
+    args: &[&SpicyObj],
+) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Int])?;
+    let threshold = args[0].to_i64()?;
+    if threshold < 0 {
+        return Err(SpicyError::EvalErr(format!(
+            "compression threshold must be 0 (disabled) or a byte size, got {}",
+            threshold
+        )));
+    }
+    state.set_compression(threshold as usize);
+    Ok(SpicyObj::I64(state.get_compression() as i64))
+}
+
 fn upsert(state: &EngineState, _stack: &mut Stack, args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::Any, ArgType::DataFrameOrList])?;
     let arg0 = args[0];
@@ -325,10 +437,11 @@ fn tick(state: &EngineState, _stack: &mut Stack, args: &[&SpicyObj]) -> SpicyRes
     state.tick(index, inc)
 }
 
-fn set(state: &EngineState, _stack: &mut Stack, args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+fn set(state: &EngineState, stack: &mut Stack, args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let id = args[0].str()?;
     let value = args[1];
     state.set_var(id, value.clone())?;
+    state.claim_var(&stack.user, id);
     Ok(args[0].clone())
 }
 
@@ -507,6 +620,75 @@ fn list(state: &EngineState, _stack: &mut Stack, args: &[&SpicyObj]) -> SpicyRes
     Ok(SpicyObj::DataFrame(df))
 }
 
+fn budget_limit(key: &str, value: &SpicyObj) -> SpicyResult<u64> {
+    let limit = match value {
+        SpicyObj::Duration(ns) if key == "time" => *ns,
+        _ if value.is_integer() => value.to_i64()?,
+        _ => {
+            return Err(SpicyError::Err(format!(
+                "eval budget '{}' expects an integer{}, got '{}'",
+                key,
+                if key == "time" { " or duration" } else { "" },
+                value.get_type_name()
+            )));
+        }
+    };
+    u64::try_from(limit).map_err(|_| {
+        SpicyError::Err(format!(
+            "eval budget '{}' cannot be negative, got {}",
+            key, limit
+        ))
+    })
+}
+
+// query, budget of `rows`, `time` (duration or ms) and `bytes`
+fn eval_budget(
+    state: &EngineState,
+    stack: &mut Stack,
+    args: &[&SpicyObj],
+) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Any, ArgType::Dict])?;
+    let mut max_rows = 0;
+    let mut max_time = None;
+    let mut max_bytes = 0;
+    for (key, value) in args[1].dict()?.iter() {
+        let limit = budget_limit(key, value)?;
+        match key.as_str() {
+            "rows" => max_rows = limit as usize,
+            "time" if value.is_integer() => max_time = Some(Duration::from_millis(limit)),
+            "time" => max_time = Some(Duration::from_nanos(limit)),
+            "bytes" => max_bytes = limit as usize,
+            _ => {
+                return Err(SpicyError::Err(format!(
+                    "unknown eval budget '{}', expect one of rows, time, bytes",
+                    key
+                )));
+            }
+        }
+    }
+    let mut budget = EvalBudget::new(max_rows, max_time, max_bytes);
+    if let Some(outer) = &stack.budget {
+        budget = budget.within(outer);
+    }
+    match args[0] {
+        SpicyObj::String(source) => {
+            let mut str_stack = Stack::new(None, 0, stack.h, &stack.user);
+            str_stack.budget = Some(budget);
+            state.eval_str_on(&mut str_stack, source)
+        }
+        query => {
+            let outer = stack.budget.replace(budget);
+            let res = eval_op(state, stack, &[query]);
+            stack.budget = outer;
+            res
+        }
+    }
+}
+
+fn snapshot(state: &EngineState, _stack: &mut Stack, _args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    state.snapshot()
+}
+
 fn partition(state: &EngineState, _stack: &mut Stack, args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let name = args[0].str()?;
     state.get_par_df(name).map(SpicyObj::ParDataFrame)
@@ -546,6 +728,23 @@ pub static SIDE_EFFECT_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["fn_args", "times"],
             ),
         ),
+        (
+            "timeit_stats".to_owned(),
+            Func::new_side_effect_built_in_fn(
+                Some(Box::new(time_it_stats)),
+                2,
+                "timeit_stats",
+                &["fn_args", "times"],
+            ),
+        ),
+        (
+            "check".to_owned(),
+            Func::new_side_effect_built_in_fn(Some(Box::new(check)), 1, "check", &["source"]),
+        ),
+        (
+            "ast".to_owned(),
+            Func::new_side_effect_built_in_fn(Some(Box::new(ast)), 1, "ast", &["source"]),
+        ),
         (
             "parallel".to_owned(),
             Func::new_side_effect_built_in_fn(
@@ -563,6 +762,19 @@ pub static SIDE_EFFECT_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "exit".to_owned(),
             Func::new_side_effect_built_in_fn(Some(Box::new(exit)), 1, "exit", &["exit_code"]),
         ),
+        (
+            "on_exit".to_owned(),
+            Func::new_side_effect_built_in_fn(Some(Box::new(on_exit)), 1, "on_exit", &["f"]),
+        ),
+        (
+            "set_compression".to_owned(),
+            Func::new_side_effect_built_in_fn(
+                Some(Box::new(set_compression)),
+                1,
+                "set_compression",
+                &["threshold"],
+            ),
+        ),
         (
             "upsert".to_owned(),
             Func::new_side_effect_built_in_fn(
@@ -667,6 +879,19 @@ pub static SIDE_EFFECT_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "list".to_owned(),
             Func::new_side_effect_built_in_fn(Some(Box::new(list)), 1, "list", &["pattern"]),
         ),
+        (
+            "eval_budget".to_owned(),
+            Func::new_side_effect_built_in_fn(
+                Some(Box::new(eval_budget)),
+                2,
+                "eval_budget",
+                &["query", "budget"],
+            ),
+        ),
+        (
+            "snapshot".to_owned(),
+            Func::new_side_effect_built_in_fn(Some(Box::new(snapshot)), 0, "snapshot", &[]),
+        ),
         (
             ".handle.list".to_owned(),
             Func::new_side_effect_built_in_fn(Some(Box::new(list_handle)), 0, ".handle.list", &[]),
@@ -734,6 +959,24 @@ pub static SIDE_EFFECT_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["handle_num", "uri"],
             ),
         ),
+        (
+            ".handle.ipcVersion".to_owned(),
+            Func::new_side_effect_built_in_fn(
+                Some(Box::new(get_ipc_version)),
+                1,
+                ".handle.ipcVersion",
+                &["handle_num"],
+            ),
+        ),
+        (
+            ".handle.setIpcVersion".to_owned(),
+            Func::new_side_effect_built_in_fn(
+                Some(Box::new(set_ipc_version)),
+                2,
+                ".handle.setIpcVersion",
+                &["handle_num", "version"],
+            ),
+        ),
         (
             ".handle.fsync".to_owned(),
             Func::new_side_effect_built_in_fn(
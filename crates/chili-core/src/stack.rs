@@ -1,7 +1,102 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use crate::{SpicyObj, errors::SpicyError, func::Func};
 
+/// Resource limits for one evaluation, `0`/`None` disables a limit.
+///
+/// Checked between ast nodes, so a single long running op finishes before the time budget
+/// or the cancel flag aborts the eval. Rows and bytes are checked on every materialized
+/// node result, lazy frames are not counted until collected.
+#[derive(Debug, Clone, Default)]
+pub struct EvalBudget {
+    pub max_rows: usize,
+    pub max_bytes: usize,
+    pub deadline: Option<Instant>,
+    pub cancelled: Arc<AtomicBool>,
+}
+
+impl PartialEq for EvalBudget {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_rows == other.max_rows
+            && self.max_bytes == other.max_bytes
+            && self.deadline == other.deadline
+            && Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+}
+
+impl EvalBudget {
+    pub fn new(max_rows: usize, max_time: Option<Duration>, max_bytes: usize) -> Self {
+        Self {
+            max_rows,
+            max_bytes,
+            deadline: max_time.and_then(|t| Instant::now().checked_add(t)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// The tighter of each limit, so a budget nested in `outer` can't loosen it. Cancelling
+    /// `outer` still aborts the nested eval.
+    pub fn within(self, outer: &EvalBudget) -> Self {
+        let tighter = |a: usize, b: usize| match (a, b) {
+            (0, b) => b,
+            (a, 0) => a,
+            (a, b) => a.min(b),
+        };
+        Self {
+            max_rows: tighter(self.max_rows, outer.max_rows),
+            max_bytes: tighter(self.max_bytes, outer.max_bytes),
+            deadline: match (self.deadline, outer.deadline) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+            cancelled: Arc::clone(&outer.cancelled),
+        }
+    }
+
+    /// Abort every eval sharing this budget at its next checkpoint.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn check_time(&self) -> Result<(), SpicyError> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(SpicyError::Err("eval cancelled".to_owned()));
+        }
+        if let Some(deadline) = self.deadline
+            && Instant::now() >= deadline
+        {
+            return Err(SpicyError::Err("eval time budget exceeded".to_owned()));
+        }
+        Ok(())
+    }
+
+    pub fn check_obj(&self, obj: &SpicyObj) -> Result<(), SpicyError> {
+        let (rows, bytes) = match obj {
+            SpicyObj::Series(s) => (s.len(), s.estimated_size()),
+            SpicyObj::DataFrame(df) => (df.height(), df.estimated_size()),
+            SpicyObj::Matrix(m) => (m.nrows(), m.len() * size_of::<f64>()),
+            SpicyObj::MixedList(l) => (l.len(), 0),
+            _ => return Ok(()),
+        };
+        if self.max_rows > 0 && rows > self.max_rows {
+            return Err(SpicyError::Err(format!(
+                "eval row budget exceeded, got {} rows, limit is {}",
+                rows, self.max_rows
+            )));
+        }
+        if self.max_bytes > 0 && bytes > self.max_bytes {
+            return Err(SpicyError::Err(format!(
+                "eval memory budget exceeded, got {} bytes, limit is {}",
+                bytes, self.max_bytes
+            )));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Stack<'a> {
     pub vars: HashMap<String, SpicyObj>,
@@ -10,6 +105,8 @@ pub struct Stack<'a> {
     pub stack_layer: usize,
     pub h: i64,
     pub user: String,
+    /// Inherited by nested fn calls, `None` for unbudgeted eval.
+    pub budget: Option<EvalBudget>,
 }
 
 impl<'a> Default for Stack<'a> {
@@ -27,6 +124,7 @@ impl<'a> Stack<'a> {
             stack_layer,
             h,
             user: user.to_owned(),
+            budget: None,
         }
     }
 
@@ -0,0 +1,216 @@
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::time::Duration;
+
+// tls ships with the ipc feature, a lean build only serves plain tcp
+#[cfg(feature = "ipc")]
+use {
+    crate::{SpicyError, SpicyResult},
+    parking_lot::Mutex,
+    rustls::pki_types::pem::PemObject,
+    rustls::pki_types::{CertificateDer, PrivateKeyDer},
+    rustls::{ServerConfig, ServerConnection},
+    std::sync::Arc,
+};
+
+/// A peer that has not finished the handshake by then is dropped.
+#[cfg(feature = "ipc")]
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[cfg(feature = "ipc")]
+/// Load a PEM certificate chain and private key into a server TLS config.
+pub fn load_server_config(cert_path: &str, key_path: &str) -> SpicyResult<Arc<ServerConfig>> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| SpicyError::Err(format!("failed to read tls cert '{}', {}", cert_path, e)))?;
+    if certs.is_empty() {
+        return Err(SpicyError::Err(format!(
+            "no certificate found in tls cert '{}'",
+            cert_path
+        )));
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|e| SpicyError::Err(format!("failed to read tls key '{}', {}", key_path, e)))?;
+    let config =
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| SpicyError::Err(e.to_string()))?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| SpicyError::Err(format!("invalid tls cert or key, {}", e)))?;
+    Ok(Arc::new(config))
+}
+
+#[cfg(feature = "ipc")]
+/// Server side TLS stream over a `TcpStream`.
+///
+/// Clones share one TLS session but own their socket dup, so the conn handler can
+/// block reading while other threads write to the same handle. The session lock is
+/// only held while moving bytes in and out of it, never across a blocking socket read.
+pub struct TlsStream {
+    conn: Arc<Mutex<ServerConnection>>,
+    sock: TcpStream,
+}
+
+#[cfg(feature = "ipc")]
+impl TlsStream {
+    /// Complete the TLS handshake on an accepted socket, giving up after `HANDSHAKE_TIMEOUT`.
+    pub fn accept(config: Arc<ServerConfig>, mut sock: TcpStream) -> io::Result<Self> {
+        let mut conn = ServerConnection::new(config).map_err(io::Error::other)?;
+        sock.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        sock.set_write_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        while conn.is_handshaking() {
+            conn.complete_io(&mut sock)?;
+        }
+        sock.set_read_timeout(None)?;
+        sock.set_write_timeout(None)?;
+        Ok(TlsStream {
+            conn: Arc::new(Mutex::new(conn)),
+            sock,
+        })
+    }
+
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(TlsStream {
+            conn: Arc::clone(&self.conn),
+            sock: self.sock.try_clone()?,
+        })
+    }
+
+    fn flush_tls(conn: &mut ServerConnection, sock: &mut TcpStream) -> io::Result<()> {
+        while conn.wants_write() {
+            conn.write_tls(sock)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ipc")]
+impl Read for TlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut tls_buf = [0u8; 16 * 1024];
+        loop {
+            match self.conn.lock().reader().read(buf) {
+                Ok(n) => return Ok(n),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => return Err(e),
+            }
+            let n = self.sock.read(&mut tls_buf)?;
+            if n == 0 {
+                return Ok(0);
+            }
+            let mut conn = self.conn.lock();
+            let mut records = &tls_buf[..n];
+            while !records.is_empty() {
+                conn.read_tls(&mut records)?;
+                conn.process_new_packets()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            }
+            // alerts and key updates
+            Self::flush_tls(&mut conn, &mut self.sock)?;
+        }
+    }
+}
+
+#[cfg(feature = "ipc")]
+impl Write for TlsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut conn = self.conn.lock();
+        let n = conn.writer().write(buf)?;
+        Self::flush_tls(&mut conn, &mut self.sock)?;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut conn = self.conn.lock();
+        conn.writer().flush()?;
+        Self::flush_tls(&mut conn, &mut self.sock)?;
+        self.sock.flush()
+    }
+}
+
+/// An accepted IPC connection, plain or TLS.
+pub enum IpcStream {
+    Tcp(TcpStream),
+    #[cfg(feature = "ipc")]
+    Tls(TlsStream),
+}
+
+impl IpcStream {
+    /// The underlying socket, for socket options and shutdown.
+    pub fn tcp(&self) -> &TcpStream {
+        match self {
+            IpcStream::Tcp(s) => s,
+            #[cfg(feature = "ipc")]
+            IpcStream::Tls(s) => &s.sock,
+        }
+    }
+
+    pub fn try_clone(&self) -> io::Result<Self> {
+        match self {
+            IpcStream::Tcp(s) => s.try_clone().map(IpcStream::Tcp),
+            #[cfg(feature = "ipc")]
+            IpcStream::Tls(s) => s.try_clone().map(IpcStream::Tls),
+        }
+    }
+
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.tcp().peer_addr()
+    }
+
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.tcp().shutdown(how)
+    }
+
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.tcp().set_nodelay(nodelay)
+    }
+
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.tcp().set_read_timeout(timeout)
+    }
+}
+
+impl Read for IpcStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            IpcStream::Tcp(s) => s.read(buf),
+            #[cfg(feature = "ipc")]
+            IpcStream::Tls(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for IpcStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            IpcStream::Tcp(s) => s.write(buf),
+            #[cfg(feature = "ipc")]
+            IpcStream::Tls(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            IpcStream::Tcp(s) => s.flush(),
+            #[cfg(feature = "ipc")]
+            IpcStream::Tls(s) => s.flush(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ipc"))]
+mod tests {
+    use super::load_server_config;
+
+    #[test]
+    fn load_server_config_rejects_missing_or_empty_pem() {
+        let err = load_server_config("missing.crt", "missing.key").unwrap_err();
+        assert!(err.to_string().contains("failed to read tls cert"));
+
+        let cert = tempfile::NamedTempFile::new().unwrap();
+        let path = cert.path().to_str().unwrap();
+        let err = load_server_config(path, path).unwrap_err();
+        assert!(err.to_string().contains("no certificate found"));
+    }
+}
@@ -16,7 +16,9 @@ use polars::{
 };
 use regex::Regex;
 
-use crate::{ConnType, EngineState, Stack, engine_state::ReadWrite, serde6, serde9};
+#[cfg(feature = "ipc")]
+use crate::serde6;
+use crate::{ConnType, EngineState, Stack, engine_state::ReadWrite, serde9};
 
 /// A thin wrapper around [`std::fs::File`] that makes [`Write::flush`] call
 /// [`File::sync_data`] (`fdatasync`), so that an explicit `.flush()` guarantees
@@ -335,33 +337,161 @@ pub fn send_auth(
     Ok(version[0])
 }
 
+/// Byte order of a q ipc frame, flagged by the first byte of its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    Big,
+    Little,
+}
+
+impl ByteOrder {
+    pub fn from_header(flag: u8) -> Self {
+        if flag == 0 {
+            ByteOrder::Big
+        } else {
+            ByteOrder::Little
+        }
+    }
+}
+
+pub(crate) fn read_bytes<const N: usize>(
+    vec: &[u8],
+    pos: usize,
+    order: ByteOrder,
+) -> Result<[u8; N], SpicyError> {
+    let mut bytes: [u8; N] = vec
+        .get(pos..pos + N)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| {
+            SpicyError::DeserializationErr(format!(
+                "expect {} bytes at position {}, got buffer of {} bytes",
+                N,
+                pos,
+                vec.len()
+            ))
+        })?;
+    if order == ByteOrder::Big {
+        bytes.reverse();
+    }
+    Ok(bytes)
+}
+
+pub fn read_u32(vec: &[u8], pos: usize, order: ByteOrder) -> Result<u32, SpicyError> {
+    read_bytes(vec, pos, order).map(u32::from_le_bytes)
+}
+
+pub fn read_u64(vec: &[u8], pos: usize, order: ByteOrder) -> Result<u64, SpicyError> {
+    read_bytes(vec, pos, order).map(u64::from_le_bytes)
+}
+
+// a message over `max_message_bytes` is rejected before reading any of it, like other
+// deserialization errors the peer is then disconnected; `0` disables the limit
+fn check_msg_size(length: usize, max_message_bytes: usize) -> Result<(), SpicyError> {
+    if max_message_bytes > 0 && length > max_message_bytes {
+        return Err(msg_too_large(length, max_message_bytes));
+    }
+    Ok(())
+}
+
+fn msg_too_large(length: usize, max_message_bytes: usize) -> SpicyError {
+    SpicyError::DeserializationErr(format!(
+        "message of {} bytes exceeds max message size of {} bytes",
+        length, max_message_bytes
+    ))
+}
+
+// uncompressed size carried by a compressed message, including the 8 bytes header
+#[cfg(feature = "ipc")]
+fn decompressed_len(length: usize, max_message_bytes: usize) -> Result<usize, SpicyError> {
+    let length = length.checked_sub(8).ok_or_else(|| {
+        SpicyError::DeserializationErr(format!(
+            "invalid uncompressed message size of {} bytes",
+            length
+        ))
+    })?;
+    if max_message_bytes > 0 && length > max_message_bytes {
+        return Err(msg_too_large(length, max_message_bytes));
+    }
+    Ok(length)
+}
+
+/// Read a q IPC message body of `length` bytes. Declared sizes over
+/// `max_message_bytes` error before anything is allocated for them.
+#[cfg(feature = "ipc")]
 pub fn read_q_msg(
     rw: &mut dyn ReadWrite,
     length: usize,
     compression_mode: u8,
+    order: ByteOrder,
+    max_message_bytes: usize,
 ) -> Result<SpicyObj, SpicyError> {
+    check_msg_size(length, max_message_bytes)?;
     let mut vec = vec![0u8; length];
+    // an io error leaves the stream mid-frame, so it is told apart as OsErr
     rw.read_exact(&mut vec)
-        .map_err(|e| SpicyError::Err(e.to_string()))?;
+        .map_err(|e| SpicyError::OsErr(e.to_string()))?;
     if compression_mode == 1 {
-        let length = u32::from_le_bytes(vec[..4].try_into().unwrap()) as usize;
-        let mut de_vec = vec![0u8; length - 8];
-        serde6::decompress(&vec, &mut de_vec, 4);
-        Ok(serde6::deserialize(&de_vec, &mut 0, false)?)
+        let length = read_u32(&vec, 0, order)? as usize;
+        let mut de_vec = vec![0u8; decompressed_len(length, max_message_bytes)?];
+        serde6::decompress(&vec, &mut de_vec, 4)?;
+        Ok(serde6::deserialize_with_order(
+            &de_vec, &mut 0, false, order,
+        )?)
     } else if compression_mode == 2 {
-        let length = u64::from_le_bytes(vec[..8].try_into().unwrap()) as usize;
-        let mut de_vec = vec![0u8; length - 8];
-        serde6::decompress(&vec, &mut de_vec, 8);
-        Ok(serde6::deserialize(&de_vec, &mut 0, false)?)
+        let length = read_u64(&vec, 0, order)? as usize;
+        let mut de_vec = vec![0u8; decompressed_len(length, max_message_bytes)?];
+        serde6::decompress(&vec, &mut de_vec, 8)?;
+        Ok(serde6::deserialize_with_order(
+            &de_vec, &mut 0, false, order,
+        )?)
     } else {
-        Ok(serde6::deserialize(&vec, &mut 0, false)?)
+        Ok(serde6::deserialize_with_order(&vec, &mut 0, false, order)?)
     }
 }
 
+#[cfg(not(feature = "ipc"))]
+pub fn read_q_msg(
+    _rw: &mut dyn ReadWrite,
+    length: usize,
+    _compression_mode: u8,
+    _order: ByteOrder,
+    max_message_bytes: usize,
+) -> Result<SpicyObj, SpicyError> {
+    check_msg_size(length, max_message_bytes)?;
+    Err(q_ipc_disabled())
+}
+
+/// Serialize an object to the q wire format.
+#[cfg(feature = "ipc")]
+pub fn serialize_q(obj: &SpicyObj) -> Result<Vec<u8>, SpicyError> {
+    serde6::serialize(obj)
+}
+
+#[cfg(not(feature = "ipc"))]
+pub fn serialize_q(_obj: &SpicyObj) -> Result<Vec<u8>, SpicyError> {
+    Err(q_ipc_disabled())
+}
+
+/// Deserialize an object from the q wire format, starting at `pos`.
+#[cfg(feature = "ipc")]
+pub fn deserialize_q(vec: &[u8], pos: &mut usize) -> Result<SpicyObj, SpicyError> {
+    serde6::deserialize(vec, pos, false)
+}
+
+#[cfg(not(feature = "ipc"))]
+pub fn deserialize_q(_vec: &[u8], _pos: &mut usize) -> Result<SpicyObj, SpicyError> {
+    Err(q_ipc_disabled())
+}
+
+#[cfg(not(feature = "ipc"))]
+pub(crate) fn q_ipc_disabled() -> SpicyError {
+    SpicyError::Err("q ipc is not enabled".to_string())
+}
+
 pub fn read_chili_ipc_msg(rw: &mut dyn ReadWrite, length: usize) -> Result<SpicyObj, SpicyError> {
     let mut vec = vec![0u8; length];
     rw.read_exact(&mut vec)
-        .map_err(|e| SpicyError::Err(e.to_string()))?;
+        .map_err(|e| SpicyError::OsErr(e.to_string()))?;
     serde9::deserialize(&vec, &mut 0)
 }
 
@@ -394,7 +524,7 @@ pub fn read_q_table_name(msg: &[u8]) -> Result<String, SpicyError> {
             }
             _ => unreachable!(),
         }
-        let table_name = serde6::deserialize(msg, &mut pos, false)?;
+        let table_name = deserialize_q(msg, &mut pos)?;
         Ok(table_name.str()?.to_string())
     } else {
         Err(SpicyError::Err(
@@ -422,13 +552,16 @@ impl MessageType {
     }
 }
 
-pub fn decode_header6(header: &[u8]) -> (MessageType, usize, u8) {
+// the first header byte flags the byte order of the length and the payload
+pub fn decode_header6(header: &[u8]) -> (MessageType, usize, u8, ByteOrder) {
     let message_type = MessageType::from_u8(header[1]).unwrap();
-    let len = u32::from_le_bytes(header[4..].try_into().unwrap()) as usize;
+    let order = ByteOrder::from_header(header[0]);
+    let len = read_u32(header, 4, order).unwrap() as usize;
     (
         message_type,
         len + ((header[3] as usize).wrapping_shl(32)),
         header[2],
+        order,
     )
 }
 
@@ -521,6 +654,56 @@ pub fn coerce_extend_tz(target: &DataFrame, incoming: &DataFrame) -> DataFrame {
 
 static RE_STYLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1B\[[0-9;]*m").unwrap());
 
+// like read_exact, but on failure also reports how many bytes were consumed
+fn read_header(rw: &mut dyn ReadWrite, header: &mut [u8]) -> Result<(), (std::io::Error, usize)> {
+    let mut filled = 0;
+    while filled < header.len() {
+        match rw.read(&mut header[filled..]) {
+            Ok(0) => return Err((std::io::ErrorKind::UnexpectedEof.into(), filled)),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err((e, filled)),
+        }
+    }
+    Ok(())
+}
+
+// true when the conn handler should keep reading instead of disconnecting
+fn on_read_header_err(
+    e: &std::io::Error,
+    consumed: usize,
+    state: &EngineState,
+    handle: i64,
+    user: &str,
+) -> bool {
+    match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => info!("publisher disconnected, handle {}", handle),
+        // a timeout after part of a header leaves the stream mid-frame
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut if consumed > 0 => error!(
+            "timed out after {} bytes of a header, disconnecting handle {}",
+            consumed, handle
+        ),
+        // read timeout is only set for idle timeout, subscribers are silent by design
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => {
+            if state.is_publishing_handle(&handle) {
+                return true;
+            }
+            info!(
+                "{} idle for more than {} seconds, disconnecting handle {}",
+                user,
+                state.get_idle_timeout(),
+                handle
+            )
+        }
+        _ => error!(
+            "failed to read from publisher with error {}, disconnecting",
+            e
+        ),
+    }
+    false
+}
+
+#[cfg(feature = "ipc")]
 pub fn handle_q_conn(
     rw: &mut dyn ReadWrite,
     is_local: bool,
@@ -532,21 +715,28 @@ pub fn handle_q_conn(
     let mut stack = Stack::new(None, 0, handle, user);
     loop {
         // little endian, msg type()
-        if let Err(e) = rw.read_exact(&mut header) {
-            if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                info!("publisher disconnected, handle {}", handle);
-            } else {
-                error!(
-                    "failed to read from publisher with error {}, disconnecting",
-                    e
-                );
+        if let Err((e, consumed)) = read_header(rw, &mut header) {
+            if on_read_header_err(&e, consumed, &state, handle, user) {
+                continue;
             }
             break;
         }
-        let len = u32::from_le_bytes(header[4..].try_into().unwrap()) as usize;
-        let message_type = MessageType::from_u8(header[1]).unwrap();
-        let obj = match crate::read_q_msg(rw, len - 8, header[2]) {
+        let (message_type, len, compression_mode, order) = decode_header6(&header);
+        let obj = match crate::read_q_msg(
+            rw,
+            len.saturating_sub(8),
+            compression_mode,
+            order,
+            state.get_max_msg_bytes(),
+        ) {
             Ok(obj) => obj,
+            Err(SpicyError::OsErr(e)) => {
+                error!(
+                    "failed to read message: {}, disconnecting handle {}",
+                    e, handle
+                );
+                break;
+            }
             Err(e) => {
                 if message_type == MessageType::Sync
                     && let Err(_) =
@@ -554,6 +744,11 @@ pub fn handle_q_conn(
                 {
                     break;
                 }
+                // a malformed or oversized frame, the peer is not to be trusted anymore
+                if let SpicyError::DeserializationErr(_) = e {
+                    error!("{}, disconnecting handle {}", e, handle);
+                    break;
+                }
                 continue;
             }
         };
@@ -573,7 +768,7 @@ pub fn handle_q_conn(
                 Ok(obj) => match serde6::serialize(&obj) {
                     Ok(mut v8) => {
                         if !is_local {
-                            v8 = serde6::compress(v8);
+                            v8 = state.compress_q_msg(v8);
                         }
                         let _ = rw.write(&[1, 2, 0, 0]);
                         let _ = rw.write_all(&((v8.len() + 8) as u32).to_le_bytes());
@@ -628,6 +823,21 @@ pub fn handle_q_conn(
     }
 }
 
+#[cfg(not(feature = "ipc"))]
+pub fn handle_q_conn(
+    _rw: &mut dyn ReadWrite,
+    _is_local: bool,
+    handle: i64,
+    state: Arc<EngineState>,
+    user: &str,
+) {
+    error!(
+        "q ipc is not enabled, disconnecting handle {} of {}",
+        handle, user
+    );
+    let _ = state.disconnect_handle(&handle);
+}
+
 pub fn handle_chili_conn(
     rw: &mut dyn ReadWrite,
     is_local: bool,
@@ -639,20 +849,22 @@ pub fn handle_chili_conn(
     let mut stack = Stack::new(None, 0, handle, user);
     loop {
         // little endian, msg type()
-        if let Err(e) = rw.read_exact(&mut header) {
-            if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                info!("publisher disconnected, handle {}", handle);
-            } else {
-                error!(
-                    "failed to read from publisher with error {}, disconnecting",
-                    e
-                );
+        if let Err((e, consumed)) = read_header(rw, &mut header) {
+            if on_read_header_err(&e, consumed, &state, handle, user) {
+                continue;
             }
             break;
         }
         let (message_type, len) = crate::utils::decode_header9(&header);
         let any = match crate::read_chili_ipc_msg(rw, len) {
             Ok(obj) => obj,
+            Err(SpicyError::OsErr(e)) => {
+                error!(
+                    "failed to read message: {}, disconnecting handle {}",
+                    e, handle
+                );
+                break;
+            }
             Err(e) => {
                 if message_type == MessageType::Sync
                     && rw
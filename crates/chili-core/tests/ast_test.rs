@@ -0,0 +1,70 @@
+//! `ast` builtin tests.
+
+use chili_core::{EngineState, SpicyObj, Stack, explain, parse};
+use chili_op::BUILT_IN_FN;
+use chili_parser::Language;
+
+fn new_engine(pepper: bool) -> EngineState {
+    let mut state = EngineState::initialize();
+    if pepper {
+        state.enable_pepper();
+    }
+    state.register_fn(&BUILT_IN_FN);
+    state
+}
+
+fn ast(state: &EngineState, call: &str, path: &str) -> String {
+    let mut s = Stack::new(None, 0, 0, "");
+    state
+        .eval(&mut s, &SpicyObj::String(call.to_owned()), path)
+        .unwrap()
+        .str()
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn chili_binary_ops_group_left_to_right() {
+    let state = new_engine(false);
+    assert_eq!(ast(&state, r#"ast("2*3+1")"#, "ast.chi"), "(+ (* 2 3) 1)");
+    assert_eq!(
+        ast(&state, r#"ast("x: f(y, 1)")"#, "ast.chi"),
+        "(: x (f y 1))"
+    );
+    assert_eq!(
+        ast(&state, r#"ast("2*(3+1); x")"#, "ast.chi"),
+        "(* 2 (+ 3 1))\nx"
+    );
+}
+
+#[test]
+fn pepper_binary_ops_group_right_to_left() {
+    let state = new_engine(true);
+    assert_eq!(ast(&state, r#"ast["2*3+1"]"#, "ast.pep"), "(* 2 (+ 3 1))");
+    assert_eq!(ast(&state, r#"ast["(2*3)+1"]"#, "ast.pep"), "(+ (* 2 3) 1)");
+}
+
+#[test]
+fn explain_shows_pepper_evaluating_right_to_left() {
+    let nodes = parse("2*3+1", 0, "explain.pep").unwrap();
+    assert_eq!(
+        explain(Language::Pepper, &nodes),
+        vec![
+            "pepper binary ops have no precedence and group right to left",
+            "(* 2 (+ 3 1))",
+            "  1. (+ 3 1)",
+            "  2. (* 2 (+ 3 1))",
+        ]
+    );
+
+    let nodes = parse("x: 2*3+1", 0, "explain.chi").unwrap();
+    assert_eq!(
+        explain(Language::Chili, &nodes)[1..],
+        [
+            "(: x (+ (* 2 3) 1))",
+            "  1. (* 2 3)",
+            "  2. (+ (* 2 3) 1)",
+            "  3. (: x (+ (* 2 3) 1))"
+        ]
+    );
+}
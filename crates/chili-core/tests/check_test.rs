@@ -0,0 +1,57 @@
+//! Static `check` dry-run tests.
+
+use chili_core::{EngineState, SpicyObj, Stack};
+use chili_op::BUILT_IN_FN;
+
+fn new_engine() -> EngineState {
+    let state = EngineState::initialize();
+    state.register_fn(&BUILT_IN_FN);
+    state
+}
+
+fn check(state: &EngineState, src: &str) -> Vec<SpicyObj> {
+    let mut s = Stack::new(None, 0, 0, "");
+    let code = format!("check({:?})", src);
+    state
+        .eval(&mut s, &SpicyObj::String(code), "check.chi")
+        .unwrap()
+        .as_vec()
+        .unwrap()
+}
+
+fn kind(diagnostic: &SpicyObj) -> String {
+    diagnostic.dict().unwrap()["kind"].str().unwrap().to_owned()
+}
+
+#[test]
+fn check_flags_arity_error_without_evaluating() {
+    let state = new_engine();
+    let src = "set(`touched, 1); count(1 2, 3)";
+    let diagnostics = check(&state, src);
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(kind(&diagnostics[0]), "arity");
+    let d = diagnostics[0].dict().unwrap();
+    assert!(
+        d["msg"]
+            .str()
+            .unwrap()
+            .contains("'count' expects 1 arg(s), got 2")
+    );
+    assert_eq!(d["pos"], SpicyObj::I64(src.find("count").unwrap() as i64));
+    assert!(state.get_var("touched").is_err());
+}
+
+#[test]
+fn check_flags_unbalanced_bracket() {
+    let state = new_engine();
+    let diagnostics = check(&state, "f: function(x){x + 1;");
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(kind(&diagnostics[0]), "syntax");
+}
+
+#[test]
+fn check_clean_source_and_shadowed_builtin() {
+    let state = new_engine();
+    assert!(check(&state, "x: count(1 2 3); x + 1").is_empty());
+    assert!(check(&state, "count: function(a, b){a + b}; count(1, 2)").is_empty());
+}
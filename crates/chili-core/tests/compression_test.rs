@@ -0,0 +1,68 @@
+//! Runtime q IPC compression threshold tests.
+#![cfg(feature = "ipc")]
+
+use chili_core::{EngineState, SpicyObj, Stack, serde6};
+use chili_op::BUILT_IN_FN;
+
+fn new_engine() -> EngineState {
+    let mut state = EngineState::initialize();
+    state.enable_pepper();
+    state.register_fn(&BUILT_IN_FN);
+    state
+}
+
+fn eval(state: &EngineState, src: &str) -> SpicyObj {
+    let mut s = Stack::new(None, 0, 0, "");
+    state
+        .eval(&mut s, &SpicyObj::String(src.to_string()), "compress.pep")
+        .unwrap()
+}
+
+fn is_compressed(v8: &[u8]) -> bool {
+    v8[2] == 1
+}
+
+#[test]
+fn compression_threshold_is_read_at_send_time() {
+    let state = new_engine();
+    assert_eq!(state.get_compression(), serde6::IPC_COMPRESS_THRESHOLD);
+    // ~200 KB of a repeated string compresses well
+    let msg = SpicyObj::String("abcd".repeat(50_000));
+    let raw = serde6::serialize(&msg).unwrap();
+
+    // below the default 1 MB threshold the frame is sent raw
+    let v8 = state.compress_q_msg(raw.clone());
+    assert!(!is_compressed(&v8));
+    assert_eq!(v8, raw);
+
+    assert_eq!(
+        eval(&state, "set_compression[100000]"),
+        SpicyObj::I64(100_000)
+    );
+    let v8 = state.compress_q_msg(raw.clone());
+    assert!(is_compressed(&v8));
+    assert!(v8.len() < raw.len());
+
+    // raising the threshold past the payload skips compression again
+    eval(&state, "set_compression[1000000]");
+    assert_eq!(state.compress_q_msg(raw.clone()), raw);
+
+    // 0 disables compression outright
+    eval(&state, "set_compression[0]");
+    assert_eq!(state.get_compression(), 0);
+    assert_eq!(state.compress_q_msg(raw.clone()), raw);
+
+    // tiny thresholds are rounded up to a safe minimum
+    eval(&state, "set_compression[10]");
+    assert_eq!(state.get_compression(), 2000);
+    let mut s = Stack::new(None, 0, 0, "");
+    assert!(
+        state
+            .eval(
+                &mut s,
+                &SpicyObj::String("set_compression[-1]".to_string()),
+                "compress.pep"
+            )
+            .is_err()
+    );
+}
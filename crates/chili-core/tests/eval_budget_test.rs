@@ -0,0 +1,127 @@
+//! Budgeted eval tests.
+
+use std::time::{Duration, Instant};
+
+use chili_core::{EngineState, EvalBudget, SpicyObj, Stack};
+use chili_op::BUILT_IN_FN;
+
+fn new_engine() -> EngineState {
+    let state = EngineState::initialize();
+    state.register_fn(&BUILT_IN_FN);
+    state
+}
+
+#[test]
+fn row_budget_aborts_oversized_result() {
+    let state = new_engine();
+    let src = "f: function(n) { n # 1 }; t: ([]a: f(3)); t";
+    let ok = state
+        .eval_with_budget(src, EvalBudget::new(3, None, 0))
+        .unwrap();
+    assert_eq!(ok.size(), 3);
+
+    let src = "g: function(n) { n # 1 }; g(4)";
+    let err = state
+        .eval_with_budget(src, EvalBudget::new(3, None, 0))
+        .unwrap_err();
+    assert!(err.to_string().contains("row budget exceeded"), "{err}");
+    // the unbudgeted path is unaffected
+    assert_eq!(state.eval_str("g(4)").unwrap().size(), 4);
+}
+
+#[test]
+fn memory_budget_aborts_oversized_result() {
+    let state = new_engine();
+    let err = state
+        .eval_with_budget("1000 # 1", EvalBudget::new(0, None, 1000))
+        .unwrap_err();
+    assert!(err.to_string().contains("memory budget exceeded"), "{err}");
+}
+
+#[test]
+fn time_budget_aborts_runaway_loop() {
+    let state = new_engine();
+    let start = Instant::now();
+    let err = state
+        .eval_with_budget(
+            "i: 0; while (1b) { i: i + 1 }",
+            EvalBudget::new(0, Some(Duration::from_millis(50)), 0),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("time budget exceeded"), "{err}");
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(state.get_var("i").unwrap().to_i64().unwrap() > 0);
+}
+
+#[test]
+fn cancel_aborts_from_another_thread() {
+    let state = new_engine();
+    let budget = EvalBudget::new(0, None, 0);
+    let canceller = {
+        let budget = budget.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            budget.cancel();
+        })
+    };
+    let err = state
+        .eval_with_budget("while (1b) { 1 }", budget)
+        .unwrap_err();
+    assert!(err.to_string().contains("eval cancelled"), "{err}");
+    canceller.join().unwrap();
+}
+
+#[test]
+fn eval_budget_builtin() {
+    let state = new_engine();
+    let r = state
+        .eval_str("eval_budget(\"3 # 1\", {rows: 3, time: 0D00:00:01})")
+        .unwrap();
+    assert_eq!(r.size(), 3);
+    let err = state
+        .eval_str("eval_budget(\"4 # 1\", {rows: 3})")
+        .unwrap_err();
+    assert!(err.to_string().contains("row budget exceeded"), "{err}");
+    let err = state
+        .eval_str("eval_budget(\"i: 0; while (1b) { i: i + 1 }\", {time: 20})")
+        .unwrap_err();
+    assert!(err.to_string().contains("time budget exceeded"), "{err}");
+
+    for budget in ["{rows: -1}", "{rows: `a}", "{cpu: 1}"] {
+        let src = format!("eval_budget(\"1\", {})", budget);
+        assert!(state.eval_str(&src).is_err(), "{budget}");
+    }
+}
+
+#[test]
+fn nested_eval_budget_cannot_loosen_outer() {
+    let state = new_engine();
+    let err = state
+        .eval_with_budget(
+            "eval_budget(\"count(5 # 1)\", {rows: 100})",
+            EvalBudget::new(3, None, 0),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("row budget exceeded"), "{err}");
+    let r = state
+        .eval_with_budget(
+            "eval_budget(\"count(3 # 1)\", {rows: 100})",
+            EvalBudget::new(3, None, 0),
+        )
+        .unwrap();
+    assert_eq!(r, SpicyObj::I64(3));
+}
+
+#[test]
+fn eval_budget_keeps_caller_user() {
+    let mut state = EngineState::initialize();
+    state.register_fn(&BUILT_IN_FN);
+    state.set_user_quota("alice", 1.0);
+    let mut alice = Stack::new(None, 0, 3, "alice");
+    let src = SpicyObj::String("eval_budget(\"x: 100 # 1\", {rows: 1000})".to_owned());
+    state
+        .eval_with_pre_hook(&mut alice, &src, "ipc3.chi")
+        .unwrap();
+    // the global assigned inside the budgeted eval is owned by alice
+    assert!(state.get_user_bytes("alice") > 0);
+}
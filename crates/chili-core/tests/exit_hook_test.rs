@@ -0,0 +1,70 @@
+//! Exit hook tests for `on_exit` and `shutdown`.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chili_core::{EngineState, SpicyObj, Stack};
+use chili_op::BUILT_IN_FN;
+
+fn new_engine() -> EngineState {
+    let mut state = EngineState::initialize();
+    state.enable_pepper();
+    state.register_fn(&BUILT_IN_FN);
+    state
+}
+
+fn eval(state: &EngineState, src: &str) -> SpicyObj {
+    let mut s = Stack::new(None, 0, 0, "");
+    state
+        .eval(&mut s, &SpicyObj::String(src.to_string()), "exit.pep")
+        .unwrap_or_else(|e| panic!("eval failed for {src:?}: {e}"))
+}
+
+#[test]
+fn exit_hook_fires_on_normal_quit() {
+    let state = new_engine();
+    eval(&state, ".exit.fired: 0;");
+    assert_eq!(
+        eval(&state, "on_exit[{[] .exit.fired: .exit.fired + 1 }]"),
+        SpicyObj::I64(1)
+    );
+    assert_eq!(
+        eval(&state, "on_exit[{[] raise \"boom\" }]"),
+        SpicyObj::I64(2),
+        "a failing hook must not stop later hooks"
+    );
+    eval(&state, "on_exit[{[] .exit.fired: .exit.fired + 10 }]");
+    state.shutdown();
+    assert_eq!(eval(&state, ".exit.fired"), SpicyObj::I64(11));
+
+    // hooks are drained, a second shutdown does not fire them again
+    state.shutdown();
+    assert_eq!(eval(&state, ".exit.fired"), SpicyObj::I64(11));
+}
+
+#[test]
+fn on_exit_rejects_non_function() {
+    let state = new_engine();
+    let mut s = Stack::new(None, 0, 0, "");
+    let err = state
+        .eval(
+            &mut s,
+            &SpicyObj::String("on_exit[1]".to_string()),
+            "exit.pep",
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("on_exit requires a function"));
+}
+
+#[test]
+fn slow_exit_hook_is_bounded_by_budget() {
+    let state = Arc::new(new_engine());
+    state.set_arc_self(Arc::clone(&state)).unwrap();
+    eval(&state, ".exit.fired: 0;");
+    eval(&state, "on_exit[{[] .os.sleep[3000] }]");
+    eval(&state, "on_exit[{[] .exit.fired: 1 }]");
+    let start = Instant::now();
+    state.run_exit_hooks(Duration::from_millis(200));
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert_eq!(eval(&state, ".exit.fired"), SpicyObj::I64(0));
+}
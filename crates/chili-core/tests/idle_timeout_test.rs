@@ -0,0 +1,92 @@
+//! Idle timeout tests for accepted IPC connections.
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chili_core::{EngineState, utils::send_auth};
+
+fn start_server(idle_timeout: u64) -> (Arc<EngineState>, u16) {
+    let mut engine = EngineState::initialize();
+    engine.set_idle_timeout(idle_timeout);
+    let engine = Arc::new(engine);
+    engine.set_arc_self(Arc::clone(&engine)).unwrap();
+
+    let listener = EngineState::bind_tcp_listener(0, false).expect("bind on ephemeral port");
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let srv = Arc::clone(&engine);
+    std::thread::spawn(move || {
+        srv.run_accept_loop(listener, vec![]);
+    });
+    (engine, port)
+}
+
+fn incoming_handles(engine: &Arc<EngineState>) -> usize {
+    let df = engine.list_handle().expect("list_handle");
+    let conn = df.column("conn_type").unwrap().str().unwrap();
+    conn.iter().filter(|c| *c == Some("Incoming")).count()
+}
+
+#[test]
+fn idle_connection_is_dropped() {
+    let (engine, port) = start_server(1);
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).expect("connect to listener");
+    assert_eq!(
+        send_auth(&mut stream, "", "", 9).expect("auth handshake"),
+        9
+    );
+
+    // the server closes the socket once the peer has been silent for the timeout
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    let t0 = Instant::now();
+    let mut buf = [0u8; 1];
+    assert_eq!(stream.read(&mut buf).expect("read until server closes"), 0);
+    assert!(t0.elapsed() >= Duration::from_millis(900));
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while incoming_handles(&engine) > 0 {
+        assert!(
+            Instant::now() < deadline,
+            "idle handle was never cleaned up"
+        );
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[test]
+fn truncated_message_is_dropped_without_reply() {
+    let (engine, port) = start_server(1);
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).expect("connect to listener");
+    assert_eq!(
+        send_auth(&mut stream, "", "", 9).expect("auth handshake"),
+        9
+    );
+
+    // a sync header announcing 100 bytes, followed by only 10 of them
+    let mut frame = vec![1u8, 1, 0, 0, 0, 0, 0, 0];
+    frame.extend_from_slice(&100u64.to_le_bytes());
+    frame.extend_from_slice(&[0u8; 10]);
+    stream.write_all(&frame).unwrap();
+
+    // the stream is mid-frame, so the server closes it rather than replying
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    let mut buf = [0u8; 1];
+    assert_eq!(stream.read(&mut buf).expect("read until server closes"), 0);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while incoming_handles(&engine) > 0 {
+        assert!(
+            Instant::now() < deadline,
+            "truncated handle was never cleaned up"
+        );
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,59 @@
+//! Pinning the IPC version of outgoing handles.
+
+use std::sync::Arc;
+
+use chili_core::{EngineState, SpicyObj};
+use chili_op::BUILT_IN_FN;
+
+fn start_server() -> (Arc<EngineState>, u16) {
+    let engine = Arc::new(EngineState::initialize());
+    engine.register_fn(&BUILT_IN_FN);
+    engine.set_arc_self(Arc::clone(&engine)).unwrap();
+
+    let listener = EngineState::bind_tcp_listener(0, false).expect("bind on ephemeral port");
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let srv = Arc::clone(&engine);
+    std::thread::spawn(move || {
+        srv.run_accept_loop(listener, vec![]);
+    });
+    (engine, port)
+}
+
+fn ipc_type_of(engine: &EngineState, h: i64) -> String {
+    let df = engine.list_handle().unwrap();
+    let nums = df.column("num").unwrap().i64().unwrap();
+    let ipc_type = df.column("ipc_type").unwrap().str().unwrap();
+    (0..df.height())
+        .find(|i| nums.get(*i) == Some(h))
+        .and_then(|i| ipc_type.get(i))
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn pinned_version_switches_serialization() {
+    let (_server, port) = start_server();
+    let client = EngineState::initialize();
+    let h = client
+        .open_handle(&format!("chili://127.0.0.1:{}", port), 0)
+        .unwrap()
+        .to_i64()
+        .unwrap();
+    let msg = SpicyObj::String("1 + 2".to_owned());
+    assert_eq!(client.get_ipc_version(&h).unwrap(), SpicyObj::I64(9));
+    assert_eq!(client.sync(&h, &msg).unwrap(), SpicyObj::I64(3));
+
+    // the chili server also speaks v6, so the handle reconnects as a q handle
+    client.set_ipc_version(&h, 6).unwrap();
+    assert_eq!(client.get_ipc_version(&h).unwrap(), SpicyObj::I64(6));
+    assert_eq!(ipc_type_of(&client, h), "Q");
+    assert_eq!(client.sync(&h, &msg).unwrap(), SpicyObj::I64(3));
+
+    client.set_ipc_version(&h, 9).unwrap();
+    assert_eq!(ipc_type_of(&client, h), "Chili");
+    assert_eq!(client.sync(&h, &msg).unwrap(), SpicyObj::I64(3));
+
+    assert!(client.set_ipc_version(&h, 3).is_err());
+    assert!(client.get_ipc_version(&999).is_err());
+}
@@ -0,0 +1,50 @@
+//! Build of chili-core without the `ipc` feature, i.e. without the q wire format.
+
+use std::process::Command;
+
+#[cfg(not(feature = "ipc"))]
+mod no_ipc {
+    use chili_core::{EngineState, SpicyObj};
+    use chili_op::BUILT_IN_FN;
+
+    fn eval(state: &EngineState, code: &str) -> chili_core::SpicyResult<SpicyObj> {
+        let nodes = state.parse("", code)?;
+        state.eval_ast(nodes, "", code)
+    }
+
+    #[test]
+    fn eval_without_q_ipc() {
+        let mut state = EngineState::initialize();
+        let err = state.set_tls("server.crt", "server.key").unwrap_err();
+        assert!(err.to_string().contains("tls is not enabled"));
+        state.register_fn(&BUILT_IN_FN);
+        assert_eq!(eval(&state, "sum(1 2 3)").unwrap(), SpicyObj::I64(6));
+        assert_eq!(state.get_compression(), 0);
+
+        let err = eval(&state, "to_wire_hex(1)").unwrap_err();
+        assert!(err.to_string().contains("q ipc is not enabled"));
+        let err = state.open_handle("q://localhost:5001", 0).unwrap_err();
+        assert!(err.to_string().contains("q ipc is not enabled"));
+    }
+}
+
+// rebuilds chili-core and polars in a separate target dir, minutes on a cold cache and needs the
+// network for the patched polars, run with `cargo test -p chili-core --test lean_build_test -- --ignored`
+#[test]
+#[ignore = "rebuilds chili-core from cold, run explicitly with --ignored"]
+fn builds_without_ipc_feature() {
+    let status = Command::new(option_env!("CARGO").unwrap_or("cargo"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args([
+            "check",
+            "-p",
+            "chili-core",
+            "--no-default-features",
+            "--tests",
+        ])
+        .arg("--target-dir")
+        .arg(concat!(env!("CARGO_TARGET_TMPDIR"), "/no-ipc"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,38 @@
+//! Max eval depth guard tests.
+
+use chili_core::{EngineState, SpicyError, SpicyObj, Stack};
+use chili_op::BUILT_IN_FN;
+
+fn new_engine() -> EngineState {
+    let mut state = EngineState::initialize();
+    state.enable_pepper();
+    state.register_fn(&BUILT_IN_FN);
+    state
+}
+
+fn eval(state: &EngineState, src: &str) -> Result<SpicyObj, SpicyError> {
+    let mut s = Stack::new(None, 0, 0, "");
+    state.eval(&mut s, &SpicyObj::String(src.to_string()), "depth.pep")
+}
+
+#[test]
+fn non_terminating_recursion_hits_limit() {
+    let state = new_engine();
+    eval(&state, "f: {[x] f[x + 1]};").unwrap();
+    let err = eval(&state, "f[0]").unwrap_err();
+    assert!(
+        err.to_string().contains("max eval depth exceeded"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn configured_limit_is_respected() {
+    let mut state = new_engine();
+    state.set_max_depth(5);
+    assert_eq!(state.get_max_depth(), 5);
+    eval(&state, "g: {[x] if[x <= 0; :0]; g[x - 1]};").unwrap();
+    assert_eq!(eval(&state, "g[3]").unwrap().to_i64().unwrap(), 0);
+    let err = eval(&state, "g[10]").unwrap_err();
+    assert!(err.to_string().contains("max eval depth exceeded"));
+}
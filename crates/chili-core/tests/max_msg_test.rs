@@ -0,0 +1,45 @@
+//! Max inbound q IPC message size tests.
+#![cfg(feature = "ipc")]
+
+use std::io::Cursor;
+
+use chili_core::{SpicyError, SpicyObj, read_q_msg, serde6, utils::ByteOrder};
+
+#[test]
+fn oversized_message_is_rejected_unread() {
+    let big = serde6::serialize(&SpicyObj::String("a".repeat(1000))).unwrap();
+    let mut rw = Cursor::new(big.clone());
+
+    let err = read_q_msg(&mut rw, big.len(), 0, ByteOrder::Little, 100).unwrap_err();
+    assert!(matches!(err, SpicyError::DeserializationErr(_)), "{err}");
+    // nothing is buffered or drained, the connection is dropped instead
+    assert_eq!(rw.position(), 0);
+
+    // 0 disables the limit
+    let mut rw = Cursor::new(big.clone());
+    assert_eq!(
+        read_q_msg(&mut rw, big.len(), 0, ByteOrder::Little, 0).unwrap(),
+        SpicyObj::String("a".repeat(1000))
+    );
+}
+
+#[test]
+fn oversized_uncompressed_size_is_rejected() {
+    // compressed body declaring an uncompressed size of u32::MAX bytes
+    let mut rw = Cursor::new(vec![255, 255, 255, 255, 0, 0, 0, 0]);
+    let err = read_q_msg(&mut rw, 8, 1, ByteOrder::Little, 1024).unwrap_err();
+    assert!(matches!(err, SpicyError::DeserializationErr(_)), "{err}");
+
+    let mut rw = Cursor::new(vec![4, 0, 0, 0, 0, 0, 0, 0]);
+    let err = read_q_msg(&mut rw, 8, 1, ByteOrder::Little, 1024).unwrap_err();
+    assert!(matches!(err, SpicyError::DeserializationErr(_)), "{err}");
+}
+
+#[test]
+fn truncated_message_errors_without_panic() {
+    for body in [vec![], vec![249, 1, 2], vec![7, 0, 255, 255, 255, 255, 1]] {
+        let len = body.len();
+        let mut rw = Cursor::new(body);
+        assert!(read_q_msg(&mut rw, len, 0, ByteOrder::Little, 1024).is_err());
+    }
+}
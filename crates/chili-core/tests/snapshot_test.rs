@@ -0,0 +1,74 @@
+//! `snapshot` point-in-time copy tests.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chili_core::{EngineState, SpicyObj, Stack};
+use indexmap::IndexMap;
+
+fn snapshot_vars(state: &EngineState) -> IndexMap<String, SpicyObj> {
+    match state.snapshot().unwrap() {
+        SpicyObj::Dict(mut d) => match d.swap_remove("vars") {
+            Some(SpicyObj::Dict(vars)) => vars,
+            other => panic!("expected vars dict, got {:?}", other),
+        },
+        other => panic!("expected Dict, got {}", other.get_type_name()),
+    }
+}
+
+#[test]
+fn snapshot_copies_user_vars_only() {
+    let state = EngineState::initialize();
+    state.set_var("b", SpicyObj::I64(2)).unwrap();
+    state.set_var("a", SpicyObj::I64(1)).unwrap();
+    let mut stack = Stack::new(None, 0, 0, "");
+    let snap = state
+        .eval(&mut stack, &SpicyObj::String("snapshot()".to_owned()), "")
+        .unwrap();
+    let snap = snap.dict().unwrap();
+    assert!(snap.contains_key("partitioned"));
+    let vars = snap.get("vars").unwrap().dict().unwrap();
+    assert_eq!(vars.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+
+    let before = snapshot_vars(&state);
+    state.set_var("a", SpicyObj::I64(10)).unwrap();
+    state.del_var("b").unwrap();
+    assert_eq!(before.get("a"), Some(&SpicyObj::I64(1)));
+    assert_eq!(before.get("b"), Some(&SpicyObj::I64(2)));
+}
+
+#[test]
+fn snapshot_is_consistent_under_concurrent_writes() {
+    let state = Arc::new(EngineState::initialize());
+    state.set_var("n", SpicyObj::I64(-1)).unwrap();
+    let done = Arc::new(AtomicBool::new(false));
+    let writer = {
+        let state = Arc::clone(&state);
+        let done = Arc::clone(&done);
+        std::thread::spawn(move || {
+            // `v{i}` always lands before `n` moves to `i`
+            for i in 0..2000 {
+                state.set_var(&format!("v{}", i), SpicyObj::I64(i)).unwrap();
+                state.set_var("n", SpicyObj::I64(i)).unwrap();
+            }
+            done.store(true, Ordering::Release);
+        })
+    };
+    let mut snapshots = 0;
+    while !done.load(Ordering::Acquire) || snapshots == 0 {
+        let vars = snapshot_vars(&state);
+        let n = vars.get("n").unwrap().to_i64().unwrap();
+        for i in 0..=n {
+            assert_eq!(
+                vars.get(&format!("v{}", i)),
+                Some(&SpicyObj::I64(i)),
+                "snapshot at n={} is missing v{}",
+                n,
+                i
+            );
+        }
+        snapshots += 1;
+    }
+    writer.join().unwrap();
+    assert_eq!(snapshot_vars(&state).len(), 2001);
+}
@@ -0,0 +1,85 @@
+//! IPC over TLS, end to end from handshake to query.
+
+#![cfg(feature = "ipc")]
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    sync::Arc,
+    time::Duration,
+};
+
+use chili_core::{
+    EngineState, MessageType, SpicyObj, read_chili_ipc_msg, serde9, utils::decode_header9,
+    write_chili_ipc_msg,
+};
+use chili_op::BUILT_IN_FN;
+use rustls::{
+    ClientConfig, ClientConnection, RootCertStore, StreamOwned,
+    pki_types::{CertificateDer, ServerName},
+};
+
+fn start_server(dir: &tempfile::TempDir) -> (u16, CertificateDer<'static>) {
+    let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_owned()]).unwrap();
+    let cert_path = dir.path().join("server.crt");
+    let key_path = dir.path().join("server.key");
+    std::fs::write(&cert_path, certified.cert.pem()).unwrap();
+    std::fs::write(&key_path, certified.key_pair.serialize_pem()).unwrap();
+
+    let mut engine = EngineState::initialize();
+    engine
+        .set_tls(cert_path.to_str().unwrap(), key_path.to_str().unwrap())
+        .unwrap();
+    let engine = Arc::new(engine);
+    engine.register_fn(&BUILT_IN_FN);
+    engine.set_arc_self(Arc::clone(&engine)).unwrap();
+
+    let listener = EngineState::bind_tcp_listener(0, false).expect("bind on ephemeral port");
+    let port = listener.local_addr().expect("local_addr").port();
+    std::thread::spawn(move || {
+        engine.run_accept_loop(listener, vec![]);
+    });
+    (port, certified.cert.der().clone())
+}
+
+fn connect(port: u16, cert: CertificateDer<'static>) -> StreamOwned<ClientConnection, TcpStream> {
+    let mut roots = RootCertStore::empty();
+    roots.add(cert).unwrap();
+    let config =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+    let conn = ClientConnection::new(Arc::new(config), ServerName::try_from("localhost").unwrap())
+        .unwrap();
+    let sock = TcpStream::connect(("127.0.0.1", port)).expect("connect to listener");
+    sock.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    StreamOwned::new(conn, sock)
+}
+
+#[test]
+fn tls_connect_and_query() {
+    let dir = tempfile::tempdir().unwrap();
+    let (port, cert) = start_server(&dir);
+
+    // a peer that never starts the handshake must not hold up the next one
+    let _stalled = TcpStream::connect(("127.0.0.1", port)).unwrap();
+
+    let mut stream = connect(port, cert);
+    stream.write_all(b":\x09\x00").unwrap();
+    let mut version = [0u8; 1];
+    stream.read_exact(&mut version).unwrap();
+    assert_eq!(version[0], 9);
+
+    let query = serde9::serialize(&SpicyObj::String("1 + 2".to_owned()), false).unwrap();
+    write_chili_ipc_msg(&mut stream, &query, MessageType::Sync).unwrap();
+    let mut header = [0u8; 16];
+    stream.read_exact(&mut header).unwrap();
+    let (message_type, len) = decode_header9(&header);
+    assert_eq!(message_type, MessageType::Response);
+    assert_eq!(
+        read_chili_ipc_msg(&mut stream, len).unwrap(),
+        SpicyObj::I64(3)
+    );
+}
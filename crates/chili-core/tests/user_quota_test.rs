@@ -0,0 +1,76 @@
+//! Per-user memory quota tests for `eval_with_pre_hook`.
+
+use chili_core::{EngineState, SpicyObj, Stack};
+use chili_op::{BUILT_IN_FN, LOG_FN};
+
+fn new_engine() -> EngineState {
+    let mut state = EngineState::initialize();
+    state.enable_pepper();
+    state.register_fn(&LOG_FN);
+    state.register_fn(&BUILT_IN_FN);
+    // ~10 KB for alice, bob is untracked
+    state.set_user_quota("alice", 0.00001);
+    state
+}
+
+fn query(src: &str) -> SpicyObj {
+    SpicyObj::String(src.to_string())
+}
+
+#[test]
+fn repeated_small_queries_stay_under_quota() {
+    let state = new_engine();
+    let mut alice = Stack::new(None, 0, 3, "alice");
+    for _ in 0..100 {
+        let r = state
+            .eval_with_pre_hook(&mut alice, &query("range[1000]"), "ipc3.pep")
+            .unwrap();
+        assert_eq!(r.size(), 1000);
+    }
+    assert_eq!(state.get_user_bytes("alice"), 0);
+}
+
+#[test]
+fn globals_count_until_released() {
+    let state = new_engine();
+    let mut alice = Stack::new(None, 0, 3, "alice");
+    let mut bob = Stack::new(None, 0, 4, "bob");
+
+    // the assignment that takes alice over quota is reported, the global stays
+    assert!(
+        state
+            .eval_with_pre_hook(&mut alice, &query("big: range[2000];"), "ipc3.pep")
+            .is_err()
+    );
+    assert!(state.get_user_bytes("alice") >= 16000);
+    let err = state
+        .eval_with_pre_hook(&mut alice, &query("1+1"), "ipc3.pep")
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("memory quota exceeded for user 'alice'")
+    );
+
+    // other users are unaffected, and bob taking over the global frees alice
+    let r = state
+        .eval_with_pre_hook(&mut bob, &query("big: range[2000]; 1+1"), "ipc4.pep")
+        .unwrap();
+    assert_eq!(r.to_i64().unwrap(), 2);
+    assert_eq!(state.get_user_bytes("alice"), 0);
+    let r = state
+        .eval_with_pre_hook(&mut alice, &query("1+1"), "ipc3.pep")
+        .unwrap();
+    assert_eq!(r.to_i64().unwrap(), 2);
+}
+
+#[test]
+fn in_flight_result_over_quota_is_rejected() {
+    let state = new_engine();
+    let mut alice = Stack::new(None, 0, 3, "alice");
+    assert!(
+        state
+            .eval_with_pre_hook(&mut alice, &query("range[10000]"), "ipc3.pep")
+            .is_err()
+    );
+    assert_eq!(state.get_user_bytes("alice"), 0);
+}
@@ -0,0 +1,51 @@
+//! Concrete `concat_all` benchmark.
+//!
+//! Compares joining 100 small series in one `concat_all` pass against folding
+//! `,` (`operator::append`) over the same list, which re-extends the
+//! accumulator on every step.
+
+use std::time::Duration;
+
+use chili_core::SpicyObj;
+use chili_op::operator::{append, concat_all};
+use criterion::{Criterion, criterion_group, criterion_main};
+use polars::prelude::*;
+use std::hint::black_box;
+
+const PARTS: usize = 100;
+const ROWS: usize = 1_000;
+
+fn bench_concat_all(c: &mut Criterion) {
+    let parts: Vec<SpicyObj> = (0..PARTS)
+        .map(|i| {
+            let start = (i * ROWS) as i64;
+            SpicyObj::Series(Series::new(
+                "s".into(),
+                (start..start + ROWS as i64).collect::<Vec<_>>(),
+            ))
+        })
+        .collect();
+    let list = SpicyObj::MixedList(parts.clone());
+
+    let mut group = c.benchmark_group("concat_all");
+    group.sample_size(50);
+    group.measurement_time(Duration::from_secs(5));
+
+    group.bench_function("concat_all", |bencher| {
+        bencher.iter(|| black_box(concat_all(&[&list]).unwrap()));
+    });
+
+    group.bench_function("fold_append", |bencher| {
+        bencher.iter(|| {
+            let res = parts[1..]
+                .iter()
+                .fold(parts[0].clone(), |acc, s| append(&[&acc, s]).unwrap());
+            black_box(res)
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_concat_all);
+criterion_main!(benches);
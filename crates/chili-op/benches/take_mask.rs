@@ -0,0 +1,55 @@
+//! Boolean mask `take` benchmark.
+//!
+//! Compares `m # a`, which filters the series by the mask directly, against
+//! the `a @ filter[m]` composition, which first materializes the true
+//! positions as an i64 series and then gathers them. Both run over a 1M-row
+//! fixture with a pseudo-random mask.
+
+use std::time::Duration;
+
+use chili_core::{EngineState, SpicyObj, Stack};
+use criterion::{Criterion, criterion_group, criterion_main};
+use polars::prelude::*;
+use std::hint::black_box;
+
+mod common;
+use common::make_engine;
+
+const ROWS: usize = 1_000_000;
+
+fn eval(engine: &EngineState, query: &str) {
+    let mut stack = Stack::new(None, 0, 0, "");
+    let obj = engine
+        .eval(&mut stack, &SpicyObj::String(query.to_owned()), "bench.pep")
+        .unwrap();
+    black_box(obj);
+}
+
+fn bench_take_mask(c: &mut Criterion) {
+    let engine = make_engine();
+    let m: Series = (0..ROWS)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 7) % 2 == 0)
+        .collect::<BooleanChunked>()
+        .into_series()
+        .with_name("m".into());
+    let a = Series::new("a".into(), (0..ROWS as i64).collect::<Vec<_>>());
+    engine.set_var("m", SpicyObj::Series(m)).unwrap();
+    engine.set_var("a", SpicyObj::Series(a)).unwrap();
+
+    let mut group = c.benchmark_group("take_mask");
+    group.sample_size(50);
+    group.measurement_time(Duration::from_secs(10));
+
+    group.bench_function("mask", |bencher| {
+        bencher.iter(|| eval(&engine, "m # a"));
+    });
+
+    group.bench_function("filter_at", |bencher| {
+        bencher.iter(|| eval(&engine, "a @ filter[m]"));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_take_mask);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+//! Concrete `when` benchmark.
+//!
+//! Compares the series fast path (`when[m; a; b]` over materialized series,
+//! no `Expr` involved) against the same three-way select expressed as a
+//! query, which builds a polars `when/then/otherwise` expression and runs it
+//! through the lazy engine. Both draw from a 1M-row fixture with a random
+//! boolean mask.
+
+use std::time::Duration;
+
+use chili_core::{EngineState, SpicyObj, Stack};
+use criterion::{Criterion, criterion_group, criterion_main};
+use polars::prelude::*;
+use std::hint::black_box;
+
+mod common;
+use common::make_engine;
+
+const ROWS: usize = 1_000_000;
+
+fn eval(engine: &EngineState, query: &str) {
+    let mut stack = Stack::new(None, 0, 0, "");
+    let obj = engine
+        .eval(&mut stack, &SpicyObj::String(query.to_owned()), "bench.pep")
+        .unwrap();
+    black_box(obj);
+}
+
+fn bench_when(c: &mut Criterion) {
+    let engine = make_engine();
+    // deterministic pseudo-random mask, avoids long runs of the same branch
+    let m: Series = (0..ROWS)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 7) % 2 == 0)
+        .collect::<BooleanChunked>()
+        .into_series()
+        .with_name("m".into());
+    let a = Series::new("a".into(), (0..ROWS as i64).collect::<Vec<_>>());
+    let b = Series::new("b".into(), (0..ROWS as i64).map(|i| -i).collect::<Vec<_>>());
+    let df = DataFrame::new(
+        ROWS,
+        vec![m.clone().into(), a.clone().into(), b.clone().into()],
+    )
+    .unwrap();
+    engine.set_var("m", SpicyObj::Series(m)).unwrap();
+    engine.set_var("a", SpicyObj::Series(a)).unwrap();
+    engine.set_var("b", SpicyObj::Series(b)).unwrap();
+    engine.set_var("t", SpicyObj::DataFrame(df)).unwrap();
+
+    let mut group = c.benchmark_group("when");
+    group.sample_size(50);
+    group.measurement_time(Duration::from_secs(10));
+
+    group.bench_function("series", |bencher| {
+        bencher.iter(|| eval(&engine, "when[m; a; b]"));
+    });
+
+    group.bench_function("expr", |bencher| {
+        bencher.iter(|| eval(&engine, "select r: when[m; a; b] from t"));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_when);
+criterion_main!(benches);
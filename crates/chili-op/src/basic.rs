@@ -1,13 +1,14 @@
 use indexmap::IndexMap;
 use polars::{
+    chunked_array::ops::ChunkFillNullValue,
     datatypes::{Categories, DataType},
     frame::DataFrame,
     lazy::dsl,
     prelude::{
-        Expr, IntoLazy, NamedFrom, NewChunkedArray, ReshapeDimension, StringChunked,
+        Expr, IdxSize, IntoLazy, NamedFrom, NewChunkedArray, ReshapeDimension, StringChunked,
         col as polars_col,
     },
-    series::{IntoSeries, Series},
+    series::{IntoSeries, IsSorted, Series},
 };
 use polars_ops::{
     chunked_array::{ListNameSpaceImpl, StringNameSpaceImpl},
@@ -17,7 +18,11 @@ use regex::bytes::Regex;
 
 use chili_core::{ArgType, SpicyError, SpicyObj, SpicyResult, validate_args};
 
-use crate::{collection::in_op, operator::match_op, series_op};
+use crate::{
+    collection::in_op,
+    operator::{and, gt_eq, lt_eq, match_op},
+    series_op,
+};
 
 pub fn null(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let arg0 = args[0];
@@ -117,6 +122,33 @@ pub fn mode(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// series, include_nulls; all tied most frequent values
+pub fn modes(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let include_nulls = args[1].to_bool()?;
+    if args[0].is_expr() {
+        let left = args[0].as_expr()?;
+        let left = if include_nulls {
+            left
+        } else {
+            left.drop_nulls()
+        };
+        return Ok(SpicyObj::Expr(left.mode(true)));
+    }
+    validate_args(args, &[ArgType::Series, ArgType::Boolean])?;
+    let s = args[0].series().unwrap();
+    let s = if include_nulls {
+        s.clone()
+    } else {
+        s.drop_nulls()
+    };
+    if s.is_empty() {
+        return Ok(SpicyObj::Null);
+    }
+    polars_ops::chunked_array::mode::mode(&s, true)
+        .map_err(|e| SpicyError::Err(e.to_string()))
+        .map(SpicyObj::Series)
+}
+
 pub fn rank(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let arg0 = args[0];
     if arg0.is_expr() {
@@ -167,6 +199,11 @@ pub fn type_op(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     Ok(SpicyObj::Symbol(arg0.get_type_name()))
 }
 
+// negative for atoms, positive for series, e.g. -5 for i64 and 5 for i64s
+pub fn type_code(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    Ok(SpicyObj::I64(args[0].get_type_code() as i64))
+}
+
 pub fn enlist(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let arg0 = args[0];
     let err = || SpicyError::UnsupportedUnaryOpErr("enlist".to_owned(), arg0.get_type_name());
@@ -242,6 +279,54 @@ pub fn filter(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// q's `where`, positions of true values, or each position repeated by its count, nulls count as 0
+pub fn where_op(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    let err = || SpicyError::UnsupportedUnaryOpErr("where".to_owned(), arg0.get_type_name());
+    let counts: Vec<i64> = match arg0 {
+        SpicyObj::Series(s) if s.dtype().is_bool() => s
+            .bool()
+            .unwrap()
+            .iter()
+            .map(|b| b.unwrap_or(false) as i64)
+            .collect(),
+        SpicyObj::Series(s) if s.dtype().is_integer() => {
+            let s = s.strict_cast(&DataType::Int64).map_err(|_| err())?;
+            s.i64().unwrap().iter().map(|n| n.unwrap_or(0)).collect()
+        }
+        SpicyObj::MixedList(l) => l
+            .iter()
+            .map(|obj| match obj {
+                SpicyObj::Null => Ok(0),
+                _ if obj.is_bool() || obj.is_integer() => obj.to_i64(),
+                _ => Err(err()),
+            })
+            .collect::<SpicyResult<Vec<i64>>>()?,
+        _ => return Err(err()),
+    };
+    if let Some(n) = counts.iter().find(|n| **n < 0) {
+        return Err(SpicyError::Err(format!(
+            "Requires non-negative counts for 'where', got '{}'",
+            n
+        )));
+    }
+    let total = counts
+        .iter()
+        .try_fold(0usize, |acc, n| acc.checked_add(*n as usize))
+        .filter(|total| *total <= IdxSize::MAX as usize)
+        .ok_or_else(|| {
+            SpicyError::Err(format!(
+                "Requires total count of at most {} for 'where'",
+                IdxSize::MAX
+            ))
+        })?;
+    let mut indices = Vec::with_capacity(total);
+    for (i, n) in counts.into_iter().enumerate() {
+        indices.extend(std::iter::repeat_n(i as i64, n as usize));
+    }
+    Ok(SpicyObj::Series(Series::new("".into(), indices)))
+}
+
 pub fn within(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let arg0 = args[0];
     let arg1 = args[1];
@@ -274,32 +359,17 @@ pub fn within(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
             arg1.get_type_name(),
             arg1.size()
         )))
-    } else if arg0.is_atom() {
-        let v = arg1.as_vec()?;
-        let left = &v[0];
-        let right = &v[1];
-
-        if arg0.is_integer() || arg0.is_bool() {
-            let left = left.to_i64()?;
-            let right = right.to_i64()?;
-            let value = arg0.to_i64().unwrap();
-            Ok(SpicyObj::Boolean((value >= left) && (value <= right)))
-        } else if arg0.is_float() {
-            let left = left.to_f64()?;
-            let right = right.to_f64()?;
-            let value = arg0.to_f64().unwrap();
-            Ok(SpicyObj::Boolean((value >= left) && (value <= right)))
-        } else {
-            Err(err())
-        }
-    } else if arg0.is_series() {
-        let value = arg0.series().unwrap();
+    } else if arg0.is_atom() || arg0.is_series() {
+        // inclusive on both ends, comparisons follow `>=` and `<=` type rules
         let v = arg1.as_vec()?;
-        let left = v[0].as_series()?;
-        let right = v[1].as_series()?;
-        polars_ops::series::is_between(value, &left, &right, ClosedInterval::Both)
-            .map_err(|e| SpicyError::Err(e.to_string()))
-            .map(|s| SpicyObj::Series(s.into()))
+        let res = gt_eq(&[arg0, &v[0]]).and_then(|ge| {
+            let le = lt_eq(&[arg0, &v[1]])?;
+            and(&[&ge, &le])
+        });
+        res.map_err(|e| match e {
+            SpicyError::UnsupportedBinaryOpErr(..) => err(),
+            e => e,
+        })
     } else {
         Err(err())
     }
@@ -944,7 +1014,8 @@ pub fn ss(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let s0 = args[0].series().unwrap();
     let arg1 = args[1];
     let s1 = arg1.as_series().unwrap();
-    let out = polars_ops::series::search_sorted(s0, &s1, SearchSortedSide::Left, false)
+    let descending = s0.is_sorted_flag() == IsSorted::Descending;
+    let out = polars_ops::series::search_sorted(s0, &s1, SearchSortedSide::Left, descending)
         .map_err(|e| SpicyError::Err(e.to_string()))?;
     if arg1.is_atom() {
         Ok(SpicyObj::I64(out.get(0).unwrap() as i64))
@@ -971,7 +1042,8 @@ pub fn ssr(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let s0 = args[0].series().unwrap();
     let arg1 = args[1];
     let s1 = arg1.as_series().unwrap();
-    let out = polars_ops::series::search_sorted(s0, &s1, SearchSortedSide::Right, false)
+    let descending = s0.is_sorted_flag() == IsSorted::Descending;
+    let out = polars_ops::series::search_sorted(s0, &s1, SearchSortedSide::Right, descending)
         .map_err(|e| SpicyError::Err(e.to_string()))?;
     if arg1.is_atom() {
         Ok(SpicyObj::I64(out.get(0).unwrap() as i64))
@@ -1125,6 +1197,9 @@ pub fn union(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
 }
 
 pub fn when(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    if !args.iter().any(|a| a.is_expr()) && args[0].is_series() {
+        return when_series(args);
+    }
     let arg0 = args[0].as_expr()?;
     let arg1 = args[1].as_expr()?;
     let j2 = args[2].as_expr()?;
@@ -1133,6 +1208,41 @@ pub fn when(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     ))
 }
 
+// concrete when/then/otherwise, avoids building an expr for materialized series
+fn when_series(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let mask = args[0].series().unwrap();
+    if !mask.dtype().is_bool() {
+        return Err(SpicyError::Err(format!(
+            "Requires bools for condition, got '{}'",
+            args[0].get_type_name()
+        )));
+    }
+    let len = mask.len();
+    let to_series = |arg: &SpicyObj| -> SpicyResult<Series> {
+        let s = arg.as_series()?;
+        if arg.is_atom() {
+            Ok(s.new_from_index(0, len))
+        } else if s.len() != len {
+            Err(SpicyError::MismatchedLengthErr(len, s.len()))
+        } else {
+            Ok(s)
+        }
+    };
+    let then = to_series(args[1])?;
+    let otherwise = to_series(args[2])?;
+    if then.dtype() != otherwise.dtype() {
+        return Err(SpicyError::MismatchedTypeErr(
+            then.dtype().to_string(),
+            otherwise.dtype().to_string(),
+        ));
+    }
+    // null condition selects otherwise, same as polars when/then
+    let mask = mask.bool().unwrap().fill_null_with_values(false).unwrap();
+    then.zip_with(&mask, &otherwise)
+        .map(SpicyObj::Series)
+        .map_err(|e| SpicyError::Err(e.to_string()))
+}
+
 pub fn as_op(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::Any, ArgType::StrOrSym])?;
     let arg0 = args[0];
@@ -1,4 +1,4 @@
-use crate::{basic, collection, df, io, math, matrix, operator, str, sys, temporal};
+use crate::{basic, collection, df, io, math, matrix, operator, random, str, sys, temporal};
 use chili_core::Func;
 use std::{collections::HashMap, sync::LazyLock};
 
@@ -59,6 +59,33 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["type_name", "args"],
             ),
         ),
+        (
+            "reinterpret".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(operator::reinterpret)),
+                2,
+                "reinterpret",
+                &["type_name", "series"],
+            ),
+        ),
+        (
+            "cast_timestamp".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(operator::cast_timestamp)),
+                3,
+                "cast_timestamp",
+                &["unit", "epoch", "args"],
+            ),
+        ),
+        (
+            "cast_cat".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(operator::cast_cat)),
+                2,
+                "cast_cat",
+                &["domain", "series"],
+            ),
+        ),
         (
             "?".to_owned(),
             Func::new_built_in_fn(
@@ -92,6 +119,10 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "div".to_owned(),
             Func::new_built_in_fn(Some(Box::new(operator::div)), 2, "div", &["p1", "p2"]),
         ),
+        (
+            "hdiv".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(operator::hdiv)), 2, "hdiv", &["p1", "p2"]),
+        ),
         (
             "|".to_owned(),
             Func::new_built_in_fn(Some(Box::new(operator::or)), 2, "|", &["p1", "p2"]),
@@ -109,6 +140,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["integer", "collection"],
             ),
         ),
+        (
+            "take_strict".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(operator::take_strict)),
+                2,
+                "take_strict",
+                &["keys", "dict"],
+            ),
+        ),
         (
             "^".to_owned(),
             Func::new_built_in_fn(
@@ -136,6 +176,37 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["collection", "range"],
             ),
         ),
+        (
+            "bit_and".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(operator::bit_and)),
+                2,
+                "bit_and",
+                &["p1", "p2"],
+            ),
+        ),
+        (
+            "bit_or".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(operator::bit_or)), 2, "bit_or", &["p1", "p2"]),
+        ),
+        (
+            "bit_xor".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(operator::bit_xor)),
+                2,
+                "bit_xor",
+                &["p1", "p2"],
+            ),
+        ),
+        (
+            "xor".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(operator::logical_xor)),
+                2,
+                "xor",
+                &["p1", "p2"],
+            ),
+        ),
         (
             "bottom".to_owned(),
             Func::new_built_in_fn(Some(Box::new(basic::bottom)), 2, "bottom", &["k", "series"]),
@@ -156,6 +227,67 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "cross".to_owned(),
             Func::new_built_in_fn(Some(Box::new(basic::cross)), 2, "cross", &["p1", "p2"]),
         ),
+        (
+            "combine_means".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(math::combine_means)),
+                1,
+                "combine_means",
+                &["stats"],
+            ),
+        ),
+        (
+            "sign_split".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(math::sign_split)),
+                1,
+                "sign_split",
+                &["series"],
+            ),
+        ),
+        (
+            "edges".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(math::edges)),
+                2,
+                "edges",
+                &["series", "leading"],
+            ),
+        ),
+        (
+            "bits".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(math::bits)), 2, "bits", &["n", "width"]),
+        ),
+        (
+            "is_sorted".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(math::is_sorted)), 1, "is_sorted", &["series"]),
+        ),
+        (
+            "segment".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(math::segment)), 1, "segment", &["series"]),
+        ),
+        (
+            "segment_reset".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(math::segment_reset)),
+                1,
+                "segment_reset",
+                &["flags"],
+            ),
+        ),
+        (
+            "unbits".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(math::unbits)), 1, "unbits", &["bits"]),
+        ),
+        (
+            "falling_edges".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(math::falling_edges)),
+                2,
+                "falling_edges",
+                &["series", "leading"],
+            ),
+        ),
         (
             "emean".to_owned(),
             Func::new_built_in_fn(
@@ -223,10 +355,32 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["strings", "pattern"],
             ),
         ),
+        (
+            "match_value".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(operator::match_value)),
+                2,
+                "match_value",
+                &["p1", "p2"],
+            ),
+        ),
         (
             "mod".to_owned(),
             Func::new_built_in_fn(Some(Box::new(math::mod_op)), 2, "mod", &["p1", "p2"]),
         ),
+        (
+            "modulo".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(operator::modulo)), 2, "modulo", &["p1", "p2"]),
+        ),
+        (
+            "str_join_agg".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(str::str_join_agg)),
+                2,
+                "str_join_agg",
+                &["separator", "strings"],
+            ),
+        ),
         (
             "join".to_owned(),
             Func::new_built_in_fn(
@@ -236,6 +390,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["separator", "strings"],
             ),
         ),
+        (
+            "mcor".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(math::rolling_corr)),
+                3,
+                "mcor",
+                &["window", "series1", "series2"],
+            ),
+        ),
         (
             "mmax".to_owned(),
             Func::new_built_in_fn(
@@ -348,6 +511,14 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["condition", "then", "else"],
             ),
         ),
+        (
+            "iqr".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(math::iqr)), 1, "iqr", &["series"]),
+        ),
+        (
+            "qcut".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(math::qcut)), 2, "qcut", &["series", "n"]),
+        ),
         (
             "quantile".to_owned(),
             Func::new_built_in_fn(
@@ -585,6 +756,10 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "asc".to_owned(),
             Func::new_built_in_fn(Some(Box::new(collection::asc)), 1, "asc", &["series"]),
         ),
+        (
+            "iasc".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(collection::iasc)), 1, "iasc", &["collection"]),
+        ),
         (
             "bfill".to_owned(),
             Func::new_built_in_fn(
@@ -594,6 +769,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["series"],
             ),
         ),
+        (
+            "bfill_limit".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::backward_fill_limit)),
+                2,
+                "bfill_limit",
+                &["limit", "series"],
+            ),
+        ),
         (
             "cbrt".to_owned(),
             Func::new_built_in_fn(Some(Box::new(math::cbrt)), 1, "cbrt", &["n"]),
@@ -657,6 +841,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "desc".to_owned(),
             Func::new_built_in_fn(Some(Box::new(collection::desc)), 1, "desc", &["series"]),
         ),
+        (
+            "idesc".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::idesc)),
+                1,
+                "idesc",
+                &["collection"],
+            ),
+        ),
         (
             "diff".to_owned(),
             Func::new_built_in_fn(Some(Box::new(collection::diff)), 1, "diff", &["series"]),
@@ -691,6 +884,33 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["series"],
             ),
         ),
+        (
+            "fill_forward".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::forward_fill)),
+                1,
+                "fill_forward",
+                &["collection"],
+            ),
+        ),
+        (
+            "sanitize".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::sanitize)),
+                2,
+                "sanitize",
+                &["value", "data"],
+            ),
+        ),
+        (
+            "fill_limit".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::forward_fill_limit)),
+                2,
+                "fill_limit",
+                &["limit", "series"],
+            ),
+        ),
         (
             "hash".to_owned(),
             Func::new_built_in_fn(Some(Box::new(sys::nyi)), 1, "hash", &["n"]),
@@ -769,6 +989,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "mode".to_owned(),
             Func::new_built_in_fn(Some(Box::new(basic::mode)), 1, "mode", &["series"]),
         ),
+        (
+            "modes".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(basic::modes)),
+                2,
+                "modes",
+                &["series", "include_nulls"],
+            ),
+        ),
         (
             "not".to_owned(),
             Func::new_built_in_fn(Some(Box::new(operator::not)), 1, "not", &["series"]),
@@ -777,6 +1006,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "null".to_owned(),
             Func::new_built_in_fn(Some(Box::new(basic::null)), 1, "null", &["series"]),
         ),
+        (
+            "null_count".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::null_count)),
+                1,
+                "null_count",
+                &["collection"],
+            ),
+        ),
         (
             "pc".to_owned(),
             Func::new_built_in_fn(
@@ -811,6 +1049,32 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "trime".to_owned(),
             Func::new_built_in_fn(Some(Box::new(str::trim_end)), 1, "trime", &["series"]),
         ),
+        (
+            "genid".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(random::genid)), 2, "genid", &["mode", "n"]),
+        ),
+        (
+            "reservoir".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(random::reservoir)), 1, "reservoir", &["k"]),
+        ),
+        (
+            "reservoir_feed".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(random::reservoir_feed)),
+                2,
+                "reservoir_feed",
+                &["reservoir", "batch"],
+            ),
+        ),
+        (
+            "reservoir_sample".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(random::reservoir_sample)),
+                1,
+                "reservoir_sample",
+                &["reservoir"],
+            ),
+        ),
         (
             "shuffle".to_owned(),
             Func::new_built_in_fn(
@@ -864,6 +1128,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "tanh".to_owned(),
             Func::new_built_in_fn(Some(Box::new(math::tanh)), 1, "tanh", &["n"]),
         ),
+        (
+            "dedup_consecutive".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::dedup_consecutive)),
+                1,
+                "dedup_consecutive",
+                &["series"],
+            ),
+        ),
         (
             "unique".to_owned(),
             Func::new_built_in_fn(Some(Box::new(collection::unique)), 1, "unique", &["series"]),
@@ -877,6 +1150,78 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["series"],
             ),
         ),
+        (
+            "distinct".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::unique)),
+                1,
+                "distinct",
+                &["collection"],
+            ),
+        ),
+        (
+            "count_distinct".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::unique_count)),
+                1,
+                "count_distinct",
+                &["collection"],
+            ),
+        ),
+        (
+            "group".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::group)),
+                1,
+                "group",
+                &["collection"],
+            ),
+        ),
+        (
+            "approx_count_distinct".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::approx_count_distinct)),
+                1,
+                "approx_count_distinct",
+                &["series"],
+            ),
+        ),
+        (
+            "hll_sketch".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::hll_sketch)),
+                1,
+                "hll_sketch",
+                &["series"],
+            ),
+        ),
+        (
+            "hll_merge".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::hll_merge)),
+                2,
+                "hll_merge",
+                &["sketch0", "sketch1"],
+            ),
+        ),
+        (
+            "hll_estimate".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::hll_estimate)),
+                1,
+                "hll_estimate",
+                &["sketch"],
+            ),
+        ),
+        (
+            "chunks".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::chunks)),
+                2,
+                "chunks",
+                &["size", "collection"],
+            ),
+        ),
         (
             "uppercase".to_owned(),
             Func::new_built_in_fn(Some(Box::new(str::uppercase)), 1, "uppercase", &["string"]),
@@ -893,10 +1238,37 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "cols".to_owned(),
             Func::new_built_in_fn(Some(Box::new(df::cols)), 1, "cols", &["df"]),
         ),
+        (
+            "max_by".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(df::max_by)),
+                3,
+                "max_by",
+                &["df", "column", "first"],
+            ),
+        ),
+        (
+            "min_by".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(df::min_by)),
+                3,
+                "min_by",
+                &["df", "column", "first"],
+            ),
+        ),
         (
             "describe".to_owned(),
             Func::new_built_in_fn(Some(Box::new(df::describe)), 1, "describe", &["df"]),
         ),
+        (
+            "freq".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(df::freq)),
+                2,
+                "freq",
+                &["series", "drop_nulls"],
+            ),
+        ),
         (
             "enlist".to_owned(),
             Func::new_built_in_fn(Some(Box::new(basic::enlist)), 1, "enlist", &["n"]),
@@ -905,6 +1277,23 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "filter".to_owned(),
             Func::new_built_in_fn(Some(Box::new(basic::filter)), 1, "filter", &["series"]),
         ),
+        (
+            "where".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(basic::where_op)), 1, "where", &["collection"]),
+        ),
+        (
+            "attr".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(collection::flag)), 1, "attr", &["series"]),
+        ),
+        (
+            "set_attr".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::set_attr)),
+                2,
+                "set_attr",
+                &["flag", "series"],
+            ),
+        ),
         (
             "flag".to_owned(),
             Func::new_built_in_fn(Some(Box::new(collection::flag)), 1, "flag", &["series"]),
@@ -925,10 +1314,22 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "value".to_owned(),
             Func::new_built_in_fn(Some(Box::new(collection::values)), 1, "value", &["dict"]),
         ),
+        (
+            "profile".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(df::profile)), 1, "profile", &["df"]),
+        ),
         (
             "schema".to_owned(),
             Func::new_built_in_fn(Some(Box::new(df::schema)), 1, "schema", &["df"]),
         ),
+        (
+            "schema_dict".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(df::schema_dict)), 1, "schema_dict", &["df"]),
+        ),
+        (
+            "shrink".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(df::shrink)), 1, "shrink", &["df"]),
+        ),
         (
             "show".to_owned(),
             Func::new_built_in_fn(Some(Box::new(basic::show)), 1, "show", &["series"]),
@@ -937,6 +1338,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "range".to_owned(),
             Func::new_built_in_fn(Some(Box::new(collection::range)), 1, "range", &["series"]),
         ),
+        (
+            "transpose_records".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(collection::transpose_records)),
+                1,
+                "transpose_records",
+                &["records"],
+            ),
+        ),
         (
             "transpose".to_owned(),
             Func::new_built_in_fn(
@@ -950,11 +1360,33 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "type".to_owned(),
             Func::new_built_in_fn(Some(Box::new(basic::type_op)), 1, "type", &["args"]),
         ),
+        (
+            "type_code".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(basic::type_code)), 1, "type_code", &["args"]),
+        ),
         // other
         (
             "aj".to_owned(),
             Func::new_built_in_fn(Some(Box::new(df::aj)), 3, "aj", &["columns", "df0", "df1"]),
         ),
+        (
+            "aj_tolerance".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(df::aj_tolerance)),
+                4,
+                "aj_tolerance",
+                &["columns", "tolerance", "df0", "df1"],
+            ),
+        ),
+        (
+            "aj_strategy".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(df::aj_strategy)),
+                4,
+                "aj_strategy",
+                &["columns", "strategy", "df0", "df1"],
+            ),
+        ),
         (
             "cj".to_owned(),
             Func::new_built_in_fn(Some(Box::new(df::cj)), 3, "cj", &["columns", "df0", "df1"]),
@@ -1045,6 +1477,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["file", "has_header", "separator", "ignore_errors", "dtypes"],
             ),
         ),
+        (
+            "rcsv_dialect".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(io::read_csv_dialect)),
+                2,
+                "rcsv_dialect",
+                &["file", "dialect"],
+            ),
+        ),
         (
             "rdatabase".to_owned(),
             Func::new_built_in_fn(
@@ -1063,6 +1504,10 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["path", "sheet_name"],
             ),
         ),
+        (
+            "read_ipc".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(io::read_ipc)), 1, "read_ipc", &["path"]),
+        ),
         (
             "rjson".to_owned(),
             Func::new_built_in_fn(
@@ -1072,6 +1517,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["path", "dtypes"],
             ),
         ),
+        (
+            "rjson_null".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(io::read_json_null)),
+                3,
+                "rjson_null",
+                &["path", "dtypes", "null_values"],
+            ),
+        ),
         (
             "rparquet".to_owned(),
             Func::new_built_in_fn(
@@ -1081,6 +1535,28 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["path", "n_rows", "rechunk", "columns"],
             ),
         ),
+        (
+            "stream_agg".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(io::stream_agg)),
+                3,
+                "stream_agg",
+                &["source", "by", "aggs"],
+            ),
+        ),
+        (
+            "read".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(io::read)), 1, "read", &["path"]),
+        ),
+        (
+            "path_join".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(io::path_join)),
+                1,
+                "path_join",
+                &["components"],
+            ),
+        ),
         (
             "rtxt".to_owned(),
             Func::new_built_in_fn(Some(Box::new(io::read_txt)), 1, "rtxt", &["path"]),
@@ -1094,6 +1570,15 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["file", "df", "separator"],
             ),
         ),
+        (
+            "wcsv_null".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(io::write_csv_null)),
+                4,
+                "wcsv_null",
+                &["file", "df", "separator", "null_value"],
+            ),
+        ),
         (
             "wdatabase".to_owned(),
             Func::new_built_in_fn(
@@ -1112,10 +1597,45 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["path", "sheet_name", "df"],
             ),
         ),
+        (
+            "write_ipc".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(io::write_ipc)),
+                2,
+                "write_ipc",
+                &["df", "path"],
+            ),
+        ),
+        (
+            "write".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(io::write)), 2, "write", &["path", "data"]),
+        ),
+        (
+            "to_wire_hex".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(io::to_wire_hex)), 1, "to_wire_hex", &["args"]),
+        ),
+        (
+            "from_wire_hex".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(io::from_wire_hex)),
+                1,
+                "from_wire_hex",
+                &["hex"],
+            ),
+        ),
         (
             "wjson".to_owned(),
             Func::new_built_in_fn(Some(Box::new(io::write_json)), 2, "wjson", &["path", "df"]),
         ),
+        (
+            "wjson_null".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(io::write_json_null)),
+                3,
+                "wjson_null",
+                &["path", "df", "null_value"],
+            ),
+        ),
         (
             "wparquet".to_owned(),
             Func::new_built_in_fn(
@@ -1174,6 +1694,24 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
             "inv".to_owned(),
             Func::new_built_in_fn(Some(Box::new(matrix::inv)), 1, "inv", &["matrix"]),
         ),
+        (
+            "mat_to_frame".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(matrix::mat_to_frame)),
+                2,
+                "mat_to_frame",
+                &["matrix", "columns"],
+            ),
+        ),
+        (
+            "frame_to_mat".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(matrix::frame_to_mat)),
+                1,
+                "frame_to_mat",
+                &["df"],
+            ),
+        ),
         // query other
         (
             "clip".to_owned(),
@@ -1193,6 +1731,19 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["sep", "left", "right"],
             ),
         ),
+        (
+            "raze".to_owned(),
+            Func::new_built_in_fn(Some(Box::new(operator::raze)), 1, "raze", &["list"]),
+        ),
+        (
+            "concat_all".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(operator::concat_all)),
+                1,
+                "concat_all",
+                &["collections"],
+            ),
+        ),
         (
             "replace".to_owned(),
             Func::new_built_in_fn(
@@ -1248,7 +1799,7 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
         ),
         (
             "flip".to_owned(),
-            Func::new_built_in_fn(Some(Box::new(df::flip)), 1, "flip", &["dict"]),
+            Func::new_built_in_fn(Some(Box::new(df::flip)), 1, "flip", &["data"]),
         ),
         (
             "explode".to_owned(),
@@ -1298,6 +1849,24 @@ pub static BUILT_IN_FN: LazyLock<HashMap<String, Func>> = LazyLock::new(|| {
                 &["timestamp", "from_timezone", "to_timezone"],
             ),
         ),
+        (
+            "to_unix".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(temporal::to_unix)),
+                2,
+                "to_unix",
+                &["unit", "temporal"],
+            ),
+        ),
+        (
+            "from_unix".to_owned(),
+            Func::new_built_in_fn(
+                Some(Box::new(temporal::from_unix)),
+                2,
+                "from_unix",
+                &["unit", "args"],
+            ),
+        ),
     ]
     .into_iter()
     .collect()
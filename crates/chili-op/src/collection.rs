@@ -3,7 +3,7 @@ use polars_compute::rolling::RollingQuantileParams;
 
 use crate::{
     math,
-    operator::{eq, rand},
+    operator::{eq, lt, match_op, rand},
     random::get_global_random_u64,
 };
 use indexmap::IndexMap;
@@ -14,12 +14,15 @@ use polars::{
     datatypes::{LogicalType, PolarsFloatType},
     error::{PolarsResult, polars_bail},
     prelude::{
-        ChunkApply, FillNullStrategy, ListNameSpaceImpl, NamedFrom, RollingFnParams,
+        BooleanChunked, ChunkApply, ChunkCompareEq, FillNullStrategy, Float32Chunked,
+        Float64Chunked, IdxSize, ListNameSpaceImpl, NamedFrom, RollingFnParams,
         RollingOptionsFixedWindow, SortOptions, concat_str,
     },
-    series::{IntoSeries, ops::NullBehavior},
+    series::{IntoSeries, IsSorted, ops::NullBehavior},
     time::chunkedarray::SeriesOpsTime,
 };
+use std::cmp::Ordering;
+use std::collections::HashSet;
 
 use polars::{
     datatypes::{DataType, TimeUnit::Milliseconds as ms, TimeUnit::Nanoseconds as ns},
@@ -30,16 +33,9 @@ use polars_ops::{
     series::{InterpolationMethod, MomentSeries, clip as series_clip},
 };
 
+// nulls sort first
 pub fn asc(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
-    if args[0].is_expr() {
-        let left = args[0].as_expr()?;
-        return Ok(SpicyObj::Expr(left.sort(SortOptions::default())));
-    }
-    validate_args(args, &[ArgType::Series])?;
-    let s = args[0].series().unwrap();
-    s.sort(SortOptions::default())
-        .map_err(|_| SpicyError::UnsupportedUnaryOpErr("asc".to_owned(), args[0].get_type_name()))
-        .map(SpicyObj::Series)
+    sort_values(args, false)
 }
 
 pub fn backward_fill(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
@@ -63,6 +59,27 @@ pub fn forward_fill(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
             left.fill_null_with_strategy(FillNullStrategy::Forward(None)),
         ));
     }
+    // lists and dict values carry the previous non null item, leading nulls stay null
+    let carry = |items: &mut dyn Iterator<Item = &SpicyObj>| -> Vec<SpicyObj> {
+        let mut prev = SpicyObj::Null;
+        items
+            .map(|obj| {
+                if !obj.is_null() {
+                    prev = obj.clone();
+                }
+                prev.clone()
+            })
+            .collect()
+    };
+    match args[0] {
+        SpicyObj::MixedList(l) => return Ok(SpicyObj::MixedList(carry(&mut l.iter()))),
+        SpicyObj::Dict(d) => {
+            return Ok(SpicyObj::Dict(
+                d.keys().cloned().zip(carry(&mut d.values())).collect(),
+            ));
+        }
+        _ => {}
+    }
     validate_args(args, &[ArgType::Series])?;
     let s = args[0].series().unwrap();
     s.fill_null(FillNullStrategy::Forward(None))
@@ -70,6 +87,118 @@ pub fn forward_fill(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         .map(SpicyObj::Series)
 }
 
+// limit caps how many consecutive nulls a single value fills, the rest stay null
+fn fill_with_limit(
+    args: &[&SpicyObj],
+    name: &str,
+    strategy: fn(Option<IdxSize>) -> FillNullStrategy,
+) -> SpicyResult<SpicyObj> {
+    let limit = args[0].to_i64()?;
+    if limit <= 0 {
+        return Err(SpicyError::EvalErr(format!(
+            "{} requires a positive limit, got {}",
+            name, limit
+        )));
+    }
+    let strategy = strategy(Some(limit as IdxSize));
+    if args[1].is_expr() {
+        let left = args[1].as_expr()?;
+        return Ok(SpicyObj::Expr(left.fill_null_with_strategy(strategy)));
+    }
+    validate_args(args, &[ArgType::Int, ArgType::Series])?;
+    let s = args[1].series().unwrap();
+    s.fill_null(strategy)
+        .map_err(|_| SpicyError::UnsupportedUnaryOpErr(name.to_owned(), args[1].get_type_name()))
+        .map(SpicyObj::Series)
+}
+
+// limit, series
+pub fn forward_fill_limit(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    fill_with_limit(args, "fill_limit", FillNullStrategy::Forward)
+}
+
+// limit, series
+pub fn backward_fill_limit(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    fill_with_limit(args, "bfill_limit", FillNullStrategy::Backward)
+}
+
+// replaces NaN and +/-inf in float series, other dtypes pass through unchanged
+fn sanitize_series(s: &Series, value: Option<f64>) -> (Series, usize) {
+    let is_non_finite = |f: Option<f64>| f.is_some_and(|f| !f.is_finite());
+    let (res, altered) = match s.dtype() {
+        DataType::Float32 => {
+            let ca = s.f32().unwrap();
+            let altered = ca
+                .iter()
+                .filter(|f| is_non_finite(f.map(f64::from)))
+                .count();
+            let res = ca
+                .iter()
+                .map(|f| {
+                    f.and_then(|f| {
+                        if f.is_finite() {
+                            Some(f)
+                        } else {
+                            value.map(|v| v as f32)
+                        }
+                    })
+                })
+                .collect::<Float32Chunked>();
+            (res.into_series(), altered)
+        }
+        DataType::Float64 => {
+            let ca = s.f64().unwrap();
+            let altered = ca.iter().filter(|f| is_non_finite(*f)).count();
+            let res = ca
+                .iter()
+                .map(|f| f.and_then(|f| if f.is_finite() { Some(f) } else { value }))
+                .collect::<Float64Chunked>();
+            (res.into_series(), altered)
+        }
+        _ => return (s.clone(), 0),
+    };
+    if altered == 0 {
+        (s.clone(), 0)
+    } else {
+        (res.with_name(s.name().clone()), altered)
+    }
+}
+
+// value, series|df - value is a float to replace non-finite floats with, or null
+pub fn sanitize(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Any, ArgType::DataFrameOrSeries])?;
+    let value = if args[0].is_null() {
+        None
+    } else {
+        validate_args(args, &[ArgType::Float, ArgType::DataFrameOrSeries])?;
+        Some(args[0].to_f64()?)
+    };
+    let (data, altered) = match args[1] {
+        SpicyObj::Series(s) => {
+            let (s, altered) = sanitize_series(s, value);
+            (SpicyObj::Series(s), altered)
+        }
+        SpicyObj::DataFrame(df) => {
+            let mut df = df.clone();
+            let mut altered = 0;
+            for column in df.columns().to_vec() {
+                let (s, n) = sanitize_series(column.as_materialized_series(), value);
+                if n > 0 {
+                    altered += n;
+                    df.with_column(s)
+                        .map_err(|e| SpicyError::Err(e.to_string()))?;
+                }
+            }
+            (SpicyObj::DataFrame(df), altered)
+        }
+        _ => unreachable!(),
+    };
+    Ok(SpicyObj::Dict(IndexMap::from([
+        ("data".to_owned(), data),
+        ("altered".to_owned(), SpicyObj::I64(altered as i64)),
+    ])))
+}
+
 pub fn diff(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     if args[0].is_expr() {
         let left = args[0].as_expr()?;
@@ -82,19 +211,128 @@ pub fn diff(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         .map(SpicyObj::Series)
 }
 
+// nulls sort last
 pub fn desc(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
-    if args[0].is_expr() {
-        let left = args[0].as_expr()?;
-        return Ok(SpicyObj::Expr(
-            left.sort(SortOptions::default().with_order_descending(true)),
-        ));
+    sort_values(args, true)
+}
+
+// NaN is larger than any number, matching polars
+fn cmp_atoms(a: &SpicyObj, b: &SpicyObj) -> Ordering {
+    let is_nan = |o: &SpicyObj| {
+        matches!(o, SpicyObj::F32(f) if f.is_nan()) || matches!(o, SpicyObj::F64(f) if f.is_nan())
+    };
+    let is_true = |o: SpicyResult<SpicyObj>| o.and_then(|o| o.to_bool()).unwrap_or(false);
+    match (is_nan(a), is_nan(b)) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        _ if is_true(lt(&[a, b])) => Ordering::Less,
+        _ if is_true(lt(&[b, a])) => Ordering::Greater,
+        _ => Ordering::Equal,
+    }
+}
+
+// atoms are ordered by `<`, so each must compare with the first non null one
+fn sort_atoms<T>(
+    items: &mut [T],
+    value: impl Fn(&T) -> &SpicyObj,
+    descending: bool,
+    op: &str,
+) -> SpicyResult<()> {
+    let mut non_null = items.iter().map(&value).filter(|o| !o.is_null());
+    if let Some(first) = non_null.next() {
+        for o in std::iter::once(first).chain(non_null) {
+            if !o.is_atom() || lt(&[first, o]).and_then(|b| b.to_bool()).is_err() {
+                return Err(SpicyError::Err(format!(
+                    "{} requires comparable atoms, got '{}' and '{}'",
+                    op,
+                    first.get_type_name(),
+                    o.get_type_name()
+                )));
+            }
+        }
+    }
+    items.sort_by(|a, b| {
+        let (a, b) = (value(a), value(b));
+        let ord = match (a.is_null(), b.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => cmp_atoms(a, b),
+        };
+        if descending { ord.reverse() } else { ord }
+    });
+    Ok(())
+}
+
+// indices that sort a series or list ascending, nulls first
+pub fn iasc(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    sort_indices(args, false)
+}
+
+// indices that sort a series or list descending, nulls last
+pub fn idesc(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    sort_indices(args, true)
+}
+
+fn sort_indices(args: &[&SpicyObj], descending: bool) -> SpicyResult<SpicyObj> {
+    let op = if descending { "idesc" } else { "iasc" };
+    let indices = match args[0] {
+        SpicyObj::Series(s) => {
+            let options = SortOptions::default()
+                .with_order_descending(descending)
+                .with_nulls_last(descending);
+            s.arg_sort(options)
+                .into_series()
+                .cast(&DataType::Int64)
+                .map_err(|e| SpicyError::Err(e.to_string()))?
+        }
+        SpicyObj::MixedList(l) => {
+            let mut entries: Vec<(i64, SpicyObj)> = (0..).zip(l.iter().cloned()).collect();
+            sort_atoms(&mut entries, |(_, v)| v, descending, op)?;
+            Series::new(
+                "".into(),
+                entries.into_iter().map(|(i, _)| i).collect::<Vec<i64>>(),
+            )
+        }
+        arg0 => {
+            return Err(SpicyError::UnsupportedUnaryOpErr(
+                op.to_owned(),
+                arg0.get_type_name(),
+            ));
+        }
+    };
+    Ok(SpicyObj::Series(indices))
+}
+
+// series by polars, list by `<`, dict by value
+fn sort_values(args: &[&SpicyObj], descending: bool) -> SpicyResult<SpicyObj> {
+    let op = if descending { "desc" } else { "asc" };
+    let options = SortOptions::default()
+        .with_order_descending(descending)
+        .with_nulls_last(descending);
+    match args[0] {
+        arg0 if arg0.is_expr() => Ok(SpicyObj::Expr(arg0.as_expr()?.sort(options))),
+        SpicyObj::Series(s) => s
+            .sort(options)
+            .map_err(|_| SpicyError::UnsupportedUnaryOpErr(op.to_owned(), args[0].get_type_name()))
+            .map(SpicyObj::Series),
+        SpicyObj::MixedList(l) => {
+            let mut l = l.clone();
+            sort_atoms(&mut l, |o| o, descending, op)?;
+            Ok(SpicyObj::MixedList(l))
+        }
+        SpicyObj::Dict(d) => {
+            let mut entries: Vec<(String, SpicyObj)> =
+                d.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            sort_atoms(&mut entries, |(_, v)| v, descending, op)?;
+            Ok(SpicyObj::Dict(entries.into_iter().collect()))
+        }
+        arg0 => Err(SpicyError::UnsupportedUnaryOpErr(
+            op.to_owned(),
+            arg0.get_type_name(),
+        )),
     }
-    validate_args(args, &[ArgType::Series])?;
-    let s = args[0].series().unwrap();
-    let options = SortOptions::default().with_order_descending(true);
-    s.sort(options)
-        .map_err(|_| SpicyError::UnsupportedUnaryOpErr("desc".to_owned(), args[0].get_type_name()))
-        .map(SpicyObj::Series)
 }
 
 pub fn sum(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
@@ -173,6 +411,27 @@ pub fn count(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     Ok(SpicyObj::I64(arg0.size() as i64))
 }
 
+pub fn null_count(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    if arg0.is_expr() {
+        let left = arg0.as_expr()?;
+        return Ok(SpicyObj::Expr(left.null_count()));
+    }
+    let n = match arg0 {
+        SpicyObj::Series(s) => s.null_count(),
+        SpicyObj::MixedList(l) => l.iter().filter(|obj| obj.is_null()).count(),
+        SpicyObj::Dict(d) => d.values().filter(|obj| obj.is_null()).count(),
+        _ if arg0.is_atom() || arg0.is_null() => arg0.is_null() as usize,
+        _ => {
+            return Err(SpicyError::UnsupportedUnaryOpErr(
+                "null_count".to_owned(),
+                arg0.get_type_name(),
+            ));
+        }
+    };
+    Ok(SpicyObj::I64(n as i64))
+}
+
 pub fn cum_op(
     args: &[&SpicyObj],
     f: fn(s: &Series, reverse: bool) -> PolarsResult<Series>,
@@ -864,7 +1123,7 @@ pub fn median(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         | SpicyObj::Symbol(_)
         | SpicyObj::Null
         | SpicyObj::Fn(_) => Ok(arg0.clone()),
-        SpicyObj::Series(s) => Ok(SpicyObj::F64(s.median().unwrap_or(f64::NAN))),
+        SpicyObj::Series(s) => Ok(s.median().map_or(SpicyObj::Null, SpicyObj::F64)),
         _ => Err(err()),
     }
 }
@@ -1194,7 +1453,9 @@ pub fn reverse(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         | SpicyObj::Duration(_)
         | SpicyObj::F32(_)
         | SpicyObj::F64(_)
+        | SpicyObj::Symbol(_)
         | SpicyObj::Null => Ok(arg0.clone()),
+        SpicyObj::DataFrame(df) => Ok(SpicyObj::DataFrame(df.reverse())),
         SpicyObj::Matrix(m) => {
             if m.nrows() == 0 {
                 Ok(arg0.clone())
@@ -1327,6 +1588,76 @@ pub fn sign(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// hashable identity of an atom for `unique`, equal keys where `=` holds, e.g. 1, 1.0 and 1b, and
+// `a and "a"; temporals only match their own type
+#[derive(PartialEq, Eq, Hash)]
+enum UniqueKey<'a> {
+    Int(i64),
+    Float(u64),
+    Str(&'a str),
+    Temporal(i16, i64),
+}
+
+fn unique_key(obj: &SpicyObj) -> Option<UniqueKey<'_>> {
+    match obj {
+        SpicyObj::Boolean(_)
+        | SpicyObj::U8(_)
+        | SpicyObj::I16(_)
+        | SpicyObj::I32(_)
+        | SpicyObj::I64(_) => obj.to_i64().ok().map(UniqueKey::Int),
+        SpicyObj::F32(_) | SpicyObj::F64(_) => {
+            let v = obj.to_f64().ok()?;
+            if v.is_nan() {
+                // NaN never equals itself, so each one is kept like a null
+                None
+            } else if v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64 {
+                Some(UniqueKey::Int(v as i64))
+            } else {
+                Some(UniqueKey::Float(v.to_bits()))
+            }
+        }
+        SpicyObj::Symbol(s) | SpicyObj::String(s) => Some(UniqueKey::Str(s)),
+        SpicyObj::Date(_)
+        | SpicyObj::Time(_)
+        | SpicyObj::Datetime(_)
+        | SpicyObj::Timestamp(_)
+        | SpicyObj::Duration(_) => {
+            Some(UniqueKey::Temporal(obj.get_type_code(), obj.to_i64().ok()?))
+        }
+        _ => None,
+    }
+}
+
+// atoms dedupe through their key, other items keep the `=` scan, only against each other since
+// `=` never holds between an atom and a collection
+fn unique_items<'a>(items: impl Iterator<Item = &'a SpicyObj>) -> Vec<SpicyObj> {
+    let mut seen = HashSet::new();
+    let mut others: Vec<&SpicyObj> = Vec::new();
+    let mut res = Vec::new();
+    for obj in items {
+        let is_new = match unique_key(obj) {
+            Some(key) => seen.insert(key),
+            None if obj.is_null() || obj.is_atom() => true,
+            None => {
+                let exists = others.iter().any(|a| {
+                    eq(&[obj, a])
+                        .map(|b| b.bool().copied().unwrap_or(false))
+                        .unwrap_or(false)
+                });
+                if !exists {
+                    others.push(obj)
+                }
+                !exists
+            }
+        };
+        if is_new {
+            res.push(obj.clone())
+        }
+    }
+    res
+}
+
+// series, list, dict values or matrix rows, first occurrences in order
 pub fn unique(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let arg0 = args[0];
     if arg0.is_expr() {
@@ -1336,21 +1667,18 @@ pub fn unique(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let err = || SpicyError::UnsupportedUnaryOpErr("unique".to_owned(), arg0.get_type_name());
     match arg0 {
         SpicyObj::Series(s) => Ok(SpicyObj::Series(s.unique_stable().map_err(|_| err())?)),
-        SpicyObj::MixedList(l) => {
-            let mut res = Vec::new();
-            for obj in l {
-                let exists = res.iter().any(|a| {
-                    eq(&[obj, a])
-                        .map(|b| b.bool().copied().unwrap_or(false))
-                        .unwrap_or(false)
-                });
-                if exists {
-                    continue;
-                } else {
-                    res.push(obj.clone())
-                }
-            }
-            Ok(SpicyObj::MixedList(res))
+        SpicyObj::MixedList(l) => Ok(SpicyObj::MixedList(unique_items(l.iter()))),
+        SpicyObj::Dict(d) => Ok(SpicyObj::MixedList(unique_items(d.values()))),
+        SpicyObj::Matrix(m) => {
+            let mut seen = HashSet::new();
+            let rows: Vec<usize> = m
+                .rows()
+                .into_iter()
+                .enumerate()
+                .filter(|(_, row)| seen.insert(row.iter().map(|v| v.to_bits()).collect::<Vec<_>>()))
+                .map(|(i, _)| i)
+                .collect();
+            Ok(SpicyObj::Matrix(m.select(Axis(0), &rows).into_shared()))
         }
         SpicyObj::Boolean(_)
         | SpicyObj::U8(_)
@@ -1364,11 +1692,51 @@ pub fn unique(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         | SpicyObj::Duration(_)
         | SpicyObj::F32(_)
         | SpicyObj::F64(_)
+        | SpicyObj::Symbol(_)
+        | SpicyObj::String(_)
         | SpicyObj::Null => Ok(arg0.clone()),
         _ => Err(err()),
     }
 }
 
+// keep the first of each run of equal values, nulls compare equal to nulls
+pub fn dedup_consecutive(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    let err =
+        || SpicyError::UnsupportedUnaryOpErr("dedup_consecutive".to_owned(), arg0.get_type_name());
+    match arg0 {
+        SpicyObj::Series(s) => {
+            if s.len() < 2 {
+                return Ok(arg0.clone());
+            }
+            let len = s.len() - 1;
+            let changed = s
+                .slice(1, len)
+                .not_equal_missing(&s.slice(0, len))
+                .map_err(|_| err())?;
+            let mask: BooleanChunked = std::iter::once(true)
+                .chain(changed.iter().map(|b| b.unwrap_or(true)))
+                .collect();
+            Ok(SpicyObj::Series(s.filter(&mask).map_err(|_| err())?))
+        }
+        SpicyObj::MixedList(l) => {
+            let mut res: Vec<SpicyObj> = Vec::with_capacity(l.len());
+            for obj in l {
+                let is_dup = res
+                    .last()
+                    .map(|a| matches!(match_op(&[obj, a]), Ok(SpicyObj::Boolean(true))))
+                    .unwrap_or(false);
+                if !is_dup {
+                    res.push(obj.clone())
+                }
+            }
+            Ok(SpicyObj::MixedList(res))
+        }
+        _ if arg0.is_atom() || arg0.is_null() => Ok(arg0.clone()),
+        _ => Err(err()),
+    }
+}
+
 pub fn unique_count(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let arg0 = args[0];
     if arg0.is_expr() {
@@ -1378,22 +1746,10 @@ pub fn unique_count(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let err = || SpicyError::UnsupportedUnaryOpErr("uc".to_owned(), arg0.get_type_name());
     match arg0 {
         SpicyObj::Series(s) => Ok(SpicyObj::I64(s.n_unique().map_err(|_| err())? as i64)),
-        SpicyObj::MixedList(l) => {
-            let mut res = Vec::new();
-            for obj in l {
-                let exists = res.iter().any(|a| {
-                    eq(&[obj, a])
-                        .map(|b| b.bool().copied().unwrap_or(false))
-                        .unwrap_or(false)
-                });
-                if exists {
-                    continue;
-                } else {
-                    res.push(obj.clone())
-                }
-            }
-            Ok(SpicyObj::I64(res.len() as i64))
-        }
+        SpicyObj::MixedList(_) | SpicyObj::Dict(_) | SpicyObj::Matrix(_) => match unique(args)? {
+            SpicyObj::Matrix(m) => Ok(SpicyObj::I64(m.nrows() as i64)),
+            obj => Ok(SpicyObj::I64(obj.size() as i64)),
+        },
         SpicyObj::Boolean(_)
         | SpicyObj::U8(_)
         | SpicyObj::I16(_)
@@ -1406,11 +1762,186 @@ pub fn unique_count(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         | SpicyObj::Duration(_)
         | SpicyObj::F32(_)
         | SpicyObj::F64(_)
+        | SpicyObj::Symbol(_)
+        | SpicyObj::String(_)
         | SpicyObj::Null => Ok(SpicyObj::I64(1)),
         _ => Err(err()),
     }
 }
 
+// keys are the values as strings in first-seen order, nulls group under ""
+pub fn group(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    let err = || SpicyError::UnsupportedUnaryOpErr("group".to_owned(), arg0.get_type_name());
+    let keys: Vec<String> = match arg0 {
+        SpicyObj::Series(s) => {
+            let s = s.cast(&DataType::String).map_err(|_| err())?;
+            s.str()
+                .unwrap()
+                .iter()
+                .map(|k| k.unwrap_or("").to_owned())
+                .collect()
+        }
+        SpicyObj::MixedList(l) => l
+            .iter()
+            .map(|obj| match obj {
+                SpicyObj::Null => "".to_owned(),
+                SpicyObj::String(k) | SpicyObj::Symbol(k) => k.to_owned(),
+                _ => obj.to_string(),
+            })
+            .collect(),
+        _ => return Err(err()),
+    };
+    let mut groups: IndexMap<String, Vec<i64>> = IndexMap::new();
+    for (i, k) in keys.into_iter().enumerate() {
+        groups.entry(k).or_default().push(i as i64);
+    }
+    Ok(SpicyObj::Dict(
+        groups
+            .into_iter()
+            .map(|(k, v)| (k, SpicyObj::Series(Series::new("".into(), v))))
+            .collect(),
+    ))
+}
+
+/// HyperLogLog precision, 2^14 registers give a standard error of about 0.8%.
+const HLL_PRECISION: u32 = 14;
+const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+// splitmix64 finalizer
+fn hll_mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+// fnv-1a
+fn hll_hash_bytes(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |h, b| {
+        (h ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// one hash per value, nulls share a sentinel hash so they count once like `uc`
+fn hll_hashes(s: &Series) -> SpicyResult<Vec<u64>> {
+    let err = || SpicyError::UnsupportedUnaryOpErr("hll_sketch".to_owned(), s.dtype().to_string());
+    let null = hll_mix(u64::MAX);
+    let s = if s.dtype().is_categorical() {
+        s.cast(&DataType::String).map_err(|_| err())?
+    } else {
+        s.to_physical_repr().into_owned()
+    };
+    let hashes = match s.dtype() {
+        DataType::String => s
+            .str()
+            .unwrap()
+            .iter()
+            .map(|v| v.map_or(null, |v| hll_mix(hll_hash_bytes(v.as_bytes()))))
+            .collect(),
+        DataType::Float32 | DataType::Float64 => {
+            let s = s.cast(&DataType::Float64).map_err(|_| err())?;
+            s.f64()
+                .unwrap()
+                .iter()
+                .map(|v| {
+                    v.map_or(null, |v| {
+                        // -0.0 == 0.0 and all NaNs are the same value
+                        let v = if v == 0.0 {
+                            0.0
+                        } else if v.is_nan() {
+                            f64::NAN
+                        } else {
+                            v
+                        };
+                        hll_mix(v.to_bits())
+                    })
+                })
+                .collect()
+        }
+        dtype if dtype.is_bool() || dtype.is_integer() => {
+            let s = s.cast(&DataType::Int64).map_err(|_| err())?;
+            s.i64()
+                .unwrap()
+                .iter()
+                .map(|v| v.map_or(null, |v| hll_mix(v as u64)))
+                .collect()
+        }
+        _ => return Err(err()),
+    };
+    Ok(hashes)
+}
+
+fn hll_registers(s: &Series) -> SpicyResult<Vec<u8>> {
+    let mut registers = vec![0u8; HLL_REGISTERS];
+    for h in hll_hashes(s)? {
+        let i = (h >> (64 - HLL_PRECISION)) as usize;
+        let w = (h << HLL_PRECISION) | (1 << (HLL_PRECISION - 1));
+        let rank = w.leading_zeros() as u8 + 1;
+        if rank > registers[i] {
+            registers[i] = rank;
+        }
+    }
+    Ok(registers)
+}
+
+fn hll_estimate_registers(registers: &[u8]) -> i64 {
+    let m = HLL_REGISTERS as f64;
+    let alpha = 0.7213 / (1.0 + 1.079 / m);
+    let sum: f64 = registers.iter().map(|r| 2f64.powi(-(*r as i32))).sum();
+    let estimate = alpha * m * m / sum;
+    let zeros = registers.iter().filter(|r| **r == 0).count();
+    // linear counting for small cardinalities
+    let estimate = if estimate <= 2.5 * m && zeros > 0 {
+        m * (m / zeros as f64).ln()
+    } else {
+        estimate
+    };
+    estimate.round() as i64
+}
+
+fn hll_sketch_registers(arg: &SpicyObj) -> SpicyResult<Vec<u8>> {
+    let err = || {
+        SpicyError::Err(format!(
+            "Requires a hll sketch of {} u8 registers, got '{}'",
+            HLL_REGISTERS,
+            arg.get_type_name()
+        ))
+    };
+    let s = arg.series().map_err(|_| err())?;
+    if s.dtype() != &DataType::UInt8 || s.len() != HLL_REGISTERS {
+        return Err(err());
+    }
+    Ok(s.u8().unwrap().iter().map(|r| r.unwrap_or(0)).collect())
+}
+
+// series, estimated number of unique values
+pub fn approx_count_distinct(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Series])?;
+    let registers = hll_registers(args[0].series().unwrap())?;
+    Ok(SpicyObj::I64(hll_estimate_registers(&registers)))
+}
+
+// series, mergeable hll sketch as u8 registers
+pub fn hll_sketch(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Series])?;
+    let registers = hll_registers(args[0].series().unwrap())?;
+    Ok(SpicyObj::Series(Series::new("hll".into(), registers)))
+}
+
+// sketch, sketch
+pub fn hll_merge(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let r0 = hll_sketch_registers(args[0])?;
+    let r1 = hll_sketch_registers(args[1])?;
+    let registers: Vec<u8> = r0.iter().zip(r1.iter()).map(|(a, b)| *a.max(b)).collect();
+    Ok(SpicyObj::Series(Series::new("hll".into(), registers)))
+}
+
+// sketch
+pub fn hll_estimate(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let registers = hll_sketch_registers(args[0])?;
+    Ok(SpicyObj::I64(hll_estimate_registers(&registers)))
+}
+
 // op: in
 //  Lbchij | efdtzpnMDS
 // LSSSSSS | SSSSSSS-SS
@@ -1617,22 +2148,25 @@ pub fn values(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     ))
 }
 
+// `asc`, `desc` or an empty sym, the names shared by flag, set_attr and is_sorted
+pub(crate) fn sorted_flag_name(sorted: IsSorted) -> &'static str {
+    match sorted {
+        IsSorted::Ascending => "asc",
+        IsSorted::Descending => "desc",
+        IsSorted::Not => "",
+    }
+}
+
 pub fn flag(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::DataFrameOrSeries])?;
     match args[0] {
-        SpicyObj::Series(s) => match s.get_flags().is_sorted() {
-            polars::series::IsSorted::Ascending => Ok(SpicyObj::Symbol("asc".to_owned())),
-            polars::series::IsSorted::Descending => Ok(SpicyObj::Symbol("desc".to_owned())),
-            polars::series::IsSorted::Not => Ok(SpicyObj::Symbol("".to_owned())),
-        },
+        SpicyObj::Series(s) => Ok(SpicyObj::Symbol(
+            sorted_flag_name(s.is_sorted_flag()).to_owned(),
+        )),
         SpicyObj::DataFrame(df) => {
             let mut res = IndexMap::new();
             for c in df.columns() {
-                let flag = match c.get_flags().is_sorted() {
-                    polars::series::IsSorted::Ascending => "asc",
-                    polars::series::IsSorted::Descending => "desc",
-                    polars::series::IsSorted::Not => "",
-                };
+                let flag = sorted_flag_name(c.get_flags().is_sorted());
                 res.insert(c.name().to_string(), SpicyObj::Symbol(flag.to_owned()));
             }
             Ok(SpicyObj::Dict(res))
@@ -1641,6 +2175,86 @@ pub fn flag(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// flag, series - not verified, asserting one that doesn't hold gives wrong results
+pub fn set_attr(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Sym, ArgType::Series])?;
+    let flag = args[0].str().unwrap();
+    let sorted = [IsSorted::Ascending, IsSorted::Descending, IsSorted::Not]
+        .into_iter()
+        .find(|sorted| sorted_flag_name(*sorted) == flag)
+        .ok_or_else(|| {
+            SpicyError::Err(format!(
+                "Expect flag one of `asc`desc or empty sym, got '{}'",
+                flag
+            ))
+        })?;
+    let mut s = args[1].series().unwrap().clone();
+    s.set_sorted_flag(sorted);
+    Ok(SpicyObj::Series(s))
+}
+
+// list of dicts <-> dict of lists, keys missing from a record become nulls
+pub fn transpose_records(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    match arg0 {
+        SpicyObj::MixedList(records) => {
+            let mut keys: IndexMap<String, Vec<SpicyObj>> = IndexMap::new();
+            for record in records {
+                let record = match record {
+                    SpicyObj::Dict(d) => d,
+                    _ => {
+                        return Err(SpicyError::Err(format!(
+                            "Requires a list of dicts, got '{}'",
+                            record.get_type_name()
+                        )));
+                    }
+                };
+                for k in record.keys() {
+                    if !keys.contains_key(k) {
+                        keys.insert(k.to_owned(), vec![]);
+                    }
+                }
+            }
+            for record in records {
+                let record = record.dict().unwrap();
+                for (k, column) in keys.iter_mut() {
+                    column.push(record.get(k).cloned().unwrap_or(SpicyObj::Null));
+                }
+            }
+            Ok(SpicyObj::Dict(
+                keys.into_iter()
+                    .map(|(k, column)| (k, SpicyObj::MixedList(column)))
+                    .collect(),
+            ))
+        }
+        SpicyObj::Dict(columns) => {
+            let columns = columns
+                .iter()
+                .map(|(k, v)| Ok((k, v.as_vec()?)))
+                .collect::<SpicyResult<Vec<_>>>()?;
+            let len = columns.first().map(|(_, c)| c.len()).unwrap_or(0);
+            if let Some((_, c)) = columns.iter().find(|(_, c)| c.len() != len) {
+                return Err(SpicyError::MismatchedLengthErr(len, c.len()));
+            }
+            let records = (0..len)
+                .map(|i| {
+                    SpicyObj::Dict(
+                        columns
+                            .iter()
+                            .map(|(k, c)| (k.to_string(), c[i].clone()))
+                            .collect(),
+                    )
+                })
+                .collect();
+            Ok(SpicyObj::MixedList(records))
+        }
+        _ => Err(SpicyError::UnsupportedUnaryOpErr(
+            "transpose_records".to_owned(),
+            arg0.get_type_name(),
+        )),
+    }
+}
+
 pub fn transpose(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::DataFrameOrMatrix])?;
     let arg0 = args[0];
@@ -1672,3 +2286,26 @@ pub fn flatten(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         args[0]
     )))
 }
+
+// size, series or df
+pub fn chunks(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Int, ArgType::DataFrameOrSeries])?;
+    let size = args[0].to_i64().unwrap();
+    if size <= 0 {
+        return Err(SpicyError::EvalErr(format!(
+            "chunk size must be positive, got {}",
+            size
+        )));
+    }
+    let size = size as usize;
+    let len = args[1].size();
+    let chunks = (0..len)
+        .step_by(size)
+        .map(|offset| match args[1] {
+            SpicyObj::Series(s) => SpicyObj::Series(s.slice(offset as i64, size)),
+            SpicyObj::DataFrame(df) => SpicyObj::DataFrame(df.slice(offset as i64, size)),
+            _ => unreachable!(),
+        })
+        .collect();
+    Ok(SpicyObj::MixedList(chunks))
+}
@@ -4,18 +4,20 @@ use std::sync::Arc;
 
 use polars::{
     datatypes::DataType,
-    error::PolarsError,
+    error::{PolarsError, PolarsResult},
     frame::{DataFrame, PivotColumnNaming, UniqueKeepStrategy},
     lazy::dsl::{col, lit, when},
     prelude::{
-        AggExpr, AsofStrategy, Categories, Column, DataTypeExpr, ExplodeOptions, Expr, IntoColumn,
-        IntoLazy, NamedFrom, PlSmallStr, QuantileMethod, Selector, SortMultipleOptions,
-        SortOptions, UnpivotDF, int_ranges,
+        AggExpr, AnyValue, AsofStrategy, Categories, Column, DataTypeExpr, ExplodeOptions, Expr,
+        IntoColumn, IntoLazy, NamedFrom, PlSmallStr, QuantileMethod, Scalar, Selector,
+        SortMultipleOptions, SortOptions, UnpivotDF, int_ranges,
     },
     series::Series,
 };
 use polars_ops::frame::{AsOfOptions, JoinArgs, JoinCoalesce, JoinType, JoinValidation};
+use polars_ops::series::SeriesMethods;
 
+use crate::operator::CAST_DATA_TYPES;
 use crate::util::get_data_type_name;
 
 // df, idColumns, valueColumns
@@ -254,7 +256,79 @@ pub fn join_op(args: &[&SpicyObj], join_args: JoinArgs) -> SpicyResult<SpicyObj>
 
 // df, idColumns, valueColumns
 pub fn aj(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    asof_join(args, AsOfOptions::default())
+}
+
+// columns, tolerance, df0, df1
+// asof join that leaves the right columns null when the nearest match is further than tolerance
+pub fn aj_tolerance(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let tolerance = args[1];
+    let negative = || {
+        Err(SpicyError::EvalErr(format!(
+            "aj_tolerance requires a non-negative tolerance, got {}",
+            tolerance
+        )))
+    };
     let mut asof_options = AsOfOptions::default();
+    match tolerance {
+        // duration string keeps the time unit of the asof column, e.g. ms for datetime
+        SpicyObj::Duration(ns) => {
+            if *ns < 0 {
+                return negative();
+            }
+            asof_options.tolerance_str = Some(format!("{}ns", ns).into());
+        }
+        SpicyObj::I16(_) | SpicyObj::I32(_) | SpicyObj::I64(_) => {
+            let v = tolerance.to_i64().unwrap();
+            if v < 0 {
+                return negative();
+            }
+            asof_options.tolerance = Some(Scalar::new(DataType::Int64, AnyValue::Int64(v)));
+        }
+        SpicyObj::F32(_) | SpicyObj::F64(_) => {
+            let v = tolerance.to_f64().unwrap();
+            if v < 0.0 || v.is_nan() {
+                return negative();
+            }
+            asof_options.tolerance = Some(Scalar::new(DataType::Float64, AnyValue::Float64(v)));
+        }
+        _ => {
+            return Err(SpicyError::new_arg_type_err(
+                tolerance,
+                1,
+                &ArgType::Duration,
+            ));
+        }
+    }
+    asof_join(&[args[0], args[2], args[3]], asof_options)
+}
+
+// columns, strategy, df0, df1
+// asof join matching the prior (backward), next (forward) or nearest right row
+pub fn aj_strategy(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let strategy = match args[1] {
+        SpicyObj::Symbol(s) => s.as_str(),
+        arg1 => return Err(SpicyError::new_arg_type_err(arg1, 1, &ArgType::Sym)),
+    };
+    let strategy = match strategy {
+        "backward" => AsofStrategy::Backward,
+        "forward" => AsofStrategy::Forward,
+        "nearest" => AsofStrategy::Nearest,
+        strategy => {
+            return Err(SpicyError::Err(format!(
+                "Unknown 'aj_strategy' strategy '{}', expect one of backward, forward, nearest",
+                strategy
+            )));
+        }
+    };
+    let asof_options = AsOfOptions {
+        strategy,
+        ..Default::default()
+    };
+    asof_join(&[args[0], args[2], args[3]], asof_options)
+}
+
+fn asof_join(args: &[&SpicyObj], mut asof_options: AsOfOptions) -> SpicyResult<SpicyObj> {
     let arg0 = args[0];
     let mut syms = arg0.to_str_vec()?;
     if syms.len() > 1 {
@@ -604,6 +678,56 @@ pub fn cols(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     ))
 }
 
+fn extremum_by(args: &[&SpicyObj], is_max: bool) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::DataFrame, ArgType::Sym, ArgType::Boolean])?;
+    let df = args[0].df().unwrap();
+    let column = args[1].sym().unwrap();
+    let first = args[2].to_bool().unwrap();
+    if df.column(column).is_err() {
+        return Err(SpicyError::EvalErr(format!(
+            "missing column '{}' in df",
+            column
+        )));
+    }
+    let extremum = if is_max {
+        col(column).max()
+    } else {
+        col(column).min()
+    };
+    let mut lf = df.clone().lazy().filter(col(column).eq(extremum));
+    if first {
+        lf = lf.limit(1);
+    }
+    lf.collect()
+        .map_err(|e| SpicyError::Err(e.to_string()))
+        .map(SpicyObj::DataFrame)
+}
+
+// df, column, first
+pub fn max_by(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    extremum_by(args, true)
+}
+
+// df, column, first
+pub fn min_by(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    extremum_by(args, false)
+}
+
+// series, drop_nulls
+// value and count of each distinct value, most frequent first, nulls count as a value unless dropped
+pub fn freq(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Series, ArgType::Boolean])?;
+    let s = args[0].series().unwrap().clone().with_name("value".into());
+    let s = if args[1].to_bool().unwrap() {
+        s.drop_nulls()
+    } else {
+        s
+    };
+    s.value_counts(true, false, "count".into(), false)
+        .map_err(|e| SpicyError::Err(e.to_string()))
+        .map(SpicyObj::DataFrame)
+}
+
 pub fn describe(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::DataFrameOrSeries])?;
     let arg0 = args[0];
@@ -741,6 +865,143 @@ pub fn schema(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     ))
 }
 
+// df, column to type name in `CAST_DATA_TYPES`, for the `dtypes` of `rcsv` or `$`
+pub fn schema_dict(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::DataFrame])?;
+    let df = args[0].df().unwrap();
+    let dtypes = df
+        .columns()
+        .iter()
+        .map(|c| {
+            let name = get_data_type_name(c.dtype());
+            if CAST_DATA_TYPES.contains(&name) {
+                Ok((c.name().to_string(), SpicyObj::String(name.to_owned())))
+            } else {
+                Err(SpicyError::Err(format!(
+                    "column '{}' of type '{}' has no castable type name",
+                    c.name(),
+                    c.dtype()
+                )))
+            }
+        })
+        .collect::<SpicyResult<_>>()?;
+    Ok(SpicyObj::Dict(dtypes))
+}
+
+// min or max reduced in the column's own dtype, rendered as text like describe renders temporal
+// stats so every column fits one string column, null for dtypes without an order
+fn profile_extremum(reduced: PolarsResult<Scalar>) -> SpicyResult<Option<String>> {
+    let Ok(scalar) = reduced else {
+        return Ok(None);
+    };
+    let s = scalar
+        .into_series("".into())
+        .cast(&DataType::String)
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+    Ok(s.str().unwrap().get(0).map(|v| v.to_owned()))
+}
+
+// per column null count, distinct count, min and max and, for numeric columns, mean
+pub fn profile(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::DataFrame])?;
+    let df = args[0].df().unwrap();
+    let width = df.width();
+    let mut columns = Vec::with_capacity(width);
+    let mut types = Vec::with_capacity(width);
+    let mut null_counts = Vec::with_capacity(width);
+    let mut distinct_counts = Vec::with_capacity(width);
+    let mut mins = Vec::with_capacity(width);
+    let mut maxs = Vec::with_capacity(width);
+    let mut means = Vec::with_capacity(width);
+    let err = |e: PolarsError| SpicyError::Err(e.to_string());
+    for c in df.columns() {
+        let s = c.as_materialized_series();
+        columns.push(s.name().as_str());
+        types.push(get_data_type_name(s.dtype()));
+        null_counts.push(s.null_count() as i64);
+        // nulls are excluded from the distinct count
+        distinct_counts.push(s.drop_nulls().n_unique().map_err(err)? as i64);
+        mins.push(profile_extremum(s.min_reduce())?);
+        maxs.push(profile_extremum(s.max_reduce())?);
+        means.push(if s.dtype().is_primitive_numeric() {
+            s.mean()
+        } else {
+            None
+        });
+    }
+    DataFrame::new(
+        width,
+        vec![
+            Series::new("column".into(), columns).into(),
+            Series::new("datatype".into(), types).into(),
+            Series::new("null_count".into(), null_counts).into(),
+            Series::new("distinct_count".into(), distinct_counts).into(),
+            Series::new("min".into(), mins).into(),
+            Series::new("max".into(), maxs).into(),
+            Series::new("mean".into(), means).into(),
+        ],
+    )
+    .map_err(err)
+    .map(SpicyObj::DataFrame)
+}
+
+// downcast numeric columns to the smallest dtype that losslessly holds their values
+pub fn shrink(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::DataFrame])?;
+    let df = args[0].df().unwrap();
+    let columns = df
+        .columns()
+        .iter()
+        .map(|c| shrink_series(c.as_materialized_series()).map(|s| s.into_column()))
+        .collect::<SpicyResult<Vec<Column>>>()?;
+    DataFrame::new(df.height(), columns)
+        .map_err(|e| SpicyError::Err(e.to_string()))
+        .map(SpicyObj::DataFrame)
+}
+
+fn shrink_series(series: &Series) -> SpicyResult<Series> {
+    let dtype = series.dtype();
+    let s = if dtype.is_float() {
+        // only integer-valued floats are downcast, NaN and fractions cast to null
+        match series.cast(&DataType::Int64) {
+            Ok(s) if s.cast(dtype).is_ok_and(|f| f.equals_missing(series)) => s,
+            _ => return Ok(series.clone()),
+        }
+    } else if dtype.is_integer() {
+        series.clone()
+    } else {
+        return Ok(series.clone());
+    };
+    let (min, max) = match (s.min::<i128>(), s.max::<i128>()) {
+        (Ok(Some(min)), Ok(Some(max))) => (min, max),
+        _ => return Ok(series.clone()),
+    };
+    let candidates = if s.dtype().is_unsigned_integer() {
+        [
+            (DataType::UInt8, 0, u8::MAX as i128),
+            (DataType::UInt16, 0, u16::MAX as i128),
+            (DataType::UInt32, 0, u32::MAX as i128),
+            (DataType::UInt64, 0, u64::MAX as i128),
+        ]
+    } else {
+        [
+            (DataType::Int8, i8::MIN as i128, i8::MAX as i128),
+            (DataType::Int16, i16::MIN as i128, i16::MAX as i128),
+            (DataType::Int32, i32::MIN as i128, i32::MAX as i128),
+            (DataType::Int64, i64::MIN as i128, i64::MAX as i128),
+        ]
+    };
+    match candidates
+        .iter()
+        .find(|(_, lo, hi)| min >= *lo && max <= *hi)
+    {
+        Some((target, _, _)) if target != s.dtype() => s
+            .strict_cast(target)
+            .map_err(|e| SpicyError::Err(e.to_string())),
+        _ => Ok(s),
+    }
+}
+
 // may cause a reallocation
 pub fn extend(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::DataFrame, ArgType::DataFrame])?;
@@ -782,7 +1043,20 @@ pub fn vstack(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     Ok(SpicyObj::DataFrame(res))
 }
 
+// dict of equal-length series, df or matrix
+// a flipped df keeps its column names in a leading `column` column
 pub fn flip(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    match args[0] {
+        SpicyObj::Matrix(m) => return Ok(SpicyObj::Matrix(m.t().to_shared())),
+        SpicyObj::DataFrame(df) => {
+            return df
+                .clone()
+                .transpose(Some("column"), None)
+                .map_err(|e| SpicyError::Err(e.to_string()))
+                .map(SpicyObj::DataFrame);
+        }
+        _ => {}
+    }
     validate_args(args, &[ArgType::Dict])?;
     let arg0 = args[0].dict().unwrap();
 
@@ -800,12 +1074,18 @@ pub fn flip(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         })
         .collect::<SpicyResult<Vec<Column>>>()?;
     let height = columns.first().map(|c| c.len()).unwrap_or(0);
+    if let Some(c) = columns.iter().find(|c| c.len() != height) {
+        return Err(SpicyError::MismatchedLengthErr(height, c.len()));
+    }
 
     Ok(SpicyObj::DataFrame(
         DataFrame::new(height, columns).unwrap(),
     ))
 }
 
+// df, columns
+// undoes the per-group lists of `select ... by`, rows come back in group order
+// (groups are stable, first seen first), so keep a row index to restore the original order
 pub fn explode(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::DataFrame, ArgType::SymOrSyms])?;
     let df = args[0].df().unwrap();
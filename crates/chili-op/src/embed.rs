@@ -0,0 +1,16 @@
+use chili_core::EngineState;
+
+use crate::{BUILT_IN_FN, LOG_FN};
+
+/// Create an engine with all built-in fns registered, without the REPL, IPC listener,
+/// job scheduler or memory monitor. Evaluate with `EngineState::eval_str` and read
+/// results back with the `SpicyObj` accessors, e.g. `to_i64`, `to_f64`, `str`, `df`.
+///
+/// Wrap the state in an `Arc` and call `set_arc_self` before using fns that spawn
+/// threads on the engine, e.g. publishing to subscribers and exit hooks.
+pub fn new_engine(lazy: bool, pepper: bool) -> EngineState {
+    let state = EngineState::new(false, lazy, pepper);
+    state.register_fn(&LOG_FN);
+    state.register_fn(&BUILT_IN_FN);
+    state
+}
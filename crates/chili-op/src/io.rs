@@ -10,15 +10,16 @@ use polars::{
         frame::{LazyFrame, ScanArgsParquet},
     },
     prelude::{
-        Categories, CsvWriter, FileWriteFormat, IntoLazy, JsonFormat, JsonReader, JsonWriter,
-        ParquetReader, PlRefPath, PlSmallStr, SinkDestination, SinkTarget, UnifiedSinkArgs,
+        Categories, CsvWriter, DataFrame, Engine, FileWriteFormat, IntoLazy, JsonFormat,
+        JsonReader, JsonWriter, LazyCsvReader, LazyFileListReader, NamedFrom, NullValues,
+        ParquetReader, PlRefPath, PlSmallStr, Series, SinkDestination, SinkTarget, UnifiedSinkArgs,
     },
 };
 use std::{
     collections::HashMap,
     fs::{self, File, OpenOptions},
     io::Write,
-    path::{Path, PathBuf},
+    path::{MAIN_SEPARATOR_STR, Path, PathBuf, is_separator},
     str::FromStr,
     sync::{Arc, RwLock},
 };
@@ -29,7 +30,11 @@ use polars::{
 };
 use std::sync::LazyLock;
 
-use chili_core::{ArgType, SpicyError, SpicyObj, SpicyResult, validate_args};
+#[cfg(feature = "feather")]
+use polars::prelude::{IpcReader, IpcWriter};
+
+use chili_core::utils::{deserialize_q, serialize_q};
+use chili_core::{ArgType, SpicyError, SpicyObj, SpicyResult, serde9, validate_args};
 
 /// Cache `fs::canonicalize` results for HDB paths (invariant for process lifetime).
 static CANON_CACHE: LazyLock<RwLock<HashMap<String, PathBuf>>> =
@@ -70,29 +75,148 @@ pub fn read_csv(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let ignore_errors = args[3].to_bool()?;
     let dtypes = args[4];
 
-    let mut schema_ref: Option<SchemaRef> = None;
-    let mut columns = None;
+    let (schema_ref, columns) = csv_schema(dtypes)?;
 
-    // support dict only, as slice of datatype could fail
-    if dtypes.is_dict() && dtypes.size() > 0 {
-        let dict = dtypes.dict().unwrap();
-        let mut fields: Vec<Field> = Vec::with_capacity(dtypes.size());
-        let mut cols: Vec<PlSmallStr> = Vec::with_capacity(dtypes.size());
-        for i in 0..dtypes.size() {
-            let (key, value) = dict.get_index(i).unwrap();
-            fields.push(Field::new(
-                key.into(),
-                map_str_to_polars_dtype(value.str()?)?,
-            ));
-            cols.push(key.into());
+    let parse_options = CsvParseOptions::default()
+        .with_separator(separator.as_bytes()[0])
+        .with_missing_is_null(true)
+        .with_try_parse_dates(true);
+
+    let df = CsvReadOptions::default()
+        .with_has_header(has_header)
+        .with_schema_overwrite(schema_ref)
+        .with_ignore_errors(ignore_errors)
+        .with_columns(columns)
+        .with_parse_options(parse_options)
+        .try_into_reader_with_file_path(Some(file.into()))
+        .map_err(|e| SpicyError::EvalErr(e.to_string()))?
+        .finish()
+        .map_err(|e| SpicyError::EvalErr(e.to_string()))?;
+
+    Ok(SpicyObj::DataFrame(df))
+}
+
+// support dict only, as slice of datatype could fail
+#[allow(clippy::type_complexity)]
+fn csv_schema(dtypes: &SpicyObj) -> SpicyResult<(Option<SchemaRef>, Option<Arc<[PlSmallStr]>>)> {
+    if !dtypes.is_dict() || dtypes.size() == 0 {
+        return Ok((None, None));
+    }
+    let dict = dtypes.dict().unwrap();
+    let mut fields: Vec<Field> = Vec::with_capacity(dtypes.size());
+    let mut cols: Vec<PlSmallStr> = Vec::with_capacity(dtypes.size());
+    for i in 0..dtypes.size() {
+        let (key, value) = dict.get_index(i).unwrap();
+        fields.push(Field::new(
+            key.into(),
+            map_str_to_polars_dtype(value.str()?)?,
+        ));
+        cols.push(key.into());
+    }
+    let schema: Schema = fields.into_iter().collect();
+    Ok((Some(schema.into()), Some(Arc::from(cols))))
+}
+
+const CSV_DIALECT_KEYS: &str =
+    "delimiter, quote, header, comment, ignore_errors, dtypes, null_values, lazy";
+
+// a single byte char option, empty string or null disables it
+fn csv_dialect_char(key: &str, value: &SpicyObj) -> SpicyResult<Option<u8>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    let s = value.str().map_err(|_| {
+        SpicyError::Err(format!(
+            "csv dialect '{}' expects a string, got '{}'",
+            key,
+            value.get_type_name()
+        ))
+    })?;
+    match s.as_bytes() {
+        [] => Ok(None),
+        [b] => Ok(Some(*b)),
+        _ => Err(SpicyError::Err(format!(
+            "csv dialect '{}' expects a single byte char, got '{}'",
+            key, s
+        ))),
+    }
+}
+
+// path, dialect
+pub fn read_csv_dialect(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::StrOrSym, ArgType::Dict])?;
+    let file = args[0].str()?;
+    let dialect = args[1].dict()?;
+
+    let mut separator = b',';
+    let mut quote_char = Some(b'"');
+    let mut has_header = true;
+    let mut comment_prefix: Option<String> = None;
+    let mut ignore_errors = false;
+    let mut dtypes = &SpicyObj::Null;
+    let mut null_values: Option<NullValues> = None;
+    let mut is_lazy = false;
+    for (key, value) in dialect.iter() {
+        match key.as_str() {
+            "delimiter" => {
+                separator = csv_dialect_char(key, value)?.ok_or_else(|| {
+                    SpicyError::Err("csv dialect 'delimiter' cannot be empty".to_owned())
+                })?
+            }
+            "quote" => quote_char = csv_dialect_char(key, value)?,
+            "header" => has_header = value.to_bool()?,
+            "comment" => {
+                if !value.is_null() {
+                    let prefix = value.str()?;
+                    if !prefix.is_empty() {
+                        comment_prefix = Some(prefix.to_owned());
+                    }
+                }
+            }
+            "ignore_errors" => ignore_errors = value.to_bool()?,
+            "dtypes" => dtypes = value,
+            "null_values" => {
+                let tokens = value.to_str_vec()?;
+                null_values = Some(NullValues::AllColumns(
+                    tokens.into_iter().map(PlSmallStr::from).collect(),
+                ))
+            }
+            "lazy" => is_lazy = value.to_bool()?,
+            _ => {
+                return Err(SpicyError::Err(format!(
+                    "unknown csv dialect option '{}', expect one of {}",
+                    key, CSV_DIALECT_KEYS
+                )));
+            }
         }
-        let schema: Schema = fields.into_iter().collect();
-        schema_ref = Some(schema.into());
-        columns = Some(Arc::from(cols));
+    }
+    let (schema_ref, columns) = csv_schema(dtypes)?;
+
+    // a scan for the streaming engine, the file is read in batches once the query runs
+    if is_lazy {
+        let mut lf = LazyCsvReader::new(PlRefPath::new(file))
+            .with_has_header(has_header)
+            .with_separator(separator)
+            .with_quote_char(quote_char)
+            .with_comment_prefix(comment_prefix.map(PlSmallStr::from))
+            .with_null_values(null_values)
+            .with_missing_is_null(true)
+            .with_try_parse_dates(true)
+            .with_ignore_errors(ignore_errors)
+            .with_dtype_overwrite(schema_ref)
+            .finish()
+            .map_err(|e| SpicyError::EvalErr(e.to_string()))?;
+        if let Some(columns) = columns {
+            lf = lf.select(columns.iter().map(|c| col(c.clone())).collect::<Vec<_>>());
+        }
+        return Ok(SpicyObj::LazyFrame(lf));
     }
 
     let parse_options = CsvParseOptions::default()
-        .with_separator(separator.as_bytes()[0])
+        .with_separator(separator)
+        .with_quote_char(quote_char)
+        .with_comment_prefix(comment_prefix.as_deref())
+        .with_null_values(null_values)
         .with_missing_is_null(true)
         .with_try_parse_dates(true);
 
@@ -152,6 +276,64 @@ pub fn read_json(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     Ok(SpicyObj::DataFrame(df))
 }
 
+// json has no typed null token, so only string columns are mapped to and from null tokens
+fn str_nulls_to_token(df: &mut DataFrame, token: &str) -> SpicyResult<()> {
+    for name in df.get_column_names_owned() {
+        let column = df.column(&name).unwrap();
+        if column.dtype() != &DataType::String {
+            continue;
+        }
+        let values: Vec<&str> = column
+            .str()
+            .unwrap()
+            .iter()
+            .map(|v| v.unwrap_or(token))
+            .collect();
+        let s = Series::new(name, values);
+        df.with_column(s)
+            .map_err(|e| SpicyError::Err(e.to_string()))?;
+    }
+    Ok(())
+}
+
+fn str_tokens_to_nulls(df: &mut DataFrame, tokens: &[&str]) -> SpicyResult<()> {
+    for name in df.get_column_names_owned() {
+        let column = df.column(&name).unwrap();
+        if column.dtype() != &DataType::String {
+            continue;
+        }
+        let values: Vec<Option<&str>> = column
+            .str()
+            .unwrap()
+            .iter()
+            .map(|v| v.filter(|v| !tokens.contains(v)))
+            .collect();
+        let s = Series::new(name, values);
+        df.with_column(s)
+            .map_err(|e| SpicyError::Err(e.to_string()))?;
+    }
+    Ok(())
+}
+
+// path, dtypes, null_values
+pub fn read_json_null(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::StrOrSym, ArgType::Any, ArgType::StrOrStrs])?;
+    let file = args[0].str().unwrap();
+    let (schema_ref, _) = csv_schema(args[1])?;
+
+    // same json lines format as wjson_null writes
+    let mut file = File::open(file).map_err(|e| SpicyError::Err(e.to_string()))?;
+    let mut reader = JsonReader::new(&mut file).with_json_format(JsonFormat::JsonLines);
+    if let Some(schema) = &schema_ref {
+        reader = reader.with_schema_overwrite(schema);
+    }
+    let mut df = reader
+        .finish()
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+    str_tokens_to_nulls(&mut df, &args[2].to_str_vec()?)?;
+    Ok(SpicyObj::DataFrame(df))
+}
+
 // file, n_rows, rechunk, columns
 pub fn read_parquet(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(
@@ -213,6 +395,61 @@ pub fn read_parquet(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         .map(SpicyObj::DataFrame)
 }
 
+// parquet dataset path or a lazy scan, group by columns, aggregations
+pub fn stream_agg(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Any, ArgType::StrLike, ArgType::Any])?;
+    let by = args[1]
+        .to_str_vec()?
+        .into_iter()
+        .map(col)
+        .collect::<Vec<_>>();
+    let aggs = args[2].as_exprs()?;
+    let lf = match args[0] {
+        SpicyObj::LazyFrame(lf) => lf.clone(),
+        SpicyObj::String(path) | SpicyObj::Symbol(path) => LazyFrame::scan_parquet(
+            PlRefPath::new(Path::new(path).to_str().unwrap_or_default()),
+            ScanArgsParquet {
+                cache: false,
+                ..Default::default()
+            },
+        )
+        .map_err(|e| SpicyError::EvalErr(e.to_string()))?,
+        arg => {
+            return Err(SpicyError::EvalErr(format!(
+                "expect dataset path or lazy frame, got '{}'",
+                arg.get_type_name()
+            )));
+        }
+    };
+    // streaming engine processes the scan in morsels, input is never fully materialized
+    lf.group_by(by)
+        .agg(aggs)
+        .collect_with_engine(Engine::Streaming)
+        .map_err(|e| SpicyError::EvalErr(e.to_string()))
+        .map(SpicyObj::DataFrame)
+}
+
+// path components, joined with the os separator
+pub fn path_join(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Any])?;
+    let components = args[0].to_str_vec()?;
+    let mut path = PathBuf::new();
+    for (i, component) in components.iter().enumerate() {
+        // keep the root of the first component, later components never reset the path
+        let trimmed = if i == 0 {
+            component.trim_end_matches(is_separator)
+        } else {
+            component.trim_matches(is_separator)
+        };
+        if !trimmed.is_empty() {
+            path.push(trimmed);
+        } else if i == 0 && !component.is_empty() {
+            path.push(MAIN_SEPARATOR_STR);
+        }
+    }
+    Ok(SpicyObj::String(path.to_string_lossy().into_owned()))
+}
+
 // file path
 pub fn read_txt(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::StrOrSym])?;
@@ -224,10 +461,40 @@ pub fn read_txt(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
 // file path, df, sep
 pub fn write_csv(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::StrOrSym, ArgType::DataFrame, ArgType::Str])?;
-    let file = args[0].str().unwrap();
-    let df = args[1].df().unwrap();
-    let sep = args[2].str().unwrap();
-    if sep.len() > 1 || !sep.is_ascii() {
+    write_csv_with(
+        args[0].str().unwrap(),
+        args[1].df().unwrap(),
+        args[2].str().unwrap(),
+        None,
+    )
+}
+
+// file path, df, sep, null_value
+pub fn write_csv_null(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(
+        args,
+        &[
+            ArgType::StrOrSym,
+            ArgType::DataFrame,
+            ArgType::Str,
+            ArgType::Str,
+        ],
+    )?;
+    write_csv_with(
+        args[0].str().unwrap(),
+        args[1].df().unwrap(),
+        args[2].str().unwrap(),
+        Some(args[3].str().unwrap()),
+    )
+}
+
+fn write_csv_with(
+    file: &str,
+    df: &DataFrame,
+    sep: &str,
+    null_value: Option<&str>,
+) -> SpicyResult<SpicyObj> {
+    if sep.len() != 1 || !sep.is_ascii() {
         return Err(SpicyError::Err(format!(
             "expect len 1 ascii string, got '{}'",
             sep
@@ -238,8 +505,11 @@ pub fn write_csv(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let mut file = File::create(file)
         .map_err(|e| SpicyError::Err(format!("failed to create file '{}': {}", file, e)))?;
 
-    CsvWriter::new(&mut file)
-        .with_separator(sep)
+    let mut writer = CsvWriter::new(&mut file).with_separator(sep);
+    if let Some(null_value) = null_value {
+        writer = writer.with_null_value(null_value.to_owned());
+    }
+    writer
         .finish(&mut df.clone())
         .map_err(|e| SpicyError::Err(e.to_string()))?;
     Ok(SpicyObj::Null)
@@ -259,6 +529,14 @@ pub fn write_json(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     Ok(SpicyObj::Null)
 }
 
+// file path, df, null_value
+pub fn write_json_null(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::StrOrSym, ArgType::DataFrame, ArgType::Str])?;
+    let mut df = args[1].df().unwrap().clone();
+    str_nulls_to_token(&mut df, args[2].str().unwrap())?;
+    write_json(&[args[0], &SpicyObj::DataFrame(df)])
+}
+
 // file, df, compress level
 pub fn write_parquet(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::StrOrSym, ArgType::DataFrame, ArgType::Int])?;
@@ -634,6 +912,150 @@ pub fn write_txt(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     Ok(SpicyObj::Null)
 }
 
+// file path
+#[cfg(feature = "feather")]
+pub fn read_ipc(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::StrOrSym])?;
+    let file = args[0].str().unwrap();
+    let file = File::open(file)
+        .map_err(|e| SpicyError::Err(format!("failed to open file '{}': {}", file, e)))?;
+    IpcReader::new(file)
+        .finish()
+        .map_err(|e| SpicyError::Err(e.to_string()))
+        .map(SpicyObj::DataFrame)
+}
+
+#[cfg(not(feature = "feather"))]
+pub fn read_ipc(_: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    Err(SpicyError::Err("feather io is not enabled".to_string()))
+}
+
+// df, file path
+#[cfg(feature = "feather")]
+pub fn write_ipc(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::DataFrame, ArgType::StrOrSym])?;
+    let df = args[0].df().unwrap();
+    let file = args[1].str().unwrap();
+    let mut file = File::create(file)
+        .map_err(|e| SpicyError::Err(format!("failed to create file '{}': {}", file, e)))?;
+    IpcWriter::new(&mut file)
+        .finish(&mut df.clone())
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+    Ok(SpicyObj::Null)
+}
+
+#[cfg(not(feature = "feather"))]
+pub fn write_ipc(_: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    Err(SpicyError::Err("feather io is not enabled".to_string()))
+}
+
+const WRITE_READ_FORMATS: &str = "csv, parquet, json, ipc, chili";
+
+fn get_file_ext(path: &str) -> SpicyResult<String> {
+    match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some(ext) => Ok(ext.to_lowercase()),
+        None => Err(SpicyError::Err(format!(
+            "missing file extension for '{}', expect one of {}",
+            path, WRITE_READ_FORMATS
+        ))),
+    }
+}
+
+// file path, data
+pub fn write(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::StrOrSym, ArgType::Any])?;
+    let path = args[0];
+    let data = args[1];
+    match get_file_ext(path.str().unwrap())?.as_str() {
+        "csv" => write_csv(&[path, data, &SpicyObj::String(",".to_owned())]),
+        "parquet" => write_parquet(&[path, data, &SpicyObj::I64(3)]),
+        "json" => write_json(&[path, data]),
+        "ipc" => write_ipc(&[data, path]),
+        "chili" => {
+            let file = path.str().unwrap();
+            let mut file = File::create(file)
+                .map_err(|e| SpicyError::Err(format!("failed to create file '{}': {}", file, e)))?;
+            for b in serde9::serialize(data, true)? {
+                file.write_all(&b)
+                    .map_err(|e| SpicyError::Err(e.to_string()))?;
+            }
+            Ok(SpicyObj::Null)
+        }
+        ext => Err(SpicyError::Err(format!(
+            "unsupported file extension '{}', expect one of {}",
+            ext, WRITE_READ_FORMATS
+        ))),
+    }
+}
+
+// file path
+pub fn read(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::StrOrSym])?;
+    let path = args[0];
+    match get_file_ext(path.str().unwrap())?.as_str() {
+        "csv" => read_csv(&[
+            path,
+            &SpicyObj::Boolean(true),
+            &SpicyObj::String(",".to_owned()),
+            &SpicyObj::Boolean(false),
+            &SpicyObj::Null,
+        ]),
+        "parquet" => read_parquet(&[
+            path,
+            &SpicyObj::I64(0),
+            &SpicyObj::Boolean(false),
+            &SpicyObj::Null,
+        ]),
+        "json" => read_json(&[path, &SpicyObj::Null]),
+        "ipc" => read_ipc(&[path]),
+        "chili" => {
+            let bytes =
+                fs::read(path.str().unwrap()).map_err(|e| SpicyError::Err(e.to_string()))?;
+            serde9::deserialize(&bytes, &mut 0)
+        }
+        ext => Err(SpicyError::Err(format!(
+            "unsupported file extension '{}', expect one of {}",
+            ext, WRITE_READ_FORMATS
+        ))),
+    }
+}
+
+// any, q ipc bytes as space separated hex
+pub fn to_wire_hex(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let bytes = serialize_q(args[0])?;
+    let hex = bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(SpicyObj::String(hex))
+}
+
+// hex string
+pub fn from_wire_hex(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Str])?;
+    let hex: String = args[0]
+        .str()
+        .unwrap()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if hex.len() % 2 != 0 {
+        return Err(SpicyError::Err(format!(
+            "Requires an even number of hex digits, got {}",
+            hex.len()
+        )));
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| SpicyError::Err(format!("Invalid hex byte '{}'", &hex[i..i + 2])))
+        })
+        .collect::<SpicyResult<Vec<u8>>>()?;
+    deserialize_q(&bytes, &mut 0)
+}
+
 pub fn map_str_to_polars_dtype(s: &str) -> SpicyResult<DataType> {
     match DATATYPE_MAP.get(s) {
         Some(data_type) => Ok(data_type.clone()),
@@ -2,6 +2,7 @@ mod basic;
 mod built_in_fn;
 mod collection;
 mod df;
+mod embed;
 mod io;
 mod logger;
 mod math;
@@ -14,5 +15,6 @@ mod sys;
 mod temporal;
 mod util;
 pub use built_in_fn::BUILT_IN_FN;
+pub use embed::new_engine;
 pub use io::{write_partition_native, write_partition_native_full};
 pub use logger::LOG_FN;
@@ -2,18 +2,24 @@ use indexmap::IndexMap;
 use polars::{
     chunked_array::ops::ChunkApply,
     datatypes::DataType,
-    lazy::dsl::col,
+    error::{PolarsError, PolarsResult},
+    lazy::dsl::{col, lit},
     prelude::{
-        EWMOptions, IntoLazy, QuantileMethod, RollingFnParams, RollingOptionsFixedWindow,
-        RollingVarParams, RoundMode,
+        BooleanChunked, ChunkCompareEq, Column, EWMOptions, IntoColumn, IntoLazy, NamedFrom,
+        QuantileMethod, RollingFnParams, RollingOptionsFixedWindow, RollingVarParams, RoundMode,
+        SortOptions,
     },
-    series::IntoSeries,
+    series::{IntoSeries, IsSorted, Series},
     time::chunkedarray::SeriesOpsTime,
 };
 use polars_compute::rolling::RollingQuantileParams;
 use polars_ops::series::{LogSeries, RoundSeries, negate};
 
-use crate::util::cast_to_int;
+use crate::collection::sorted_flag_name;
+use crate::util::{
+    atom_op_dict, atom_op_list, cast_to_int, dict_op_atom, dict_op_list, list_op_atom,
+    list_op_dict, list_op_list,
+};
 use chili_core::{ArgType, SpicyError, SpicyObj, SpicyResult, validate_args};
 
 pub fn abs(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
@@ -463,6 +469,282 @@ pub(crate) fn tanh(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     float_op(args, f32::tanh, f64::tanh, &err)
 }
 
+// dict of group -> (count, mean), or (counts, means), pooled into the overall count and mean
+pub fn combine_means(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Any])?;
+    let arg0 = args[0];
+    let pairs = match arg0 {
+        SpicyObj::Dict(d) => d
+            .values()
+            .map(|v| {
+                let v = v.as_vec()?;
+                if v.len() != 2 {
+                    Err(SpicyError::MismatchedLengthErr(2, v.len()))
+                } else {
+                    Ok((v[0].clone(), v[1].clone()))
+                }
+            })
+            .collect::<SpicyResult<Vec<_>>>()?,
+        SpicyObj::MixedList(l) if l.len() == 2 => {
+            let counts = l[0].as_vec()?;
+            let means = l[1].as_vec()?;
+            if counts.len() != means.len() {
+                return Err(SpicyError::MismatchedLengthErr(counts.len(), means.len()));
+            }
+            counts.into_iter().zip(means).collect()
+        }
+        _ => {
+            return Err(SpicyError::Err(format!(
+                "Requires a dict of (count, mean) or (counts, means), got '{}'",
+                arg0.get_type_name()
+            )));
+        }
+    };
+    let mut total = 0i64;
+    let mut sum = 0.0;
+    for (count, mean) in pairs {
+        // groups with null or non-positive counts, or null means, are skipped
+        if count.is_null() || mean.is_null() {
+            continue;
+        }
+        let count = count.to_f64()?;
+        let mean = mean.to_f64()?;
+        if count.is_nan() || count <= 0.0 || mean.is_nan() {
+            continue;
+        }
+        total += count as i64;
+        sum += count * mean;
+    }
+    let mean = if total == 0 {
+        SpicyObj::Null
+    } else {
+        SpicyObj::F64(sum / total as f64)
+    };
+    Ok(SpicyObj::Dict(IndexMap::from([
+        ("count".to_owned(), SpicyObj::I64(total)),
+        ("mean".to_owned(), mean),
+    ])))
+}
+
+// numeric series, counts and sums of positive, negative and zero values, nulls excluded
+pub fn sign_split(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Series])?;
+    let s0 = args[0].series().unwrap();
+    if !(s0.dtype().is_bool() || s0.dtype().is_primitive_numeric()) {
+        return Err(SpicyError::UnsupportedUnaryOpErr(
+            "sign_split".to_owned(),
+            args[0].get_type_name(),
+        ));
+    }
+    let s0 = s0.cast(&DataType::Float64).unwrap();
+    let (mut pos_count, mut neg_count, mut zero_count) = (0i64, 0i64, 0i64);
+    let (mut pos_sum, mut neg_sum) = (0.0, 0.0);
+    for v in s0.f64().unwrap().iter().flatten() {
+        if v > 0.0 {
+            pos_count += 1;
+            pos_sum += v;
+        } else if v < 0.0 {
+            neg_count += 1;
+            neg_sum += v;
+        } else if v == 0.0 {
+            zero_count += 1;
+        }
+    }
+    Ok(SpicyObj::Dict(IndexMap::from([
+        ("pos_count".to_owned(), SpicyObj::I64(pos_count)),
+        ("neg_count".to_owned(), SpicyObj::I64(neg_count)),
+        ("zero_count".to_owned(), SpicyObj::I64(zero_count)),
+        ("pos_sum".to_owned(), SpicyObj::F64(pos_sum)),
+        ("neg_sum".to_owned(), SpicyObj::F64(neg_sum)),
+    ])))
+}
+
+// q's differ, true wherever a value differs from the one before it, the first value always
+// counts as a change and nulls equal nulls; chili's own `differ` is the set difference
+fn differ_flags(s0: &Series) -> PolarsResult<Series> {
+    let len = s0.len().saturating_sub(1);
+    let changed = s0.slice(1, len).not_equal_missing(&s0.slice(0, len))?;
+    let flags: BooleanChunked = std::iter::once(true)
+        .chain(changed.iter().map(|b| b.unwrap_or(true)))
+        .take(s0.len())
+        .collect();
+    Ok(flags.with_name(s0.name().clone()).into_series())
+}
+
+// indices where a bool series switches to `to`, nulls count as false; `leading` also reports
+// index 0 when the series already starts at `to`
+fn edge_indices(args: &[&SpicyObj], name: &str, to: bool) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Series, ArgType::Boolean])?;
+    let s0 = args[0].series().unwrap();
+    if !s0.dtype().is_bool() {
+        return Err(SpicyError::UnsupportedUnaryOpErr(
+            name.to_owned(),
+            args[0].get_type_name(),
+        ));
+    }
+    let leading = args[1].to_bool().unwrap();
+    let values: BooleanChunked = s0
+        .bool()
+        .unwrap()
+        .iter()
+        .map(|v| v.unwrap_or(false))
+        .collect();
+    let flags = differ_flags(&values.clone().into_series()).unwrap();
+    let indices: Vec<i64> = values
+        .iter()
+        .zip(flags.bool().unwrap().iter())
+        .enumerate()
+        .filter(|(i, (v, changed))| *v == Some(to) && *changed == Some(true) && (*i > 0 || leading))
+        .map(|(i, _)| i as i64)
+        .collect();
+    Ok(SpicyObj::Series(Series::new(name.into(), indices)))
+}
+
+// bool series, leading
+pub fn edges(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    edge_indices(args, "edges", true)
+}
+
+// bool series, leading
+pub fn falling_edges(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    edge_indices(args, "falling_edges", false)
+}
+
+// flag's `asc with nulls first or `desc with nulls last, otherwise an empty sym,
+// checks the values rather than trusting the sorted flag
+pub fn is_sorted(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Series])?;
+    let mut s0 = args[0].series().unwrap().clone();
+    s0.set_sorted_flag(IsSorted::Not);
+    let err = |e: PolarsError| SpicyError::Err(e.to_string());
+    let sorted = if s0.is_sorted(SortOptions::default()).map_err(err)? {
+        IsSorted::Ascending
+    } else if s0
+        .is_sorted(
+            SortOptions::default()
+                .with_order_descending(true)
+                .with_nulls_last(true),
+        )
+        .map_err(err)?
+    {
+        IsSorted::Descending
+    } else {
+        IsSorted::Not
+    };
+    Ok(SpicyObj::Symbol(sorted_flag_name(sorted).to_owned()))
+}
+
+// running sum of segment starts minus one, so the first segment is 0
+fn segment_ids(starts: &Series) -> PolarsResult<Series> {
+    let sums = polars_ops::series::cum_sum(&starts.cast(&DataType::Int64)?, false)?;
+    Ok(sums - 1)
+}
+
+// series
+// id of each run of equal values, increasing by 1 whenever the value changes, nulls equal to nulls
+pub fn segment(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Series])?;
+    let s0 = args[0].series().unwrap();
+    differ_flags(s0)
+        .and_then(|starts| segment_ids(&starts))
+        .map(SpicyObj::Series)
+        .map_err(|_| {
+            SpicyError::UnsupportedUnaryOpErr("segment".to_owned(), args[0].get_type_name())
+        })
+}
+
+// bool series
+// id of each segment, a new segment starts wherever the series is true, nulls count as false
+pub fn segment_reset(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Series])?;
+    let s0 = args[0].series().unwrap();
+    if !s0.dtype().is_bool() {
+        return Err(SpicyError::UnsupportedUnaryOpErr(
+            "segment_reset".to_owned(),
+            args[0].get_type_name(),
+        ));
+    }
+    let starts: BooleanChunked = s0
+        .bool()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .map(|(i, b)| i == 0 || b.unwrap_or(false))
+        .collect();
+    segment_ids(&starts.with_name(s0.name().clone()).into_series())
+        .map(SpicyObj::Series)
+        .map_err(|e| SpicyError::Err(e.to_string()))
+}
+
+// the low `width` bits of the two's complement, most significant bit first
+fn int_to_bits(n: i64, width: usize) -> Series {
+    let bits: Vec<bool> = (0..width).rev().map(|i| (n as u64 >> i) & 1 == 1).collect();
+    Series::new("".into(), bits)
+}
+
+// int | ints, width
+// bool series of the low `width` bits, most significant bit first, one series per int
+pub fn bits(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Any, ArgType::Int])?;
+    let arg0 = args[0];
+    let width = args[1].to_i64().unwrap();
+    if !(1..=64).contains(&width) {
+        return Err(SpicyError::Err(format!(
+            "Requires bit width between 1 and 64, got '{}'",
+            width
+        )));
+    }
+    let width = width as usize;
+    match arg0 {
+        _ if arg0.is_integer() => Ok(SpicyObj::Series(int_to_bits(arg0.to_i64().unwrap(), width))),
+        SpicyObj::Series(s) if s.dtype().is_integer() => {
+            let s = s.cast(&DataType::Int64).unwrap();
+            s.i64()
+                .unwrap()
+                .iter()
+                .map(|n| match n {
+                    Some(n) => Ok(SpicyObj::Series(int_to_bits(n, width))),
+                    None => Err(SpicyError::Err("Requires non-null ints".to_owned())),
+                })
+                .collect::<SpicyResult<Vec<_>>>()
+                .map(SpicyObj::MixedList)
+        }
+        _ => Err(SpicyError::UnsupportedUnaryOpErr(
+            "bits".to_owned(),
+            arg0.get_type_name(),
+        )),
+    }
+}
+
+// most significant bit first, nulls count as false
+fn bits_to_int(obj: &SpicyObj) -> SpicyResult<i64> {
+    match obj {
+        SpicyObj::Series(s) if s.dtype().is_bool() && s.len() <= 64 => {
+            Ok(s.bool()
+                .unwrap()
+                .iter()
+                .fold(0u64, |n, b| (n << 1) | b.unwrap_or(false) as u64) as i64)
+        }
+        _ => Err(SpicyError::Err(format!(
+            "Requires bool series of up to 64 bits, got '{}'",
+            obj.get_type_name()
+        ))),
+    }
+}
+
+// bool series | list of bool series
+// inverse of `bits`, 64 bits wrap to negative ints
+pub fn unbits(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Any])?;
+    match args[0] {
+        SpicyObj::MixedList(l) => Ok(SpicyObj::Series(Series::new(
+            "".into(),
+            l.iter().map(bits_to_int).collect::<SpicyResult<Vec<_>>>()?,
+        ))),
+        arg0 => Ok(SpicyObj::I64(bits_to_int(arg0)?)),
+    }
+}
+
 pub fn ewm_mean(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     if args[1].is_expr() {
         let alpha = args[0].to_f64()?;
@@ -652,6 +934,78 @@ pub fn rolling_max(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     ))
 }
 
+// pearson correlation over trailing windows of complete pairs, partial windows at the start
+// like the other rolling fns, null with fewer than 2 pairs
+fn rolling_corr_f64(window: usize, x: &[Option<f64>], y: &[Option<f64>]) -> Vec<Option<f64>> {
+    (0..x.len())
+        .map(|i| {
+            let start = (i + 1).saturating_sub(window);
+            let pairs = || (start..=i).filter_map(|j| Some((x[j]?, y[j]?)));
+            let (n, sum_x, sum_y) = pairs().fold((0usize, 0.0, 0.0), |(n, sx, sy), (a, b)| {
+                (n + 1, sx + a, sy + b)
+            });
+            if n < 2 {
+                return None;
+            }
+            let mean_x = sum_x / n as f64;
+            let mean_y = sum_y / n as f64;
+            let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+            for (a, b) in pairs() {
+                cov += (a - mean_x) * (b - mean_y);
+                var_x += (a - mean_x) * (a - mean_x);
+                var_y += (b - mean_y) * (b - mean_y);
+            }
+            Some(cov / (var_x * var_y).sqrt())
+        })
+        .collect()
+}
+
+fn rolling_corr_column(window: usize, x: &Column, y: &Column) -> PolarsResult<Column> {
+    let cast = |c: &Column| -> PolarsResult<Vec<Option<f64>>> {
+        Ok(c.cast(&DataType::Float64)?.f64()?.iter().collect())
+    };
+    let corr = rolling_corr_f64(window, &cast(x)?, &cast(y)?);
+    Ok(Series::new(x.name().clone(), corr).into_column())
+}
+
+// window, series, series
+pub fn rolling_corr(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let window = args[0].to_i64()?;
+    if window <= 0 {
+        return Err(SpicyError::EvalErr(format!(
+            "mcor requires a positive window, got {}",
+            window
+        )));
+    }
+    let window = window as usize;
+    if args[1].is_expr() || args[2].is_expr() {
+        let x = args[1].as_expr()?.cast(DataType::Float64);
+        let y = args[2].as_expr()?.cast(DataType::Float64);
+        return Ok(SpicyObj::Expr(x.map_many(
+            move |columns: &mut [Column]| rolling_corr_column(window, &columns[0], &columns[1]),
+            &[y],
+            |_, f| Ok(f[0].clone()),
+        )));
+    }
+    validate_args(
+        args,
+        &[ArgType::Int, ArgType::NumericLike, ArgType::NumericLike],
+    )?;
+    let s1 = args[1].as_series()?;
+    let s2 = args[2].as_series()?;
+    if s1.len() != s2.len() {
+        return Err(SpicyError::MismatchedLengthErr(s1.len(), s2.len()));
+    }
+    let cast = |s: &Series| -> SpicyResult<Vec<Option<f64>>> {
+        let s = s.cast(&DataType::Float64).map_err(|_| {
+            SpicyError::UnsupportedUnaryOpErr("mcor".to_owned(), s.dtype().to_string())
+        })?;
+        Ok(s.f64().unwrap().iter().collect())
+    };
+    let corr = rolling_corr_f64(window, &cast(&s1)?, &cast(&s2)?);
+    Ok(SpicyObj::Series(Series::new(s1.name().clone(), corr)))
+}
+
 pub fn rolling_mean(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     if args[1].is_expr() {
         let windows_size = args[0].to_i64()?;
@@ -890,6 +1244,33 @@ pub fn pow(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         let right = arg1.as_expr()?;
         return Ok(SpicyObj::Expr(left.pow(right)));
     }
+
+    if arg0.is_mixed_collection() || arg1.is_mixed_collection() {
+        return match (arg0, arg1) {
+            (SpicyObj::MixedList(l0), _) if arg1.is_atom() => list_op_atom(l0, arg1, pow),
+            (SpicyObj::Dict(d0), _) if arg1.is_atom() => dict_op_atom(d0, arg1, pow),
+            (_, SpicyObj::MixedList(l1)) if arg0.is_atom() => atom_op_list(arg0, l1, pow),
+            (_, SpicyObj::Dict(d1)) if arg0.is_atom() => atom_op_dict(arg0, d1, pow),
+            _ => {
+                if arg0.size() != arg1.size() {
+                    return Err(SpicyError::MismatchedLengthErr(arg0.size(), arg1.size()));
+                }
+                match (arg0, arg1) {
+                    (SpicyObj::Dict(d0), _) => dict_op_list(d0, &arg1.as_vec()?, pow),
+                    (_, SpicyObj::Dict(d1)) => list_op_dict(&arg0.as_vec()?, d1, pow),
+                    _ => list_op_list(&arg0.as_vec()?, &arg1.as_vec()?, pow),
+                }
+            }
+        };
+    }
+
+    if let SpicyObj::Matrix(m0) = arg0
+        && (arg1.is_bool() || arg1.is_numeric())
+    {
+        let f1 = arg1.to_f64()?;
+        return Ok(SpicyObj::Matrix(m0.clone().mapv_into(|x| x.powf(f1))));
+    }
+
     validate_args(args, &[ArgType::NumericLike, ArgType::NumericLike])?;
 
     if arg0.is_atom() && arg1.is_atom() {
@@ -898,12 +1279,29 @@ pub fn pow(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
                 arg0.to_f64().unwrap().powf(arg1.to_f64().unwrap()),
             ))
         } else {
-            Ok(SpicyObj::I64(
-                arg0.to_i64().unwrap().pow(arg1.to_i64().unwrap() as u32),
-            ))
+            let i0 = arg0.to_i64().unwrap();
+            let i1 = arg1.to_i64().unwrap();
+            // negative exponents and overflow promote to f64
+            match u32::try_from(i1).ok().and_then(|i1| i0.checked_pow(i1)) {
+                Some(i) => Ok(SpicyObj::I64(i)),
+                None => Ok(SpicyObj::F64((i0 as f64).powf(i1 as f64))),
+            }
         }
     } else {
         let s0 = arg0.as_series().unwrap();
+        let is_negative_exponent = match arg1 {
+            SpicyObj::Series(s1) if s1.dtype().is_integer() => s1
+                .min::<i64>()
+                .map_err(|e| SpicyError::Err(e.to_string()))?
+                .is_some_and(|i| i < 0),
+            _ => arg1.is_integer() && arg1.to_i64().unwrap() < 0,
+        };
+        let s0 = if s0.dtype().is_integer() && is_negative_exponent {
+            s0.cast(&DataType::Float64)
+                .map_err(|e| SpicyError::Err(e.to_string()))?
+        } else {
+            s0
+        };
         let s0_name = s0.name();
         let df = s0.clone().into_frame();
         let res = df
@@ -939,6 +1337,72 @@ pub fn quantile(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     Ok(SpicyObj::from_any_value(res.as_any_value()))
 }
 
+// series, q3 - q1 with nulls excluded
+pub fn iqr(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    if args[0].is_expr() {
+        let expr = args[0].as_expr()?;
+        return Ok(SpicyObj::Expr(
+            expr.clone().quantile(lit(0.75), QuantileMethod::Midpoint)
+                - expr.quantile(lit(0.25), QuantileMethod::Midpoint),
+        ));
+    }
+    validate_args(args, &[ArgType::NumericLike])?;
+    let s0 = args[0].as_series().unwrap();
+    let s0 = s0
+        .cast(&DataType::Float64)
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+    let quantile = |q: f64| -> SpicyResult<Option<f64>> {
+        let res = s0
+            .quantile_reduce(q, QuantileMethod::Midpoint)
+            .map_err(|e| SpicyError::Err(e.to_string()))?;
+        Ok(res.value().extract::<f64>())
+    };
+    match (quantile(0.25)?, quantile(0.75)?) {
+        (Some(q1), Some(q3)) => Ok(SpicyObj::F64(q3 - q1)),
+        _ => Ok(SpicyObj::Null),
+    }
+}
+
+// series, n
+// bucket index of each value from 0 to n - 1 with quantile edges, nulls and NaNs stay null.
+// a bucket is right closed, so values tied at an edge fall into the lower bucket,
+// heavy ties may collapse edges and leave some buckets empty.
+pub fn qcut(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::NumericLike, ArgType::Int])?;
+    let n = args[1].to_i64().unwrap();
+    if n < 1 {
+        return Err(SpicyError::EvalErr(format!(
+            "expect at least 1 bucket, got {}",
+            n
+        )));
+    }
+    let s0 = args[0].as_series().unwrap();
+    let s0 = s0
+        .cast(&DataType::Float64)
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+    let mut edges = Vec::with_capacity(n as usize - 1);
+    for k in 1..n {
+        let res = s0
+            .quantile_reduce(k as f64 / n as f64, QuantileMethod::Midpoint)
+            .map_err(|e| SpicyError::Err(e.to_string()))?;
+        match res.value().extract::<f64>() {
+            Some(edge) => edges.push(edge),
+            // all nulls
+            None => break,
+        }
+    }
+    let buckets: Vec<Option<i64>> = s0
+        .f64()
+        .unwrap()
+        .iter()
+        .map(|v| match v {
+            Some(v) if !v.is_nan() => Some(edges.partition_point(|e| *e < v) as i64),
+            _ => None,
+        })
+        .collect();
+    Ok(SpicyObj::Series(Series::new(s0.name().clone(), buckets)))
+}
+
 pub fn round(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     if args[0].is_expr() {
         let left = args[0].as_expr()?;
@@ -1,7 +1,12 @@
 #[cfg(feature = "matrix")]
 use ndarray_linalg::Inverse;
 
-use chili_core::{SpicyError, SpicyObj, SpicyResult};
+use chili_core::{ArgType, SpicyError, SpicyObj, SpicyResult, validate_args};
+use polars::{
+    datatypes::Float64Type,
+    frame::DataFrame,
+    prelude::{Column, IndexOrder, NamedFrom},
+};
 
 #[cfg(feature = "matrix")]
 pub fn inv(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
@@ -25,3 +30,56 @@ pub fn inv(_: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         "matrix operations are not enabled".to_string(),
     ))
 }
+
+// matrix, column names
+pub fn mat_to_frame(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    let arg1 = args[1];
+    let m = match arg0 {
+        SpicyObj::Matrix(m) => m,
+        _ => {
+            return Err(SpicyError::Err(format!(
+                "requires matrix, got '{}'",
+                arg0.get_type_name()
+            )));
+        }
+    };
+    let ncols = m.ncols();
+    let names: Vec<String> = if arg1.is_null() || arg1.size() == 0 {
+        (0..ncols).map(|i| format!("col{:02}", i)).collect()
+    } else {
+        let names = arg1.to_str_vec()?;
+        if names.len() != ncols {
+            return Err(SpicyError::MismatchedLengthErr(names.len(), ncols));
+        }
+        names.into_iter().map(|s| s.to_owned()).collect()
+    };
+    let cols = m
+        .columns()
+        .into_iter()
+        .zip(names)
+        .map(|(c, name)| Column::new(name.into(), c.to_vec()))
+        .collect::<Vec<Column>>();
+    DataFrame::new(m.nrows(), cols)
+        .map(SpicyObj::DataFrame)
+        .map_err(|e| SpicyError::EvalErr(e.to_string()))
+}
+
+// dataframe
+pub fn frame_to_mat(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::DataFrame])?;
+    let df = args[0].df().unwrap();
+    for c in df.columns() {
+        if !(c.dtype().is_primitive_numeric() || c.dtype().is_bool()) {
+            return Err(SpicyError::Err(format!(
+                "Requires numeric data type for column '{}', got '{}'",
+                c.name(),
+                c.dtype()
+            )));
+        }
+    }
+    let matrix = df
+        .to_ndarray::<Float64Type>(IndexOrder::C)
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+    Ok(SpicyObj::Matrix(matrix.to_shared()))
+}
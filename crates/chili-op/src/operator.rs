@@ -1,10 +1,12 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 use indexmap::IndexMap;
 use ndarray::{Array2, Axis, s};
 use polars::chunked_array::ops::ChunkFillNullValue;
 use polars::datatypes::{DataType, TimeUnit::Milliseconds as ms, TimeUnit::Nanoseconds as ns};
+use polars::error::PolarsError;
 use polars::prelude::{
-    Categories, ChunkCompareIneq, Expr, FunctionExpr, NamedFrom, Operator, concat_list,
-    floor_div_series,
+    CategoricalPhysical, Categories, ChunkAgg, ChunkCompareIneq, Expr, FunctionExpr, IntoSeries,
+    NamedFrom, Operator, PlSmallStr, StringChunked, concat_list, floor_div_series,
 };
 use polars::series::{ChunkCompareEq, Series};
 use polars_ops::series::{max_horizontal, min_horizontal};
@@ -12,7 +14,8 @@ use rand::distr::{Distribution, Uniform};
 use rand::rngs::SmallRng;
 use rand::seq::index::IndexVec;
 use rand::{RngExt, SeedableRng};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::LazyLock;
 
 use crate::random::get_global_random_u64;
@@ -21,12 +24,32 @@ use crate::util::{
     list_op_list,
 };
 use crate::{io::map_str_to_polars_dtype, math};
-use chili_core::{ArgType, SpicyError, SpicyObj, SpicyResult, validate_args};
+use chili_core::constant::NS_DIFF;
+use chili_core::{ArgType, FuncType, SpicyError, SpicyObj, SpicyResult, validate_args};
 
 pub const NS_IN_DAY: i64 = 86_400_000_000_000;
 pub const MS_IN_DAY: i64 = 86_400_000;
 pub const NS_IN_MS: i64 = 1_000_000;
 
+// widen a date to ms/ns, 0W/-0W/0N map onto the i64 extremes instead of overflowing
+fn date_to_unit(date: i32, unit: i64) -> i64 {
+    match date {
+        i32::MAX => i64::MAX,
+        i32::MIN => i64::MIN,
+        d if d == -i32::MAX => -i64::MAX,
+        d => (d as i64).saturating_mul(unit),
+    }
+}
+
+// widen a datetime to ns, keeping 0W/-0W/0N as the i64 extremes
+fn ms_to_ns(ms: i64) -> i64 {
+    match ms {
+        i64::MAX | i64::MIN => ms,
+        ms if ms == -i64::MAX => ms,
+        ms => ms.saturating_mul(NS_IN_MS),
+    }
+}
+
 // |     |    b|   u8|  u16|  u32|  u64|   i8|  i16|  i32|  i64| i128|  f32|  f64| date| time|   ms|   ns|    d|  str|  cat|
 // |    b|  u32|   u8|  u16|  u32|  u64|   i8|  i16|  i32|  i64| i128|  f32|  f64|    -|    -|    -|    -|    -|  str|    -|
 // |   u8|   u8|   u8|  u16|  u32|  u64|  i16|  i16|  i32|  i64| i128|  f32|  f64|    -|    -|    -|    -|    -|  str|    -|
@@ -703,6 +726,9 @@ pub fn mul(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
 pub fn dict(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let keys = args[0];
     let values = args[1];
+    if keys.is_integer() && keys.is_atom() {
+        return key_table(keys, values);
+    }
     let mut m: IndexMap<String, SpicyObj> = IndexMap::new();
     let err = || SpicyError::EvalErr("Not sym keys".to_owned());
     if keys.size() == values.size() {
@@ -749,6 +775,85 @@ pub fn dict(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// `n ! table` keys a table on its first n columns, `0 ! table` unkeys it
+fn key_table(n: &SpicyObj, table: &SpicyObj) -> SpicyResult<SpicyObj> {
+    let df = match table {
+        SpicyObj::DataFrame(df) | SpicyObj::KeyedTable(df, _) => df,
+        _ => {
+            return Err(SpicyError::UnsupportedBinaryOpErr(
+                "!".to_owned(),
+                n.get_type_name(),
+                table.get_type_name(),
+            ));
+        }
+    };
+    let keys = n.to_i64()?;
+    if keys == 0 {
+        Ok(SpicyObj::DataFrame(df.clone()))
+    } else if keys < 0 || keys as usize >= df.width() {
+        Err(SpicyError::Err(format!(
+            "Requires 0 to {} key columns, got {}",
+            df.width().saturating_sub(1),
+            keys
+        )))
+    } else {
+        Ok(SpicyObj::KeyedTable(df.clone(), keys as usize))
+    }
+}
+
+// Gauss-Jordan elimination with partial pivoting
+fn invert(m: &Array2<f64>) -> SpicyResult<Array2<f64>> {
+    let n = m.nrows();
+    if n != m.ncols() {
+        return Err(SpicyError::Err(format!(
+            "Only square matrix is invertible, got '{:?}'",
+            m.shape()
+        )));
+    }
+    let scale = m.iter().fold(0.0f64, |acc, v| acc.max(v.abs()));
+    let eps = scale * 1e-12;
+    let mut a = m.clone();
+    let mut inv = Array2::<f64>::eye(n);
+    for c in 0..n {
+        let pivot = (c..n)
+            .max_by(|&i, &j| a[[i, c]].abs().total_cmp(&a[[j, c]].abs()))
+            .unwrap();
+        if a[[pivot, c]].abs() <= eps {
+            return Err(SpicyError::Err(
+                "Matrix is singular, determinant is ~0".to_owned(),
+            ));
+        }
+        if pivot != c {
+            for k in 0..n {
+                a.swap([pivot, k], [c, k]);
+                inv.swap([pivot, k], [c, k]);
+            }
+        }
+        let p = a[[c, c]];
+        for k in 0..n {
+            a[[c, k]] /= p;
+            inv[[c, k]] /= p;
+        }
+        for i in 0..n {
+            let f = a[[i, c]];
+            if i == c || f == 0.0 {
+                continue;
+            }
+            for k in 0..n {
+                a[[i, k]] -= f * a[[c, k]];
+                inv[[i, k]] -= f * inv[[c, k]];
+            }
+        }
+    }
+    Ok(inv)
+}
+
+// matrix0 * inverse matrix1
+fn matrix_div(m0: &SpicyObj, m1: &SpicyObj) -> SpicyResult<SpicyObj> {
+    let inv = invert(&m1.matrix().unwrap().to_owned())?;
+    mul(&[m0, &SpicyObj::Matrix(inv.to_shared())])
+}
+
 // |     |    b|   u8|  u16|  u32|  u64|   i8|  i16|  i32|  i64| i128|  f32|  f64| date| time|   ms|   ns|    d|  str|  cat|
 // |    b|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|    -|    -|    -|    -|    -|    -|    -|
 // |   u8|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|  f64|    -|    -|    -|    -|    -|    -|    -|
@@ -919,8 +1024,7 @@ pub fn true_div(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         }
     } else if arg0.is_matrix() || arg1.is_matrix() {
         if arg0.is_matrix() && arg1.is_matrix() {
-            // matrix0 * inverse matrix1, pending
-            Err(err())
+            matrix_div(arg0, arg1)
         } else if arg0.is_matrix() && (arg1.is_bool() || arg1.is_numeric()) {
             let m0 = arg0.matrix().unwrap();
             Ok(SpicyObj::Matrix((m0 / arg1.to_f64().unwrap()).to_shared()))
@@ -935,6 +1039,39 @@ pub fn true_div(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// elementwise division, two matrices must share a shape, anything else is `true_div`
+pub fn hdiv(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    let arg1 = args[1];
+    match (arg0, arg1) {
+        (SpicyObj::Matrix(m0), SpicyObj::Matrix(m1)) => {
+            if m0.dim() != m1.dim() {
+                return Err(SpicyError::Err(format!(
+                    "hdiv requires matrices of the same shape, got '{:?}' and '{:?}'",
+                    m0.shape(),
+                    m1.shape()
+                )));
+            }
+            Ok(SpicyObj::Matrix((m0 / m1).to_shared()))
+        }
+        (SpicyObj::Matrix(m0), _) if arg1.is_bool() || arg1.is_numeric() => {
+            Ok(SpicyObj::Matrix((m0 / arg1.to_f64().unwrap()).to_shared()))
+        }
+        (_, SpicyObj::Matrix(m1)) if arg0.is_bool() || arg0.is_numeric() => {
+            let x = arg0.to_f64().unwrap();
+            Ok(SpicyObj::Matrix(m1.mapv(|y| x / y).to_shared()))
+        }
+        (SpicyObj::Matrix(_), _) | (_, SpicyObj::Matrix(_)) => {
+            Err(SpicyError::UnsupportedBinaryOpErr(
+                "hdiv".to_owned(),
+                arg0.get_type_name(),
+                arg1.get_type_name(),
+            ))
+        }
+        _ => true_div(args),
+    }
+}
+
 // |     |    b|   u8|  u16|  u32|  u64|   i8|  i16|  i32|  i64| i128|  f32|  f64| date| time|   ms|   ns|    d|  str|  cat|
 // |    b|    -|   u8|  u16|  u32|  u64|   i8|  i16|  i32|  i64| i128|  f32|  f64|    -|    -|    -|    -|    -|    -|    -|
 // |   u8|   u8|   u8|  u16|  u32|  u64|  i16|  i16|  i32|  i64| i128|  f32|  f64|    -|    -|    -|    -|    -|    -|    -|
@@ -1117,8 +1254,7 @@ pub fn div(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         }
     } else if arg0.is_matrix() || arg1.is_matrix() {
         if arg0.is_matrix() && arg1.is_matrix() {
-            // matrix0 * inverse matrix1, pending
-            Err(err())
+            matrix_div(arg0, arg1)
         } else if arg0.is_matrix() && (arg1.is_bool() || arg1.is_numeric()) {
             let m0 = arg0.matrix().unwrap();
             Ok(SpicyObj::Matrix(
@@ -1139,6 +1275,246 @@ pub fn div(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// floored like div, so x = div(x, y) * y + modulo(x, y)
+fn floor_mod_i64(x: i64, y: i64) -> i64 {
+    let r = x.wrapping_rem(y);
+    if r != 0 && (r < 0) != (y < 0) {
+        r + y
+    } else {
+        r
+    }
+}
+
+// zero divisors are nulled first, so they yield null instead of NaN or a panic
+fn modulo_series(s0: &Series, s1: &Series) -> Result<Series, PolarsError> {
+    let s1 = s1.zip_with(
+        &s1.not_equal(0)?,
+        &Series::full_null(s1.name().clone(), s1.len(), s1.dtype()),
+    )?;
+    s0 % &s1
+}
+
+pub fn modulo(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    let arg1 = args[1];
+    if arg0.is_expr() || arg1.is_expr() {
+        return Ok(SpicyObj::Expr(Expr::BinaryExpr {
+            left: arg0.as_expr()?.into(),
+            op: Operator::Modulus,
+            right: arg1.as_expr()?.into(),
+        }));
+    }
+    let c0 = arg0.get_type_code();
+    let c1 = arg1.get_type_code();
+    let op = "modulo";
+    let err = || {
+        SpicyError::UnsupportedBinaryOpErr(
+            op.to_owned(),
+            arg0.get_type_name(),
+            arg1.get_type_name(),
+        )
+    };
+
+    if arg0.is_null() || arg1.is_null() {
+        return Ok(SpicyObj::Null);
+    }
+
+    if arg0.str().is_ok() || arg1.str().is_ok() || arg0.is_temporal() || arg1.is_temporal() {
+        return Err(err());
+    }
+
+    if c0 < 0 && c1 < 0 {
+        if c0 >= -5 && c1 >= -5 {
+            let i1 = arg1.to_i64().unwrap();
+            if i1 == 0 {
+                return Ok(SpicyObj::Null);
+            }
+            let r = floor_mod_i64(arg0.to_i64().unwrap(), i1);
+            if c0 < c1 {
+                arg0.new_same_int_atom(r)
+            } else {
+                arg1.new_same_int_atom(r)
+            }
+        } else if c0 >= -12 && c1 >= -12 {
+            let f0 = arg0.to_f64().unwrap();
+            let f1 = arg1.to_f64().unwrap();
+            if f1 == 0.0 {
+                return Ok(SpicyObj::Null);
+            }
+            let r = f0 - f1 * (f0 / f1).floor();
+            if c0 >= -11 && c1 >= -11 {
+                Ok(SpicyObj::F32(r as f32))
+            } else {
+                Ok(SpicyObj::F64(r))
+            }
+        } else {
+            Err(err())
+        }
+    } else if arg0.is_mixed_collection() && c1 < 0 {
+        match arg0 {
+            SpicyObj::MixedList(l0) => Ok(list_op_atom(l0, arg1, modulo)?),
+            SpicyObj::Dict(d0) => Ok(dict_op_atom(d0, arg1, modulo)?),
+            _ => Err(err()),
+        }
+    } else if c0 < 0 && arg1.is_mixed_collection() {
+        match arg1 {
+            SpicyObj::MixedList(l1) => Ok(atom_op_list(arg0, l1, modulo)?),
+            SpicyObj::Dict(d1) => Ok(atom_op_dict(arg0, d1, modulo)?),
+            _ => Err(err()),
+        }
+    } else if arg0.is_mixed_collection() || arg1.is_mixed_collection() {
+        if arg0.size() != arg1.size() {
+            return Err(SpicyError::MismatchedLengthErr(arg0.size(), arg1.size()));
+        }
+        match (arg0, arg1) {
+            (SpicyObj::Dict(d0), SpicyObj::Dict(d1)) => {
+                let mut res = IndexMap::new();
+                for (k, v) in d0.iter() {
+                    match d1.get(k) {
+                        Some(obj) => {
+                            res.insert(k.to_string(), modulo(&[v, obj])?);
+                        }
+                        None => {
+                            res.insert(k.to_string(), v.clone());
+                        }
+                    }
+                }
+                for (k, v) in d1.iter() {
+                    if !d0.contains_key(k) {
+                        res.insert(k.to_string(), v.clone());
+                    }
+                }
+                Ok(SpicyObj::Dict(res))
+            }
+            (SpicyObj::Dict(d0), _) => dict_op_list(d0, &arg1.as_vec()?, modulo),
+            (_, SpicyObj::Dict(d1)) => list_op_dict(&arg0.as_vec()?, d1, modulo),
+            _ => list_op_list(&arg0.as_vec()?, &arg1.as_vec()?, modulo),
+        }
+    } else if arg0.is_series() || arg1.is_series() {
+        let s0 = arg0.as_series().map_err(|_| err())?;
+        let s1 = arg1.as_series().map_err(|_| err())?;
+        if !s0.dtype().is_primitive_numeric() || !s1.dtype().is_primitive_numeric() {
+            return Err(err());
+        }
+        Ok(SpicyObj::Series(
+            modulo_series(&s0, &s1).map_err(|e| SpicyError::Err(e.to_string()))?,
+        ))
+    } else {
+        Err(err())
+    }
+}
+
+// true bitwise ops on integers, unlike `&` and `|` which are min and max
+fn bitwise(args: &[&SpicyObj], op: Operator, f: FuncType) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    let arg1 = args[1];
+    if arg0.is_expr() || arg1.is_expr() {
+        return Ok(SpicyObj::Expr(Expr::BinaryExpr {
+            left: arg0.as_expr()?.into(),
+            op,
+            right: arg1.as_expr()?.into(),
+        }));
+    }
+    let name = match op {
+        Operator::And => "bit_and",
+        Operator::Or => "bit_or",
+        _ => "bit_xor",
+    };
+    let err = || {
+        SpicyError::UnsupportedBinaryOpErr(
+            name.to_owned(),
+            arg0.get_type_name(),
+            arg1.get_type_name(),
+        )
+    };
+
+    if arg0.is_null() || arg1.is_null() {
+        return Ok(SpicyObj::Null);
+    }
+
+    if arg0.is_integer() && arg1.is_integer() {
+        let i0 = arg0.to_i64().unwrap();
+        let i1 = arg1.to_i64().unwrap();
+        let r = match op {
+            Operator::And => i0 & i1,
+            Operator::Or => i0 | i1,
+            _ => i0 ^ i1,
+        };
+        if arg0.get_type_code() < arg1.get_type_code() {
+            arg0.new_same_int_atom(r)
+        } else {
+            arg1.new_same_int_atom(r)
+        }
+    } else if arg0.is_mixed_collection() && arg1.is_atom() {
+        match arg0 {
+            SpicyObj::MixedList(l0) => list_op_atom(l0, arg1, f),
+            SpicyObj::Dict(d0) => dict_op_atom(d0, arg1, f),
+            _ => Err(err()),
+        }
+    } else if arg0.is_atom() && arg1.is_mixed_collection() {
+        match arg1 {
+            SpicyObj::MixedList(l1) => atom_op_list(arg0, l1, f),
+            SpicyObj::Dict(d1) => atom_op_dict(arg0, d1, f),
+            _ => Err(err()),
+        }
+    } else if arg0.is_mixed_collection() || arg1.is_mixed_collection() {
+        if arg0.size() != arg1.size() {
+            return Err(SpicyError::MismatchedLengthErr(arg0.size(), arg1.size()));
+        }
+        match (arg0, arg1) {
+            (SpicyObj::Dict(d0), _) => dict_op_list(d0, &arg1.as_vec()?, f),
+            (_, SpicyObj::Dict(d1)) => list_op_dict(&arg0.as_vec()?, d1, f),
+            _ => list_op_list(&arg0.as_vec()?, &arg1.as_vec()?, f),
+        }
+    } else if arg0.is_series() || arg1.is_series() {
+        let s0 = arg0.as_series().map_err(|_| err())?;
+        let s1 = arg1.as_series().map_err(|_| err())?;
+        if !s0.dtype().is_integer() || !s1.dtype().is_integer() {
+            return Err(err());
+        }
+        // an atom takes the dtype and length of the series it is applied to
+        let (s0, s1) = if arg0.is_atom() {
+            let s0 = s0.cast(s1.dtype()).map_err(|_| err())?;
+            (s0.new_from_index(0, s1.len()), s1)
+        } else if arg1.is_atom() {
+            let s1 = s1.cast(s0.dtype()).map_err(|_| err())?;
+            let len = s0.len();
+            (s0, s1.new_from_index(0, len))
+        } else if s0.len() != s1.len() {
+            return Err(SpicyError::MismatchedLengthErr(s0.len(), s1.len()));
+        } else if s0.dtype() != s1.dtype() {
+            (
+                s0.cast(&DataType::Int64).map_err(|_| err())?,
+                s1.cast(&DataType::Int64).map_err(|_| err())?,
+            )
+        } else {
+            (s0, s1)
+        };
+        let res = match op {
+            Operator::And => &s0 & &s1,
+            Operator::Or => &s0 | &s1,
+            _ => &s0 ^ &s1,
+        };
+        Ok(SpicyObj::Series(
+            res.map_err(|e| SpicyError::Err(e.to_string()))?,
+        ))
+    } else {
+        Err(err())
+    }
+}
+
+pub fn bit_and(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    bitwise(args, Operator::And, bit_and)
+}
+
+pub fn bit_or(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    bitwise(args, Operator::Or, bit_or)
+}
+
+pub fn bit_xor(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    bitwise(args, Operator::Xor, bit_xor)
+}
+
 // |     |    b|   u8|  u16|  u32|  u64|   i8|  i16|  i32|  i64| i128|  f32|  f64| date| time|   ms|   ns|    d|  str|  cat|
 // |    b|    b|    b|    b|    b|    b|    b|    b|    b|    b|    b|    b|    b|    -|    -|    -|    -|    -|    b|    -|
 // |   u8|    b|    b|    b|    b|    b|    b|    b|    b|    b|    b|    b|    b|    -|    -|    -|    -|    -|    -|    -|
@@ -1194,26 +1570,28 @@ pub fn gt(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         match arg0 {
             SpicyObj::Date(t0) => match arg1 {
                 SpicyObj::Date(t1) => Ok(SpicyObj::Boolean(*t0 > *t1)),
-                SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean((*t0 as i64) * MS_IN_DAY > *t1)),
-                SpicyObj::Timestamp(t1) => Ok(SpicyObj::Boolean((*t0 as i64) * NS_IN_DAY > *t1)),
+                SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean(date_to_unit(*t0, MS_IN_DAY) > *t1)),
+                SpicyObj::Timestamp(t1) => {
+                    Ok(SpicyObj::Boolean(date_to_unit(*t0, NS_IN_DAY) > *t1))
+                }
                 _ => Err(err()),
             },
             SpicyObj::Time(t0) => match arg1 {
                 SpicyObj::Time(v1) => Ok(SpicyObj::Boolean(*t0 > *v1)),
-                SpicyObj::Datetime(v1) => Ok(SpicyObj::Boolean(*t0 > *v1 * NS_IN_MS % NS_IN_DAY)),
+                SpicyObj::Datetime(v1) => Ok(SpicyObj::Boolean(*t0 > ms_to_ns(*v1) % NS_IN_DAY)),
                 SpicyObj::Timestamp(v1) => Ok(SpicyObj::Boolean(*t0 > *v1 % NS_IN_DAY)),
                 _ => Err(err()),
             },
             SpicyObj::Datetime(_) | SpicyObj::Timestamp(_) => {
                 let t0 = match arg0 {
-                    SpicyObj::Datetime(t0) => *t0 * NS_IN_MS,
+                    SpicyObj::Datetime(t0) => ms_to_ns(*t0),
                     SpicyObj::Timestamp(t0) => *t0,
                     _ => return Err(err()),
                 };
                 match arg1 {
                     SpicyObj::Time(t1) => Ok(SpicyObj::Boolean((t0 % NS_IN_DAY) > *t1)),
-                    SpicyObj::Date(t1) => Ok(SpicyObj::Boolean(t0 > (*t1 as i64) * NS_IN_DAY)),
-                    SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean(t0 > *t1 * NS_IN_MS)),
+                    SpicyObj::Date(t1) => Ok(SpicyObj::Boolean(t0 > date_to_unit(*t1, NS_IN_DAY))),
+                    SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean(t0 > ms_to_ns(*t1))),
                     SpicyObj::Timestamp(t1) => Ok(SpicyObj::Boolean(t0 > *t1)),
                     _ => Err(err()),
                 }
@@ -1468,26 +1846,28 @@ pub fn lt(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         match arg0 {
             SpicyObj::Date(t0) => match arg1 {
                 SpicyObj::Date(t1) => Ok(SpicyObj::Boolean(*t0 < *t1)),
-                SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean((*t0 as i64) * MS_IN_DAY < *t1)),
-                SpicyObj::Timestamp(t1) => Ok(SpicyObj::Boolean((*t0 as i64) * NS_IN_DAY < *t1)),
+                SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean(date_to_unit(*t0, MS_IN_DAY) < *t1)),
+                SpicyObj::Timestamp(t1) => {
+                    Ok(SpicyObj::Boolean(date_to_unit(*t0, NS_IN_DAY) < *t1))
+                }
                 _ => Err(err()),
             },
             SpicyObj::Time(t0) => match arg1 {
                 SpicyObj::Time(v1) => Ok(SpicyObj::Boolean(*t0 < *v1)),
-                SpicyObj::Datetime(v1) => Ok(SpicyObj::Boolean(*t0 < *v1 * NS_IN_MS % NS_IN_DAY)),
+                SpicyObj::Datetime(v1) => Ok(SpicyObj::Boolean(*t0 < ms_to_ns(*v1) % NS_IN_DAY)),
                 SpicyObj::Timestamp(v1) => Ok(SpicyObj::Boolean(*t0 < *v1 % NS_IN_DAY)),
                 _ => Err(err()),
             },
             SpicyObj::Datetime(_) | SpicyObj::Timestamp(_) => {
                 let t0 = match arg0 {
-                    SpicyObj::Datetime(t0) => *t0 * NS_IN_MS,
+                    SpicyObj::Datetime(t0) => ms_to_ns(*t0),
                     SpicyObj::Timestamp(t0) => *t0,
                     _ => return Err(err()),
                 };
                 match arg1 {
                     SpicyObj::Time(t1) => Ok(SpicyObj::Boolean((t0 % NS_IN_DAY) < *t1)),
-                    SpicyObj::Date(t1) => Ok(SpicyObj::Boolean(t0 < (*t1 as i64) * NS_IN_DAY)),
-                    SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean(t0 < *t1 * NS_IN_MS)),
+                    SpicyObj::Date(t1) => Ok(SpicyObj::Boolean(t0 < date_to_unit(*t1, NS_IN_DAY))),
+                    SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean(t0 < ms_to_ns(*t1))),
                     SpicyObj::Timestamp(t1) => Ok(SpicyObj::Boolean(t0 < *t1)),
                     _ => Err(err()),
                 }
@@ -1764,26 +2144,30 @@ pub fn eq(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         match arg0 {
             SpicyObj::Date(t0) => match arg1 {
                 SpicyObj::Date(t1) => Ok(SpicyObj::Boolean(*t0 == *t1)),
-                SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean((*t0 as i64) * MS_IN_DAY == *t1)),
-                SpicyObj::Timestamp(t1) => Ok(SpicyObj::Boolean((*t0 as i64) * NS_IN_DAY == *t1)),
+                SpicyObj::Datetime(t1) => {
+                    Ok(SpicyObj::Boolean(date_to_unit(*t0, MS_IN_DAY) == *t1))
+                }
+                SpicyObj::Timestamp(t1) => {
+                    Ok(SpicyObj::Boolean(date_to_unit(*t0, NS_IN_DAY) == *t1))
+                }
                 _ => Err(err()),
             },
             SpicyObj::Time(t0) => match arg1 {
                 SpicyObj::Time(v1) => Ok(SpicyObj::Boolean(*t0 == *v1)),
-                SpicyObj::Datetime(v1) => Ok(SpicyObj::Boolean(*t0 == *v1 * NS_IN_MS % NS_IN_DAY)),
+                SpicyObj::Datetime(v1) => Ok(SpicyObj::Boolean(*t0 == ms_to_ns(*v1) % NS_IN_DAY)),
                 SpicyObj::Timestamp(v1) => Ok(SpicyObj::Boolean(*t0 == *v1 % NS_IN_DAY)),
                 _ => Err(err()),
             },
             SpicyObj::Datetime(_) | SpicyObj::Timestamp(_) => {
                 let t0 = match arg0 {
-                    SpicyObj::Datetime(t0) => *t0 * NS_IN_MS,
+                    SpicyObj::Datetime(t0) => ms_to_ns(*t0),
                     SpicyObj::Timestamp(t0) => *t0,
                     _ => return Err(err()),
                 };
                 match arg1 {
                     SpicyObj::Time(t1) => Ok(SpicyObj::Boolean((t0 % NS_IN_DAY) == *t1)),
-                    SpicyObj::Date(t1) => Ok(SpicyObj::Boolean(t0 == (*t1 as i64) * NS_IN_DAY)),
-                    SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean(t0 == *t1 * NS_IN_MS)),
+                    SpicyObj::Date(t1) => Ok(SpicyObj::Boolean(t0 == date_to_unit(*t1, NS_IN_DAY))),
+                    SpicyObj::Datetime(t1) => Ok(SpicyObj::Boolean(t0 == ms_to_ns(*t1))),
                     SpicyObj::Timestamp(t1) => Ok(SpicyObj::Boolean(t0 == *t1)),
                     _ => Err(err()),
                 }
@@ -1926,6 +2310,9 @@ pub fn ne(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// `=` compares values element-wise, promoting numerics to a common type, so `1i = 1` is 1b.
+// `~` is identity, the whole objects must have the same type and values, nulls match nulls,
+// so `1i ~ 1` and `1 2i ~ 1 2` are 0b. `match_value` is `~` with numerics promoted first.
 pub fn match_op(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let arg0 = args[0];
     let arg1 = args[1];
@@ -1939,8 +2326,10 @@ pub fn match_op(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
     match arg0 {
         SpicyObj::Series(s0) => match arg1.series() {
+            // series equality alone lets numeric dtypes differ
             Ok(s1) => Ok(SpicyObj::Boolean(
-                s0.clone().rename("".into()) == s1.clone().rename("".into()),
+                s0.dtype() == s1.dtype()
+                    && s0.clone().rename("".into()) == s1.clone().rename("".into()),
             )),
             Err(_) => Ok(SpicyObj::Boolean(false)),
         },
@@ -1948,6 +2337,71 @@ pub fn match_op(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+fn is_numeric_series(s: &Series) -> bool {
+    s.dtype().is_bool() || s.dtype().is_integer() || s.dtype().is_float()
+}
+
+fn is_value_match(arg0: &SpicyObj, arg1: &SpicyObj) -> SpicyResult<bool> {
+    let is_numeric_atom = |arg: &SpicyObj| arg.is_bool() || arg.is_integer() || arg.is_float();
+    match (arg0, arg1) {
+        _ if is_numeric_atom(arg0) && is_numeric_atom(arg1) => {
+            if arg0.is_float() || arg1.is_float() {
+                Ok(arg0.to_f64()? == arg1.to_f64()?)
+            } else {
+                Ok(arg0.to_i64()? == arg1.to_i64()?)
+            }
+        }
+        (SpicyObj::Series(s0), SpicyObj::Series(s1))
+            if is_numeric_series(s0) && is_numeric_series(s1) =>
+        {
+            let dtype = if s0.dtype().is_float() || s1.dtype().is_float() {
+                DataType::Float64
+            } else {
+                DataType::Int64
+            };
+            let mut s0 = s0
+                .cast(&dtype)
+                .map_err(|e| SpicyError::Err(e.to_string()))?;
+            let mut s1 = s1
+                .cast(&dtype)
+                .map_err(|e| SpicyError::Err(e.to_string()))?;
+            s0.rename("".into());
+            s1.rename("".into());
+            Ok(s0 == s1)
+        }
+        (SpicyObj::MixedList(l0), SpicyObj::MixedList(l1)) => {
+            if l0.len() != l1.len() {
+                return Ok(false);
+            }
+            for (a0, a1) in l0.iter().zip(l1) {
+                if !is_value_match(a0, a1)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        (SpicyObj::Dict(d0), SpicyObj::Dict(d1)) => {
+            if d0.len() != d1.len() {
+                return Ok(false);
+            }
+            for ((k0, v0), (k1, v1)) in d0.iter().zip(d1) {
+                if k0 != k1 || !is_value_match(v0, v1)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        _ => match_op(&[arg0, arg1])?.to_bool(),
+    }
+}
+
+pub fn match_value(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    if args[0].is_expr() || args[1].is_expr() {
+        return match_op(args);
+    }
+    Ok(SpicyObj::Boolean(is_value_match(args[0], args[1])?))
+}
+
 pub fn not(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let arg0 = args[0];
     if arg0.is_expr() {
@@ -2010,6 +2464,96 @@ pub fn not(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// coerce to a boolean series the same way `not` does
+fn truthy_series(s: &Series) -> Option<Series> {
+    if s.dtype().is_bool() {
+        Some(s.clone())
+    } else if s.dtype().is_integer() {
+        s.not_equal(0).ok().map(|s| s.into_series())
+    } else if s.dtype().is_float() {
+        s.not_equal(0.0).ok().map(|s| s.into_series())
+    } else if s.dtype().is_temporal() {
+        s.cast(&DataType::Int64)
+            .ok()?
+            .not_equal(0)
+            .ok()
+            .map(|s| s.into_series())
+    } else {
+        None
+    }
+}
+
+pub fn logical_xor(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    let arg1 = args[1];
+    if arg0.is_expr() || arg1.is_expr() {
+        return Ok(SpicyObj::Expr(Expr::BinaryExpr {
+            left: arg0.as_expr()?.cast(DataType::Boolean).into(),
+            op: Operator::Xor,
+            right: arg1.as_expr()?.cast(DataType::Boolean).into(),
+        }));
+    }
+    let op = "xor";
+    let err = || {
+        SpicyError::UnsupportedBinaryOpErr(
+            op.to_owned(),
+            arg0.get_type_name(),
+            arg1.get_type_name(),
+        )
+    };
+
+    if arg0.is_null() || arg1.is_null() {
+        return Ok(SpicyObj::Null);
+    }
+
+    if arg0.is_atom() && arg1.is_atom() {
+        let b0 = arg0.is_truthy().map_err(|_| err())?;
+        let b1 = arg1.is_truthy().map_err(|_| err())?;
+        Ok(SpicyObj::Boolean(b0 != b1))
+    } else if arg0.is_mixed_collection() && arg1.is_atom() {
+        match arg0 {
+            SpicyObj::MixedList(l0) => list_op_atom(l0, arg1, logical_xor),
+            SpicyObj::Dict(d0) => dict_op_atom(d0, arg1, logical_xor),
+            _ => Err(err()),
+        }
+    } else if arg0.is_atom() && arg1.is_mixed_collection() {
+        match arg1 {
+            SpicyObj::MixedList(l1) => atom_op_list(arg0, l1, logical_xor),
+            SpicyObj::Dict(d1) => atom_op_dict(arg0, d1, logical_xor),
+            _ => Err(err()),
+        }
+    } else if arg0.is_mixed_collection() || arg1.is_mixed_collection() {
+        if arg0.size() != arg1.size() {
+            return Err(SpicyError::MismatchedLengthErr(arg0.size(), arg1.size()));
+        }
+        match (arg0, arg1) {
+            (SpicyObj::Dict(d0), _) => dict_op_list(d0, &arg1.as_vec()?, logical_xor),
+            (_, SpicyObj::Dict(d1)) => list_op_dict(&arg0.as_vec()?, d1, logical_xor),
+            _ => list_op_list(&arg0.as_vec()?, &arg1.as_vec()?, logical_xor),
+        }
+    } else if arg0.is_series() || arg1.is_series() {
+        let s0 = arg0.as_series().map_err(|_| err())?;
+        let s1 = arg1.as_series().map_err(|_| err())?;
+        let s0 = truthy_series(&s0).ok_or_else(err)?;
+        let s1 = truthy_series(&s1).ok_or_else(err)?;
+        let (s0, s1) = if arg0.is_atom() {
+            (s0.new_from_index(0, s1.len()), s1)
+        } else if arg1.is_atom() {
+            let len = s0.len();
+            (s0, s1.new_from_index(0, len))
+        } else if s0.len() != s1.len() {
+            return Err(SpicyError::MismatchedLengthErr(s0.len(), s1.len()));
+        } else {
+            (s0, s1)
+        };
+        Ok(SpicyObj::Series(
+            (s0.bool().unwrap() ^ s1.bool().unwrap()).into_series(),
+        ))
+    } else {
+        Err(err())
+    }
+}
+
 pub fn append(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let op = ",";
     let arg0 = args[0];
@@ -2058,9 +2602,9 @@ pub fn append(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     if c0 == c1 && (90..=92).contains(&c0) {
         if c0 == 90 {
             // list
-            let l0 = arg0.list().unwrap().clone();
+            let mut l0 = arg0.list().unwrap().clone();
             let l1 = arg1.list().unwrap();
-            l0.clone().extend(l1.clone());
+            l0.extend(l1.iter().cloned());
             return Ok(SpicyObj::MixedList(l0));
         } else if c0 == 91 {
             // dict
@@ -2106,6 +2650,98 @@ pub fn append(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// list, items joined one level deep by the same rule as `,`, so items of the same type promote to
+// a series and dicts are merged left to right
+pub fn raze(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    match args[0] {
+        SpicyObj::MixedList(l) if !l.is_empty() && l.iter().all(|o| o.is_dict()) => {
+            let mut res = IndexMap::new();
+            for o in l {
+                for (k, v) in o.dict().unwrap() {
+                    res.insert(k.to_string(), v.clone());
+                }
+            }
+            Ok(SpicyObj::Dict(res))
+        }
+        SpicyObj::MixedList(_) => concat_all(args),
+        arg0 => Ok(arg0.clone()),
+    }
+}
+
+// list of series | frames, concatenated in one pass instead of folding `,`
+pub fn concat_all(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    let arg0 = args[0];
+    let items = match arg0 {
+        SpicyObj::MixedList(l) => l,
+        SpicyObj::Series(_) => return Ok(arg0.clone()),
+        _ => {
+            return Err(SpicyError::UnsupportedUnaryOpErr(
+                "concat_all".to_owned(),
+                arg0.get_type_name(),
+            ));
+        }
+    };
+    if items.is_empty() {
+        return Ok(SpicyObj::MixedList(vec![]));
+    }
+
+    if items.iter().all(|o| o.is_df()) {
+        let mut df = items[0].df().unwrap().clone();
+        for o in &items[1..] {
+            df.vstack_mut(o.df().unwrap())
+                .map_err(|e| SpicyError::EvalErr(e.to_string()))?;
+        }
+        df.rechunk_mut_par();
+        return Ok(SpicyObj::DataFrame(df));
+    }
+
+    // same rule as `,`: atoms and series of the same type are joined into a series
+    let codes: Vec<i16> = items
+        .iter()
+        .filter(|o| !o.is_null())
+        .map(|o| o.get_type_code().abs())
+        .collect();
+    if !codes.is_empty() && codes[0] < 20 && codes.iter().all(|c| *c == codes[0]) {
+        let series = items
+            .iter()
+            .map(|o| o.as_series())
+            .collect::<SpicyResult<Vec<Series>>>();
+        if let Ok(series) = series {
+            let dtype = series
+                .iter()
+                .map(|s| s.dtype())
+                .find(|d| !d.is_null())
+                .unwrap_or(series[0].dtype())
+                .clone();
+            let mut res = Series::new_empty(series[0].name().clone(), &dtype);
+            for s in &series {
+                let s = if s.dtype().is_null() {
+                    s.cast(&dtype).unwrap()
+                } else {
+                    s.clone()
+                };
+                if res.append(&s).is_err() {
+                    return concat_all_as_list(items);
+                }
+            }
+            return Ok(SpicyObj::Series(res.rechunk()));
+        }
+    }
+    concat_all_as_list(items)
+}
+
+fn concat_all_as_list(items: &[SpicyObj]) -> SpicyResult<SpicyObj> {
+    let mut res = Vec::new();
+    for o in items {
+        if o.is_mixed_list() || o.is_series() {
+            res.extend(o.as_vec()?);
+        } else {
+            res.push(o.clone());
+        }
+    }
+    Ok(SpicyObj::MixedList(res))
+}
+
 pub fn take(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let op = "#";
     let arg0 = args[0];
@@ -2123,6 +2759,13 @@ pub fn take(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         }
     }
 
+    if let SpicyObj::Series(mask) = arg0
+        && mask.dtype().is_bool()
+        && (arg1.is_series() || arg1.is_mixed_list() || arg1.is_df())
+    {
+        return take_by_mask(mask, arg1);
+    }
+
     let c0 = arg0.get_type_code();
     let err = || {
         SpicyError::UnsupportedBinaryOpErr(
@@ -2383,6 +3026,52 @@ pub fn take(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// bool series, (series | list | table)
+// elements where the mask is true, nulls in the mask count as false
+fn take_by_mask(mask: &Series, arg1: &SpicyObj) -> SpicyResult<SpicyObj> {
+    if mask.len() != arg1.size() {
+        return Err(SpicyError::MismatchedLengthErr(mask.len(), arg1.size()));
+    }
+    let mask = mask.bool().unwrap();
+    match arg1 {
+        SpicyObj::Series(s) => Ok(SpicyObj::Series(
+            s.filter(mask).map_err(|e| SpicyError::Err(e.to_string()))?,
+        )),
+        SpicyObj::DataFrame(df) => Ok(SpicyObj::DataFrame(
+            df.filter(mask)
+                .map_err(|e| SpicyError::Err(e.to_string()))?,
+        )),
+        SpicyObj::MixedList(l) => Ok(SpicyObj::MixedList(
+            l.iter()
+                .zip(mask.iter())
+                .filter(|(_, b)| b.unwrap_or(false))
+                .map(|(o, _)| o.clone())
+                .collect(),
+        )),
+        _ => unreachable!(),
+    }
+}
+
+// (sym | syms), dict
+// same as `#` on dict, but errors on keys missing from the dict
+pub fn take_strict(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::SymOrSyms, ArgType::Dict])?;
+    let keys = args[0].to_str_vec()?;
+    let d1 = args[1].dict().unwrap();
+    let missing: Vec<&str> = keys
+        .iter()
+        .filter(|k| !d1.contains_key(**k))
+        .copied()
+        .collect();
+    if !missing.is_empty() {
+        return Err(SpicyError::Err(format!(
+            "missing key(s) in dict: {}",
+            missing.join(", ")
+        )));
+    }
+    take(args)
+}
+
 pub fn apply(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     let op = ".";
     let arg0 = args[0];
@@ -2485,6 +3174,52 @@ pub const CAST_TEMPORAL_DATA_TYPES: [&str; 12] = [
     "ns",
 ];
 
+const DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+];
+
+// ISO 8601 strings, date and time separated by `T` or a space, a date alone is midnight
+fn parse_temporal_str(datatype: &str, v: &str) -> SpicyResult<SpicyObj> {
+    let v = v.trim();
+    let parse_datetime = || {
+        DATETIME_FORMATS
+            .iter()
+            .find_map(|f| NaiveDateTime::parse_from_str(v, f).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(v, "%Y-%m-%d")
+                    .ok()
+                    .map(|d| d.and_time(NaiveTime::MIN))
+            })
+            .map(|dt| dt.and_utc())
+    };
+    let res = match datatype {
+        "date" => parse_datetime()
+            .map(|dt| SpicyObj::Date(dt.timestamp_millis().div_euclid(MS_IN_DAY) as i32)),
+        "timestamp" => parse_datetime()
+            .and_then(|dt| dt.timestamp_nanos_opt())
+            .map(SpicyObj::Timestamp),
+        "datetime" => parse_datetime().map(|dt| SpicyObj::Datetime(dt.timestamp_millis())),
+        "time" => ["%H:%M:%S%.f", "%H:%M"]
+            .iter()
+            .find_map(|f| NaiveTime::parse_from_str(v, f).ok())
+            .map(|t| {
+                SpicyObj::Time(
+                    t.num_seconds_from_midnight() as i64 * 1_000_000_000 + t.nanosecond() as i64,
+                )
+            }),
+        _ => None,
+    };
+    res.ok_or_else(|| {
+        SpicyError::Err(format!(
+            "Failed to cast($) '{}' to type '{}', expect an ISO 8601 string",
+            v, datatype
+        ))
+    })
+}
+
 pub fn cast(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     validate_args(args, &[ArgType::StrOrSym, ArgType::Any])?;
     let arg0 = args[0];
@@ -2715,6 +3450,7 @@ pub fn cast(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
                     match arg1 {
                         SpicyObj::Date(_) => Ok(arg1.clone()),
                         SpicyObj::Datetime(v) => Ok(SpicyObj::Date((v / MS_IN_DAY) as i32)),
+                        SpicyObj::String(s) => parse_temporal_str(datatype, s),
                         SpicyObj::Timestamp(v) => Ok(SpicyObj::Date((v / NS_IN_DAY) as i32)),
                         SpicyObj::Series(s1) => {
                             Ok(SpicyObj::Series(s1.cast(&DataType::Date).map_err(|e| {
@@ -2737,6 +3473,7 @@ pub fn cast(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
                 } else {
                     match arg1 {
                         SpicyObj::Date(v) => Ok(SpicyObj::Timestamp((*v as i64) * NS_IN_DAY)),
+                        SpicyObj::String(s) => parse_temporal_str(datatype, s),
                         SpicyObj::Datetime(v) => Ok(SpicyObj::Timestamp(v * NS_IN_MS)),
                         SpicyObj::Timestamp(_) => Ok(arg1.clone()),
                         SpicyObj::Series(s1) => Ok(SpicyObj::Series(
@@ -2760,6 +3497,7 @@ pub fn cast(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
                 } else {
                     match arg1 {
                         SpicyObj::Date(v) => Ok(SpicyObj::Datetime((*v as i64) * MS_IN_DAY)),
+                        SpicyObj::String(s) => parse_temporal_str(datatype, s),
                         SpicyObj::Datetime(_) => Ok(arg1.clone()),
                         SpicyObj::Timestamp(v) => Ok(SpicyObj::Datetime(v / NS_IN_MS)),
                         SpicyObj::Series(s1) => Ok(SpicyObj::Series(
@@ -2784,6 +3522,7 @@ pub fn cast(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
                     match arg1 {
                         SpicyObj::Time(_) => Ok(arg1.clone()),
                         SpicyObj::Duration(v) => Ok(SpicyObj::Time(*v % NS_IN_DAY)),
+                        SpicyObj::String(s) => parse_temporal_str(datatype, s),
                         SpicyObj::Series(s1) => {
                             Ok(SpicyObj::Series(s1.cast(&DataType::Time).map_err(|e| {
                                 SpicyError::Err(format!(
@@ -2871,6 +3610,160 @@ pub fn cast(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
     }
 }
 
+// type_name, series
+pub fn reinterpret(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::StrOrSym, ArgType::Series])?;
+    let data_type = map_str_to_polars_dtype(args[0].str().unwrap())?;
+    let s = args[1].series().unwrap();
+    let physical = s.dtype().to_physical();
+    // only the logical dtype changes, the underlying integers are kept as is
+    if !physical.is_primitive_numeric() || physical != data_type.to_physical() {
+        return Err(SpicyError::EvalErr(format!(
+            "Cannot reinterpret '{}' as '{}', mismatched physical dtype",
+            s.dtype(),
+            data_type
+        )));
+    }
+    s.to_physical_repr()
+        .cast(&data_type)
+        .map_err(|e| SpicyError::Err(e.to_string()))
+        .map(SpicyObj::Series)
+}
+
+// nanoseconds in one `s`ms`us`ns
+pub(crate) fn ns_per_unit(unit: &str) -> SpicyResult<i64> {
+    match unit {
+        "s" => Ok(1_000_000_000),
+        "ms" => Ok(1_000_000),
+        "us" => Ok(1_000),
+        "ns" => Ok(1),
+        _ => Err(SpicyError::EvalErr(format!(
+            "Expect unit one of `s`ms`us`ns, got '{}'",
+            unit
+        ))),
+    }
+}
+
+// unit, epoch, args
+pub fn cast_timestamp(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Sym, ArgType::Sym, ArgType::NumericLike])?;
+    let unit = args[0].str().unwrap();
+    let epoch = args[1].str().unwrap();
+    let arg2 = args[2];
+    let factor = ns_per_unit(unit)?;
+    // chili timestamps share the unix epoch
+    let offset = match epoch {
+        "unix" | "chili" => 0,
+        "q" => NS_DIFF,
+        _ => {
+            return Err(SpicyError::EvalErr(format!(
+                "Expect epoch one of `unix`chili`q, got '{}'",
+                epoch
+            )));
+        }
+    };
+    let cast_err =
+        |e: PolarsError| SpicyError::Err(format!("Failed to cast series to 'timestamp', {}", e));
+    let overflow = || {
+        SpicyError::Err(format!(
+            "Timestamp out of range for unit '{}' and epoch '{}'",
+            unit, epoch
+        ))
+    };
+    let to_ns = |v: i64| {
+        v.checked_mul(factor)
+            .and_then(|v| v.checked_add(offset))
+            .ok_or_else(overflow)
+    };
+    match arg2 {
+        SpicyObj::Series(s) if s.dtype().is_float() => {
+            let s = s.cast(&DataType::Float64).map_err(cast_err)?;
+            let s = s.f64().unwrap() * factor as f64 + offset as f64;
+            Ok(SpicyObj::Series(
+                s.into_series()
+                    .cast(&DataType::Int64)
+                    .and_then(|s| s.cast(&DataType::Datetime(ns, None)))
+                    .map_err(cast_err)?,
+            ))
+        }
+        SpicyObj::Series(s) => {
+            let s = s.cast(&DataType::Int64).map_err(cast_err)?;
+            let ca = s.i64().unwrap();
+            // scaling is monotonic, in range bounds keep every value in range
+            if let (Some(min), Some(max)) = (ca.min(), ca.max()) {
+                to_ns(min)?;
+                to_ns(max)?;
+            }
+            let s = ca * factor + offset;
+            Ok(SpicyObj::Series(
+                s.into_series()
+                    .cast(&DataType::Datetime(ns, None))
+                    .map_err(cast_err)?,
+            ))
+        }
+        _ if arg2.is_float() => {
+            let scaled = arg2.to_f64().unwrap() * factor as f64;
+            // also rejects NaN
+            if !(scaled >= i64::MIN as f64 && scaled < i64::MAX as f64) {
+                return Err(overflow());
+            }
+            Ok(SpicyObj::Timestamp(
+                (scaled as i64).checked_add(offset).ok_or_else(overflow)?,
+            ))
+        }
+        _ => Ok(SpicyObj::Timestamp(to_ns(arg2.to_i64().unwrap())?)),
+    }
+}
+
+// syms, sym(s) | str(s)
+// categorical whose codes are the positions in `domain`, independent of the global categories;
+// values outside of the domain are null
+pub fn cast_cat(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Syms, ArgType::Series])?;
+    let domain = args[0].to_str_vec().unwrap();
+    let s1 = args[1].series().unwrap();
+    let mut seen = HashSet::with_capacity(domain.len());
+    if let Some(d) = domain.iter().find(|d| !seen.insert(**d)) {
+        return Err(SpicyError::Err(format!("Duplicate '{}' in domain", d)));
+    }
+    if !matches!(s1.dtype(), DataType::String | DataType::Categorical(_, _)) {
+        return Err(SpicyError::UnsupportedBinaryOpErr(
+            "cast_cat".to_owned(),
+            args[0].get_type_name(),
+            args[1].get_type_name(),
+        ));
+    }
+    let cast_err =
+        |e: PolarsError| SpicyError::Err(format!("Failed to cast series to domain, {}", e));
+    // categories are shared by name, the same domain always resolves to the same ones, and the
+    // domain is inserted before any value so that its codes follow the domain order
+    let mut hasher = DefaultHasher::new();
+    domain.hash(&mut hasher);
+    let cats = Categories::new(
+        format!("domain_{:x}", hasher.finish()).into(),
+        PlSmallStr::EMPTY,
+        CategoricalPhysical::U32,
+    );
+    let dtype = DataType::Categorical(cats.clone(), cats.mapping());
+    Series::new("".into(), domain.as_slice())
+        .cast(&dtype)
+        .map_err(cast_err)?;
+    let s1 = s1.cast(&DataType::String).map_err(cast_err)?;
+    let values: StringChunked = s1
+        .str()
+        .unwrap()
+        .iter()
+        .map(|v| v.filter(|v| seen.contains(v)))
+        .collect();
+    Ok(SpicyObj::Series(
+        values
+            .into_series()
+            .with_name(s1.name().clone())
+            .cast(&dtype)
+            .map_err(cast_err)?,
+    ))
+}
+
 // arg0 int: rand, deal
 // arg1 null: permute
 // arg0 series, mixedList, dict
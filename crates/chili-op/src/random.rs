@@ -1,7 +1,12 @@
 use std::sync::Mutex;
 
+use chili_core::{ArgType, SpicyError, SpicyObj, SpicyResult, validate_args};
+use indexmap::IndexMap;
+use polars::prelude::{IdxSize, NamedFrom, Series};
+use rand::RngExt;
 use rand::prelude::*;
 use std::sync::LazyLock;
+use std::time::SystemTime;
 
 static GLOBAL_RNG_STATE: LazyLock<Mutex<SmallRng>> =
     LazyLock::new(|| Mutex::new(SmallRng::seed_from_u64(0)));
@@ -13,3 +18,150 @@ pub(crate) fn get_global_random_u64() -> u64 {
 pub fn set_global_random_seed(seed: u64) {
     *GLOBAL_RNG_STATE.lock().unwrap() = SmallRng::seed_from_u64(seed);
 }
+
+// k, a reservoir is a dict of k, seen and sample, fed with reservoir_feed
+pub fn reservoir(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Int])?;
+    let k = args[0].to_i64().unwrap();
+    if k < 0 {
+        return Err(SpicyError::Err(format!(
+            "Requires a non-negative int for 'reservoir', got '{}'",
+            k
+        )));
+    }
+    Ok(SpicyObj::Dict(IndexMap::from([
+        ("k".to_owned(), SpicyObj::I64(k)),
+        ("seen".to_owned(), SpicyObj::I64(0)),
+        ("sample".to_owned(), SpicyObj::Null),
+    ])))
+}
+
+// reservoir, batch - algorithm R, every value seen so far is sampled with equal probability
+pub fn reservoir_feed(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Dict, ArgType::Series])?;
+    let (k, seen, sample) = reservoir_parts(args[0])?;
+    let batch = args[1].series().unwrap();
+    let mut all = match sample {
+        SpicyObj::Series(s) => s.clone(),
+        _ => Series::new_empty(batch.name().clone(), batch.dtype()),
+    };
+    let sample_len = all.len();
+    all.append(batch)
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+
+    let mut rng = SmallRng::seed_from_u64(get_global_random_u64());
+    let mut slots: Vec<IdxSize> = (0..sample_len as IdxSize).collect();
+    for i in 0..batch.len() {
+        let pos = sample_len + i;
+        let count = seen as usize + i;
+        if slots.len() < k as usize {
+            slots.push(pos as IdxSize);
+        } else if k > 0 {
+            let j = rng.random_range(0..=count);
+            if j < k as usize {
+                slots[j] = pos as IdxSize;
+            }
+        }
+    }
+    let sample = all
+        .take_slice(&slots)
+        .map_err(|e| SpicyError::Err(e.to_string()))?;
+    Ok(SpicyObj::Dict(IndexMap::from([
+        ("k".to_owned(), SpicyObj::I64(k)),
+        ("seen".to_owned(), SpicyObj::I64(seen + batch.len() as i64)),
+        ("sample".to_owned(), SpicyObj::Series(sample)),
+    ])))
+}
+
+// reservoir
+pub fn reservoir_sample(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Dict])?;
+    let (_, _, sample) = reservoir_parts(args[0])?;
+    match sample {
+        SpicyObj::Series(_) => Ok(sample.clone()),
+        _ => Ok(SpicyObj::MixedList(vec![])),
+    }
+}
+
+fn reservoir_parts(reservoir: &SpicyObj) -> SpicyResult<(i64, i64, &SpicyObj)> {
+    let d = reservoir.dict().unwrap();
+    match (d.get("k"), d.get("seen"), d.get("sample")) {
+        (Some(SpicyObj::I64(k)), Some(SpicyObj::I64(seen)), Some(sample)) => {
+            Ok((*k, *seen, sample))
+        }
+        _ => Err(SpicyError::Err(
+            "Requires a reservoir dict with 'k', 'seen' and 'sample'".to_owned(),
+        )),
+    }
+}
+
+fn format_uuid(v: u128) -> String {
+    let h = format!("{:032x}", v);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &h[0..8],
+        &h[8..12],
+        &h[12..16],
+        &h[16..20],
+        &h[20..32]
+    )
+}
+
+// `uuid` or `ordered`, n
+// uuid is a v4 uuid from the seeded rng, ordered is a v7 uuid of the current ms and a
+// per-batch counter, so ids sort in generation order
+pub fn genid(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Sym, ArgType::Int])?;
+    let mode = args[0].str().unwrap();
+    let n = args[1].to_i64().unwrap();
+    if n < 0 {
+        return Err(SpicyError::Err(format!(
+            "Requires a non-negative int for 'genid', got '{}'",
+            n
+        )));
+    }
+    let mut rng = SmallRng::seed_from_u64(get_global_random_u64());
+    let ids: Vec<String> = match mode {
+        "uuid" => (0..n)
+            .map(|_| {
+                let v = ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128;
+                // version 4, variant 10
+                let v = (v & !(0xf << 76)) | (0x4 << 76);
+                let v = (v & !(0x3 << 62)) | (0x2 << 62);
+                format_uuid(v)
+            })
+            .collect(),
+        "ordered" => {
+            if n >= 1 << 42 {
+                return Err(SpicyError::Err(format!(
+                    "Requires less than 2^42 ids for ordered 'genid', got '{}'",
+                    n
+                )));
+            }
+            let ms = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+                & 0xffff_ffff_ffff;
+            (0..n as u128)
+                .map(|i| {
+                    // 48 bits ms, version 7, 12 + 30 bits counter, variant 10, 32 random bits
+                    let v = (ms << 80)
+                        | (0x7 << 76)
+                        | ((i >> 30) << 64)
+                        | (0x2 << 62)
+                        | ((i & 0x3fff_ffff) << 32)
+                        | rng.next_u32() as u128;
+                    format_uuid(v)
+                })
+                .collect()
+        }
+        _ => {
+            return Err(SpicyError::Err(format!(
+                "Unknown 'genid' mode '{}', expect one of uuid, ordered",
+                mode
+            )));
+        }
+    };
+    Ok(SpicyObj::Series(Series::new("id".into(), ids)))
+}
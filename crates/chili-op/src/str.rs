@@ -282,3 +282,23 @@ pub fn pad(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
         _ => unreachable!(),
     }
 }
+
+// separator, strings, nulls are skipped; an expr aggregates per group in a select by
+pub fn str_join_agg(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    if args[1].is_expr() {
+        let sep = args[0].str()?;
+        let s = args[1].as_expr()?.cast(DataType::String);
+        return Ok(SpicyObj::Expr(s.str().join(sep, true)));
+    }
+    validate_args(args, &[ArgType::Str, ArgType::StrLike])?;
+    let sep = args[0].str().unwrap();
+    match args[1] {
+        SpicyObj::String(s) | SpicyObj::Symbol(s) => Ok(SpicyObj::String(s.clone())),
+        SpicyObj::Series(s) => {
+            let s = s.cast(&DataType::String).unwrap();
+            let strs: Vec<&str> = s.str().unwrap().iter().flatten().collect();
+            Ok(SpicyObj::String(strs.join(sep)))
+        }
+        _ => unreachable!(),
+    }
+}
@@ -1,16 +1,21 @@
 use chili_core::{
-    ArgType, SpicyError, SpicyObj, SpicyResult, constant::UNIX_EPOCH_DAY, validate_args,
+    ArgType, SpicyError, SpicyObj, SpicyResult,
+    constant::{NS_DIFF, UNIX_EPOCH_DAY},
+    validate_args,
 };
 use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone, Utc};
 use chrono_tz::Tz;
 use polars::{
     error::{PolarsResult, polars_err},
     prelude::{
-        ChunkApply, ChunkedArray, Column, DataType, DatetimeChunked, DatetimeType, Int64Type,
-        IntoColumn, Logical, StringChunked, TimeUnit, lit, time_zone::parse_time_zone,
+        ChunkAgg, ChunkApply, ChunkedArray, Column, DataType, DatetimeChunked, DatetimeType,
+        Int64Type, IntoColumn, IntoSeries, Logical, StringChunked, TimeUnit, lit,
+        time_zone::parse_time_zone,
     },
 };
 
+use crate::operator::{NS_IN_DAY, NS_IN_MS, cast_timestamp, ns_per_unit};
+
 fn parse_tz(timezone_str: &str) -> SpicyResult<Tz> {
     match timezone_str.parse::<Tz>() {
         Ok(tz) => Ok(tz),
@@ -185,6 +190,61 @@ fn convert_tz_column(dt: Column, from_tz: Column, to_tz: Column) -> PolarsResult
     elementwise_convert_tz(ca, from_tz, to_tz).map(|out| out.into_column())
 }
 
+// unit, temporal(s) | int(s)
+// temporals count `unit`s from 1970.01.01, ints count `unit`s from the 2000.01.01 chili/q epoch
+// and are shifted by NS_DIFF; rounds down so times before 1970 stay before it
+pub fn to_unix(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    validate_args(args, &[ArgType::Sym, ArgType::Any])?;
+    let unit = args[0].str().unwrap();
+    let factor = ns_per_unit(unit)?;
+    let diff = NS_DIFF / factor;
+    let arg1 = args[1];
+    let overflow = || SpicyError::Err(format!("Unix time out of range for unit '{}'", unit));
+    let ns = match arg1 {
+        SpicyObj::Date(v) => (*v as i64).checked_mul(NS_IN_DAY).ok_or_else(overflow)?,
+        SpicyObj::Datetime(v) => v.checked_mul(NS_IN_MS).ok_or_else(overflow)?,
+        SpicyObj::Timestamp(v) => *v,
+        _ if arg1.is_integer() => {
+            return arg1
+                .to_i64()
+                .unwrap()
+                .checked_add(diff)
+                .map(SpicyObj::I64)
+                .ok_or_else(overflow);
+        }
+        SpicyObj::Series(s) if matches!(s.dtype(), DataType::Date | DataType::Datetime(_, _)) => {
+            let s = s
+                .cast(&DataType::Datetime(TimeUnit::Nanoseconds, None))
+                .and_then(|s| s.cast(&DataType::Int64))
+                .map_err(|e| SpicyError::Err(e.to_string()))?;
+            let s = s.i64().unwrap().apply_values(|v| v.div_euclid(factor));
+            return Ok(SpicyObj::Series(s.into_series()));
+        }
+        SpicyObj::Series(s) if s.dtype().is_integer() => {
+            let s = s
+                .cast(&DataType::Int64)
+                .map_err(|e| SpicyError::Err(e.to_string()))?;
+            let ca = s.i64().unwrap();
+            if ca.max().is_some_and(|v| v.checked_add(diff).is_none()) {
+                return Err(overflow());
+            }
+            return Ok(SpicyObj::Series((ca + diff).into_series()));
+        }
+        _ => {
+            return Err(SpicyError::EvalErr(format!(
+                "Expect date, datetime, timestamp or int, got '{}'",
+                arg1.get_type_name()
+            )));
+        }
+    };
+    Ok(SpicyObj::I64(ns.div_euclid(factor)))
+}
+
+// unit, int(s)
+pub fn from_unix(args: &[&SpicyObj]) -> SpicyResult<SpicyObj> {
+    cast_timestamp(&[args[0], &SpicyObj::Symbol("unix".to_owned()), args[1]])
+}
+
 pub fn elementwise_convert_tz(
     datetime: &Logical<DatetimeType, Int64Type>,
     from_tz: &StringChunked,
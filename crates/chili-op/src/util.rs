@@ -5,6 +5,7 @@ use polars::datatypes::{DataType, TimeUnit};
 use polars::frame::DataFrame;
 use polars::io::parquet::write::{ParquetCompression, ParquetWriter};
 use polars::series::Series;
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
 use chili_core::{FuncType, SpicyError, SpicyObj, SpicyResult};
 
@@ -58,7 +59,20 @@ pub(crate) fn list_op_dict(
     Ok(SpicyObj::Dict(res))
 }
 
+/// Lists longer than this apply the op to their items in parallel.
+const PAR_LIST_LEN: usize = 10_000;
+
+// keeps item order, the error of the first failing item wins as in the serial path
+fn collect_list(res: Vec<SpicyResult<SpicyObj>>) -> SpicyResult<SpicyObj> {
+    Ok(SpicyObj::MixedList(
+        res.into_iter().collect::<SpicyResult<Vec<SpicyObj>>>()?,
+    ))
+}
+
 pub(crate) fn list_op_atom(l: &[SpicyObj], atom: &SpicyObj, op: FuncType) -> SpicyResult<SpicyObj> {
+    if l.len() > PAR_LIST_LEN {
+        return collect_list(l.par_iter().map(|args| op(&[args, atom])).collect());
+    }
     Ok(SpicyObj::MixedList(
         l.iter()
             .map(|args| op(&[args, atom]))
@@ -67,6 +81,9 @@ pub(crate) fn list_op_atom(l: &[SpicyObj], atom: &SpicyObj, op: FuncType) -> Spi
 }
 
 pub(crate) fn atom_op_list(atom: &SpicyObj, l: &[SpicyObj], op: FuncType) -> SpicyResult<SpicyObj> {
+    if l.len() > PAR_LIST_LEN {
+        return collect_list(l.par_iter().map(|args| op(&[atom, args])).collect());
+    }
     Ok(SpicyObj::MixedList(
         l.iter()
             .map(|args| op(&[atom, args]))
@@ -79,6 +96,14 @@ pub(crate) fn list_op_list(
     l1: &[SpicyObj],
     op: FuncType,
 ) -> SpicyResult<SpicyObj> {
+    if l0.len() > PAR_LIST_LEN {
+        return collect_list(
+            l0.par_iter()
+                .zip(l1.par_iter())
+                .map(|(a0, a1)| op(&[a0, a1]))
+                .collect(),
+        );
+    }
     Ok(SpicyObj::MixedList(
         l0.iter()
             .zip(l1.iter())
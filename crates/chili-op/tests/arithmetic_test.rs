@@ -6,7 +6,7 @@
 // not a clean rounded number.
 #![allow(clippy::excessive_precision)]
 
-use chili_core::SpicyObj;
+use chili_core::{SpicyObj, SpicyResult};
 use chili_op::operator;
 use indexmap::IndexMap;
 use polars::{
@@ -861,6 +861,227 @@ fn div() {
     }
 }
 
+#[test]
+fn modulo() {
+    let nu = SpicyObj::Null;
+    let b = SpicyObj::Boolean(true);
+    let zero = SpicyObj::I64(0);
+    let h = SpicyObj::I16(-5);
+    let i = SpicyObj::I32(7);
+    let j = SpicyObj::I64(-11);
+    let f = SpicyObj::F64(3.5);
+    // 2024.04.30
+    let d = SpicyObj::Date(19843);
+
+    let sj = SpicyObj::Series(Series::new(
+        "".into(),
+        vec![Some(-37i64), Some(41i64), None, Some(5i64)],
+    ));
+    let sk = SpicyObj::Series(Series::new(
+        "".into(),
+        vec![Some(6i64), Some(0i64), Some(3i64), Some(-3i64)],
+    ));
+    let l = SpicyObj::MixedList(vec![i.clone(), j.clone()]);
+    let mut m = IndexMap::new();
+    m.insert("i".to_string(), i.clone());
+    m.insert("j".to_string(), j.clone());
+    let m = SpicyObj::Dict(m);
+    for (args, expect) in vec![
+        (vec![&nu, &i], SpicyObj::Null),
+        (vec![&i, &zero], SpicyObj::Null),
+        (vec![&f, &zero], SpicyObj::Null),
+        (vec![&b, &i], SpicyObj::I32(1)),
+        (vec![&i, &h], SpicyObj::I32(-3)),
+        (vec![&j, &i], SpicyObj::I64(3)),
+        (vec![&j, &f], SpicyObj::F64(3.0)),
+        (
+            vec![&sj, &i],
+            SpicyObj::Series(Series::new(
+                "".into(),
+                vec![Some(5i64), Some(6i64), None, Some(5i64)],
+            )),
+        ),
+        (
+            vec![&sj, &sk],
+            SpicyObj::Series(Series::new(
+                "".into(),
+                vec![Some(5i64), None, None, Some(-1i64)],
+            )),
+        ),
+        (
+            vec![&sj, &zero],
+            SpicyObj::Series(Series::new("".into(), vec![None::<i64>, None, None, None])),
+        ),
+        (
+            vec![&l, &i],
+            SpicyObj::MixedList(vec![SpicyObj::I32(0), SpicyObj::I64(3)]),
+        ),
+        (
+            vec![&m, &h],
+            SpicyObj::Dict(IndexMap::from([
+                ("i".to_string(), SpicyObj::I32(-3)),
+                ("j".to_string(), SpicyObj::I64(-1)),
+            ])),
+        ),
+    ]
+    .iter()
+    {
+        match operator::modulo(args) {
+            Ok(r) => assert_eq!(r, *expect, "test case - {:?}", args),
+            Err(e) => panic!("{} - {:?}", e, args),
+        }
+    }
+
+    for args in vec![vec![&d, &i], vec![&i, &d]].iter() {
+        assert!(operator::modulo(args).is_err(), "error case - {:?}", args)
+    }
+}
+
+#[test]
+fn i128_promotion() {
+    // i128 only exists as a series, an i64 operand widens to it instead of wrapping
+    let j = SpicyObj::I64(i64::MAX);
+    let sj = SpicyObj::Series(Series::new("".into(), vec![i64::MAX, i64::MIN]));
+    let sx = SpicyObj::Series(Series::new("".into(), vec![2i128, 2i128]));
+    let max = i64::MAX as i128;
+    let min = i64::MIN as i128;
+    for (op, args, expect) in vec![
+        (
+            operator::mul as fn(&[&SpicyObj]) -> SpicyResult<SpicyObj>,
+            vec![&j, &sx],
+            vec![max * 2, max * 2],
+        ),
+        (operator::mul, vec![&sx, &sj], vec![max * 2, min * 2]),
+        (operator::add, vec![&sj, &sx], vec![max + 2, min + 2]),
+        (operator::minus, vec![&sx, &sj], vec![2 - max, 2 - min]),
+    ] {
+        let expect = SpicyObj::Series(Series::new("".into(), expect));
+        assert_eq!(op(&args).unwrap(), expect, "{:?}", args)
+    }
+}
+
+#[test]
+fn bitwise() {
+    let nu = SpicyObj::Null;
+    let b = SpicyObj::Boolean(true);
+    let i = SpicyObj::I32(10);
+    let j = SpicyObj::I64(12);
+    let f = SpicyObj::F64(3.5);
+    // 2024.04.30
+    let d = SpicyObj::Date(19843);
+
+    let si = SpicyObj::Series(Series::new("".into(), vec![Some(1i32), None, Some(6i32)]));
+    let sj = SpicyObj::Series(Series::new(
+        "".into(),
+        vec![Some(3i64), Some(5i64), Some(6i64)],
+    ));
+    let sf = SpicyObj::Series(Series::new("".into(), vec![1.0f64, 2.0, 3.0]));
+    let l = SpicyObj::MixedList(vec![i.clone(), j.clone()]);
+    for (op, args, expect) in vec![
+        (
+            operator::bit_and as fn(&[&SpicyObj]) -> SpicyResult<SpicyObj>,
+            vec![&j, &i],
+            SpicyObj::I64(8),
+        ),
+        (operator::bit_or, vec![&j, &i], SpicyObj::I64(14)),
+        (operator::bit_xor, vec![&j, &i], SpicyObj::I64(6)),
+        (operator::bit_xor, vec![&nu, &i], SpicyObj::Null),
+        (
+            operator::bit_xor,
+            vec![&si, &j],
+            SpicyObj::Series(Series::new("".into(), vec![Some(13i32), None, Some(10i32)])),
+        ),
+        (
+            operator::bit_and,
+            vec![&i, &sj],
+            SpicyObj::Series(Series::new("".into(), vec![2i64, 0, 2])),
+        ),
+        (
+            operator::bit_or,
+            vec![&si, &sj],
+            SpicyObj::Series(Series::new("".into(), vec![Some(3i64), None, Some(6i64)])),
+        ),
+        (
+            operator::bit_xor,
+            vec![&l, &j],
+            SpicyObj::MixedList(vec![SpicyObj::I64(6), SpicyObj::I64(0)]),
+        ),
+    ]
+    .iter()
+    {
+        match op(args) {
+            Ok(r) => assert_eq!(r, *expect, "test case - {:?}", args),
+            Err(e) => panic!("{} - {:?}", e, args),
+        }
+    }
+
+    for args in vec![
+        vec![&f, &i],
+        vec![&j, &d],
+        vec![&b, &i],
+        vec![&sf, &j],
+        vec![&si, &f],
+    ]
+    .iter()
+    {
+        assert!(operator::bit_and(args).is_err(), "error case - {:?}", args)
+    }
+}
+
+#[test]
+fn logical_xor() {
+    let nu = SpicyObj::Null;
+    let t = SpicyObj::Boolean(true);
+    let b = SpicyObj::Boolean(false);
+    let j = SpicyObj::I64(12);
+    let f = SpicyObj::F64(0.0);
+    let sym = SpicyObj::Symbol("a".to_owned());
+
+    let sb0 = SpicyObj::Series(Series::new("".into(), vec![true, true, false, false]));
+    let sb1 = SpicyObj::Series(Series::new("".into(), vec![true, false, true, false]));
+    let sj = SpicyObj::Series(Series::new("".into(), vec![0i64, 3, 0, -1]));
+    let ss = SpicyObj::Series(Series::new("".into(), vec!["a", "b", "c", "d"]));
+    let l = SpicyObj::MixedList(vec![t.clone(), b.clone()]);
+    for (args, expect) in vec![
+        (vec![&t, &b], SpicyObj::Boolean(true)),
+        (vec![&t, &t], SpicyObj::Boolean(false)),
+        (vec![&j, &f], SpicyObj::Boolean(true)),
+        (vec![&nu, &t], SpicyObj::Null),
+        (
+            vec![&sb0, &sb1],
+            SpicyObj::Series(Series::new("".into(), vec![false, true, true, false])),
+        ),
+        (
+            vec![&sb0, &t],
+            SpicyObj::Series(Series::new("".into(), vec![false, false, true, true])),
+        ),
+        (
+            vec![&b, &sj],
+            SpicyObj::Series(Series::new("".into(), vec![false, true, false, true])),
+        ),
+        (
+            vec![&l, &l],
+            SpicyObj::MixedList(vec![SpicyObj::Boolean(false), SpicyObj::Boolean(false)]),
+        ),
+    ]
+    .iter()
+    {
+        match operator::logical_xor(args) {
+            Ok(r) => assert_eq!(r, *expect, "test case - {:?}", args),
+            Err(e) => panic!("{} - {:?}", e, args),
+        }
+    }
+
+    let short = SpicyObj::Series(Series::new("".into(), vec![true]));
+    for args in vec![vec![&sym, &t], vec![&ss, &t], vec![&sb0, &short]].iter() {
+        assert!(
+            operator::logical_xor(args).is_err(),
+            "error case - {:?}",
+            args
+        )
+    }
+}
+
 #[test]
 fn gt() {
     let nu = SpicyObj::Null;
@@ -1042,3 +1263,111 @@ fn gt() {
         assert!(operator::gt(args).is_err(), "error case - {:?}", args)
     }
 }
+
+#[test]
+fn compare_infinity() {
+    let nu = SpicyObj::Null;
+    let j = SpicyObj::I64(i64::MAX - 1);
+    let f = SpicyObj::F64(3.13);
+    let inf = SpicyObj::F64(f64::INFINITY);
+    let ninf = SpicyObj::F64(f64::NEG_INFINITY);
+    let einf = SpicyObj::F32(f32::INFINITY);
+
+    // 0Wd, -0Wd, 0Wz and 0Wp
+    let wd = SpicyObj::Date(i32::MAX);
+    let nwd = SpicyObj::Date(-i32::MAX);
+    let wz = SpicyObj::Datetime(i64::MAX);
+    let wp = SpicyObj::Timestamp(i64::MAX);
+    let nwp = SpicyObj::Timestamp(-i64::MAX);
+    // 2024.04.30 and 2024.04.30D02:32:01.123456789
+    let d = SpicyObj::Date(19843);
+    let p = SpicyObj::Timestamp(1714444321123456789);
+
+    let sf = SpicyObj::Series(Series::new(
+        "".into(),
+        vec![Some(-2.71f64), Some(f64::INFINITY), None],
+    ));
+
+    let t = SpicyObj::Boolean(true);
+    let b = SpicyObj::Boolean(false);
+    for (op, args, expect) in [
+        (
+            operator::gt as fn(&[&SpicyObj]) -> SpicyResult<SpicyObj>,
+            vec![&inf, &f],
+            &t,
+        ),
+        (operator::gt, vec![&inf, &j], &t),
+        (operator::lt, vec![&ninf, &f], &t),
+        (operator::eq, vec![&inf, &einf], &t),
+        (operator::eq, vec![&inf, &ninf], &b),
+        (operator::gt, vec![&inf, &nu], &nu),
+        (operator::eq, vec![&nu, &inf], &nu),
+        // temporal extremes are infinities across units
+        (operator::eq, vec![&wd, &wp], &t),
+        (operator::eq, vec![&wd, &wz], &t),
+        (operator::eq, vec![&wz, &wp], &t),
+        (operator::eq, vec![&nwd, &nwp], &t),
+        (operator::gt, vec![&wd, &p], &t),
+        (operator::lt, vec![&nwd, &p], &t),
+        (operator::lt, vec![&d, &wp], &t),
+        (operator::gt, vec![&p, &nwd], &t),
+    ]
+    .iter()
+    {
+        match op(args) {
+            Ok(obj) => assert_eq!(obj, **expect, "test case - {:?}", args),
+            Err(e) => panic!("{} - {:?}", e, args),
+        }
+    }
+
+    assert_eq!(
+        operator::gt(&[&sf, &f]).unwrap(),
+        SpicyObj::Series(Series::new("".into(), vec![Some(false), Some(true), None]))
+    );
+    // only nulls are filled, infinities are kept
+    assert_eq!(
+        operator::fill(&[&f, &sf]).unwrap(),
+        SpicyObj::Series(Series::new(
+            "".into(),
+            vec![-2.71f64, f64::INFINITY, 3.13f64]
+        ))
+    );
+}
+
+#[test]
+fn large_mixed_list() {
+    let n = 100_000;
+    let l: Vec<SpicyObj> = (0..n)
+        .map(|i| match i % 3 {
+            0 => SpicyObj::I64(i),
+            1 => SpicyObj::F64(i as f64 / 2.0),
+            _ => SpicyObj::I32(i as i32),
+        })
+        .collect();
+    let l0 = SpicyObj::MixedList(l.clone());
+    let l1 = SpicyObj::MixedList(l.iter().rev().cloned().collect());
+    let a = SpicyObj::I64(7);
+    let ops: [fn(&[&SpicyObj]) -> SpicyResult<SpicyObj>; 3] =
+        [operator::add, operator::minus, operator::mul];
+    for op in ops {
+        let serial: Vec<SpicyObj> = l.iter().map(|v| op(&[v, &a]).unwrap()).collect();
+        assert_eq!(op(&[&l0, &a]).unwrap(), SpicyObj::MixedList(serial));
+        let serial: Vec<SpicyObj> = l.iter().map(|v| op(&[&a, v]).unwrap()).collect();
+        assert_eq!(op(&[&a, &l0]).unwrap(), SpicyObj::MixedList(serial));
+        let serial: Vec<SpicyObj> = l
+            .iter()
+            .zip(l.iter().rev())
+            .map(|(v0, v1)| op(&[v0, v1]).unwrap())
+            .collect();
+        assert_eq!(op(&[&l0, &l1]).unwrap(), SpicyObj::MixedList(serial));
+    }
+
+    // the first failing item's error wins
+    let mut bad = l.clone();
+    bad[20_000] = SpicyObj::Symbol("a".to_owned());
+    bad[90_000] = SpicyObj::String("b".to_owned());
+    let bad = SpicyObj::MixedList(bad);
+    let err = operator::mul(&[&bad, &a]).unwrap_err();
+    let serial_err = operator::mul(&[&SpicyObj::Symbol("a".to_owned()), &a]).unwrap_err();
+    assert_eq!(err.to_string(), serial_err.to_string());
+}
@@ -1,6 +1,12 @@
 mod util;
 use crate::util::create_state;
-use chili_core::SpicyObj;
+use chili_core::{
+    SpicyObj,
+    serde6::{MS_DIFF, NS_DIFF},
+};
+use chili_op::BUILT_IN_FN;
+use polars::datatypes::{DataType, TimeUnit};
+use polars::prelude::{NamedFrom, Series};
 
 #[test]
 fn code_test() {
@@ -18,3 +24,1796 @@ fn code_test() {
     assert_eq!(state.get_var("h0").unwrap(), SpicyObj::I64(7));
     assert_eq!(state.get_var("h1").unwrap(), SpicyObj::I64(3));
 }
+
+#[test]
+fn when_series_test() {
+    let state = create_state(true);
+    let code = "
+    r0: when(101b, 1 2 3, 4 5 6);
+    r1: when(101b, 1 2 3, 0);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let values = |id: &str| -> Vec<Option<i64>> {
+        let obj = state.get_var(id).unwrap();
+        obj.series().unwrap().i64().unwrap().iter().collect()
+    };
+    assert_eq!(values("r0"), vec![Some(1), Some(5), Some(3)]);
+    assert_eq!(values("r1"), vec![Some(1), Some(0), Some(3)]);
+
+    for code in ["when(101b, 1 2, 4 5 6)", "when(101b, 1 2 3, 1.0 2.0 3.0)"] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err());
+    }
+}
+
+#[test]
+fn shrink_test() {
+    let state = create_state(true);
+    let code = "
+    t: ([]small: 1000 -1000 3, wide: 0 5000000000 1, tiny: 1.0 2.0 3.0, real: 1.5 2.0 3.0);
+    r: shrink(t);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r = state.get_var("r").unwrap();
+    let r = r.df().unwrap();
+    let dtype = |name: &str| r.column(name).unwrap().dtype().clone();
+    assert_eq!(dtype("small"), DataType::Int16);
+    assert_eq!(dtype("wide"), DataType::Int64);
+    assert_eq!(dtype("tiny"), DataType::Int8);
+    assert_eq!(dtype("real"), DataType::Float64);
+    assert_eq!(
+        r.column("small").unwrap().i16().unwrap().get(1),
+        Some(-1000)
+    );
+}
+
+#[test]
+fn cast_timestamp_test() {
+    let state = create_state(true);
+    let code = "
+    r0: cast_timestamp(`ms, `unix, 1704067200123);
+    r1: cast_timestamp(`s, `unix, 1704067200 1704067201);
+    r2: cast_timestamp(`s, `q, 0);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::Timestamp(1_704_067_200_123_000_000)
+    );
+    let r1 = state.get_var("r1").unwrap();
+    let r1 = r1.series().unwrap();
+    assert_eq!(r1.dtype(), &DataType::Datetime(TimeUnit::Nanoseconds, None));
+    assert_eq!(
+        r1.cast(&DataType::Int64).unwrap().i64().unwrap().get(1),
+        Some(1_704_067_201_000_000_000)
+    );
+    assert_eq!(
+        state.get_var("r2").unwrap(),
+        SpicyObj::Timestamp(946_684_800_000_000_000)
+    );
+
+    for code in [
+        "cast_timestamp(`min, `unix, 1)",
+        "cast_timestamp(`s, `unix, 9223372037)",
+        "cast_timestamp(`ns, `q, 9223372036854775807)",
+        "cast_timestamp(`s, `unix, 1 9223372037)",
+        "cast_timestamp(`s, `unix, 1.0e19)",
+    ] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err(), "{code}");
+    }
+}
+
+#[test]
+fn cast_temporal_str_test() {
+    let state = create_state(true);
+    let code = "
+    r0: `date $ \"2024-03-15\";
+    r1: `timestamp $ \"2024-03-15T09:30:00.123456789\";
+    r2: `datetime $ \"2024-03-15 09:30:00.123\";
+    r3: `time $ \"09:30:00.5\";
+    r4: `timestamp $ \"2024-03-15\";
+    r5: `date $ \"1969-12-31\";
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(state.get_var("r0").unwrap(), SpicyObj::Date(19797));
+    assert_eq!(
+        state.get_var("r1").unwrap(),
+        SpicyObj::Timestamp(1_710_495_000_123_456_789)
+    );
+    assert_eq!(
+        state.get_var("r2").unwrap(),
+        SpicyObj::Datetime(1_710_495_000_123)
+    );
+    assert_eq!(
+        state.get_var("r3").unwrap(),
+        SpicyObj::Time(34_200_500_000_000)
+    );
+    assert_eq!(
+        state.get_var("r4").unwrap(),
+        SpicyObj::Timestamp(1_710_460_800_000_000_000)
+    );
+    assert_eq!(state.get_var("r5").unwrap(), SpicyObj::Date(-1));
+
+    for code in [
+        "`date $ \"2024-13-01\"",
+        "`timestamp $ \"15/03/2024\"",
+        "`time $ \"25:00\"",
+    ] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err(), "{code}");
+    }
+}
+
+#[test]
+fn cast_cat_test() {
+    let state = create_state(true);
+    state
+        .set_var(
+            "s",
+            SpicyObj::Series(Series::new("s".into(), [Some("b"), None, Some("c")])),
+        )
+        .unwrap();
+    let code = "
+    r0: cast_cat(`c`a`b, `x`a`b`c`a);
+    r1: cast_cat(`c`a`b, s);
+    r2: cast_cat(`c`a`b, `a`b`x`c`a);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let codes = |id: &str| -> Vec<Option<u32>> {
+        let r = state.get_var(id).unwrap();
+        r.series()
+            .unwrap()
+            .cat32()
+            .unwrap()
+            .physical()
+            .iter()
+            .collect()
+    };
+    assert_eq!(codes("r0"), vec![None, Some(1), Some(2), Some(0), Some(1)]);
+    assert_eq!(codes("r1"), vec![Some(2), None, Some(0)]);
+    assert_eq!(codes("r2"), vec![Some(1), Some(2), None, Some(0), Some(1)]);
+    assert_eq!(
+        state.get_var("r2").unwrap().to_str_vec().unwrap(),
+        vec!["a", "b", "", "c", "a"]
+    );
+
+    for code in ["cast_cat(`a`a, `a`b)", "cast_cat(`a`b, 1 2)"] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err(), "{code}");
+    }
+}
+
+#[test]
+fn unix_epoch_test() {
+    let state = create_state(true);
+    let code = "
+    r0: to_unix(`s, 2024.01.01);
+    r1: to_unix(`ns, 2000.01.01D00:00:00);
+    r2: to_unix(`ms, 2000.01.01 2000.01.02);
+    r3: from_unix(`s, 1704067200);
+    r4: to_unix(`s, 1969.12.31D23:59:59.500000000);
+    r5: to_unix(`s, 1969.12.31D23:59:59.500000000 1970.01.01D00:00:00.500000000);
+    r6: to_unix(`s, 0);
+    r7: to_unix(`ms, 0 -1);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(state.get_var("r0").unwrap(), SpicyObj::I64(1_704_067_200));
+    assert_eq!(state.get_var("r1").unwrap(), SpicyObj::I64(NS_DIFF));
+    let r2 = state.get_var("r2").unwrap();
+    let r2: Vec<Option<i64>> = r2.series().unwrap().i64().unwrap().iter().collect();
+    assert_eq!(r2, vec![Some(MS_DIFF), Some(MS_DIFF + 86_400_000)]);
+    assert_eq!(
+        state.get_var("r3").unwrap(),
+        SpicyObj::Timestamp(1_704_067_200 * 1_000_000_000)
+    );
+    assert_eq!(state.get_var("r4").unwrap(), SpicyObj::I64(-1));
+    let r5 = state.get_var("r5").unwrap();
+    let r5: Vec<Option<i64>> = r5.series().unwrap().i64().unwrap().iter().collect();
+    assert_eq!(r5, vec![Some(-1), Some(0)]);
+    assert_eq!(
+        state.get_var("r6").unwrap(),
+        SpicyObj::I64(NS_DIFF / 1_000_000_000)
+    );
+    let r7 = state.get_var("r7").unwrap();
+    let r7: Vec<Option<i64>> = r7.series().unwrap().i64().unwrap().iter().collect();
+    assert_eq!(r7, vec![Some(MS_DIFF), Some(MS_DIFF - 1)]);
+
+    let code = "to_unix(`ns, 9223372036854775807)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn profile_test() {
+    let state = create_state(true);
+    let name = Series::new("name".into(), ["y", "x", "z"]);
+    state.set_var("name", SpicyObj::Series(name)).unwrap();
+    let code = "
+    t: ([]sym: `a`b`a, qty: 1 0n 3, price: 1.5 2.5 3.5, date: 2024.01.02 2024.01.01 2024.01.03, name);
+    r: profile(t);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r = state.get_var("r").unwrap();
+    let r = r.df().unwrap();
+    assert_eq!(
+        r.get_column_names_str(),
+        vec![
+            "column",
+            "datatype",
+            "null_count",
+            "distinct_count",
+            "min",
+            "max",
+            "mean"
+        ]
+    );
+    assert_eq!(r.height(), 5);
+    let i64s = |name: &str| -> Vec<Option<i64>> {
+        r.column(name).unwrap().i64().unwrap().iter().collect()
+    };
+    let f64s = |name: &str| -> Vec<Option<f64>> {
+        r.column(name).unwrap().f64().unwrap().iter().collect()
+    };
+    let strs = |name: &str| -> Vec<Option<&str>> {
+        r.column(name).unwrap().str().unwrap().iter().collect()
+    };
+    assert_eq!(
+        i64s("null_count"),
+        vec![Some(0), Some(1), Some(0), Some(0), Some(0)]
+    );
+    assert_eq!(
+        i64s("distinct_count"),
+        vec![Some(2), Some(2), Some(3), Some(3), Some(3)]
+    );
+    assert_eq!(
+        strs("min"),
+        vec![
+            Some("a"),
+            Some("1"),
+            Some("1.5"),
+            Some("2024-01-01"),
+            Some("x")
+        ]
+    );
+    assert_eq!(
+        strs("max"),
+        vec![
+            Some("b"),
+            Some("3"),
+            Some("3.5"),
+            Some("2024-01-03"),
+            Some("z")
+        ]
+    );
+    assert_eq!(f64s("mean"), vec![None, Some(2.0), Some(2.5), None, None]);
+}
+
+#[test]
+fn reinterpret_test() {
+    let state = create_state(true);
+    let code = "
+    r0: reinterpret(`i64, 0D00:00:01 1D00:00:00);
+    r1: reinterpret(`duration, 1 2 3);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let r0: Vec<Option<i64>> = r0.series().unwrap().i64().unwrap().iter().collect();
+    assert_eq!(r0, vec![Some(1_000_000_000), Some(86_400_000_000_000)]);
+    let r1 = state.get_var("r1").unwrap();
+    let r1 = r1.series().unwrap();
+    assert_eq!(r1.dtype(), &DataType::Duration(TimeUnit::Nanoseconds));
+    assert_eq!(r1.to_physical_repr().i64().unwrap().get(2), Some(3));
+
+    for code in ["reinterpret(`i32, 1 2 3)", "reinterpret(`i64, 1.0 2.0)"] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err());
+    }
+}
+
+#[test]
+fn reservoir_test() {
+    let state = create_state(true);
+    let code = "
+    .os.seed(7);
+    r: reservoir(1000);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    // 100 batches of 100 values cover 0 .. 9999
+    for i in 0..100 {
+        let code = format!("r: reservoir_feed(r, {} + range(100));", i * 100);
+        let nodes = state.parse("", &code).unwrap();
+        state.eval_ast(nodes, "", &code).unwrap();
+    }
+    let code = "s: reservoir_sample(r);";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r = state.get_var("r").unwrap();
+    assert_eq!(r.dict().unwrap().get("seen"), Some(&SpicyObj::I64(10_000)));
+    let s = state.get_var("s").unwrap();
+    let s = s.series().unwrap();
+    assert_eq!(s.len(), 1000);
+    assert_eq!(s.n_unique().unwrap(), 1000);
+    // each tenth of the input holds about a tenth of the sample
+    let mut buckets = [0; 10];
+    for v in s.i64().unwrap().iter() {
+        buckets[(v.unwrap() / 1000) as usize] += 1;
+    }
+    for count in buckets {
+        assert!((60..=140).contains(&count), "skewed buckets {:?}", buckets);
+    }
+}
+
+#[test]
+fn attr_test() {
+    let state = create_state(true);
+    let code = "
+    s: 5 4 3 2 1;
+    a0: attr(s);
+    s: set_attr(`desc, s);
+    a1: attr(s);
+    i: ss(s, 3);
+    a2: flag(set_attr(`asc, 1 2 3));
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("a0").unwrap(),
+        SpicyObj::Symbol("".to_owned())
+    );
+    assert_eq!(
+        state.get_var("a1").unwrap(),
+        SpicyObj::Symbol("desc".to_owned())
+    );
+    assert_eq!(
+        state.get_var("a2").unwrap(),
+        SpicyObj::Symbol("asc".to_owned())
+    );
+    // the descending flag lets ss binary search the reversed order
+    assert_eq!(state.get_var("i").unwrap(), SpicyObj::I64(2));
+
+    let code = "set_attr(`s, 1 2 3)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn combine_means_test() {
+    let state = create_state(true);
+    let code = "
+    a: 1 2 3;
+    b: 10 20;
+    r0: combine_means({a: [count(a), mean(a)], b: [count(b), mean(b)], c: [0, 0n]});
+    r1: combine_means([3 2 0, 2.0 15.0 99.0]);
+    e: mean(a ++ b);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let e = state.get_var("e").unwrap();
+    for id in ["r0", "r1"] {
+        let r = state.get_var(id).unwrap();
+        let r = r.dict().unwrap();
+        assert_eq!(r.get("count"), Some(&SpicyObj::I64(5)));
+        assert_eq!(r.get("mean"), Some(&e));
+    }
+}
+
+#[test]
+fn transpose_records_test() {
+    let state = create_state(true);
+    let code = "
+    records: [{a: 1, b: `x}, {a: 2}, {b: `z, c: 3.0}];
+    columns: transpose_records(records);
+    r: transpose_records(columns);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let columns = state.get_var("columns").unwrap();
+    let columns = columns.dict().unwrap();
+    assert_eq!(columns.keys().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(
+        columns.get("a"),
+        Some(&SpicyObj::MixedList(vec![
+            SpicyObj::I64(1),
+            SpicyObj::I64(2),
+            SpicyObj::Null
+        ]))
+    );
+    let r = state.get_var("r").unwrap();
+    let r = r.list().unwrap();
+    assert_eq!(r.len(), 3);
+    let second = r[1].dict().unwrap();
+    assert_eq!(second.get("a"), Some(&SpicyObj::I64(2)));
+    assert_eq!(second.get("b"), Some(&SpicyObj::Null));
+    assert_eq!(second.get("c"), Some(&SpicyObj::Null));
+    let third = r[2].dict().unwrap();
+    assert_eq!(third.get("b"), Some(&SpicyObj::Symbol("z".to_owned())));
+    assert_eq!(third.get("c"), Some(&SpicyObj::F64(3.0)));
+}
+
+#[test]
+fn take_strict_test() {
+    let state = create_state(true);
+    let code = "
+    d: {a: 1, b: 2, c: 3};
+    r0: `a`x # d;
+    r1: take_strict(`a`c, d);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let r0 = r0.dict().unwrap();
+    assert_eq!(r0.get("a"), Some(&SpicyObj::I64(1)));
+    assert_eq!(r0.get("b"), Some(&SpicyObj::Null));
+    let r1 = state.get_var("r1").unwrap();
+    let r1 = r1.dict().unwrap();
+    assert_eq!(r1.get("a"), Some(&SpicyObj::I64(1)));
+    assert_eq!(r1.get("c"), Some(&SpicyObj::I64(3)));
+
+    let code = "take_strict(`a`x`y, d)";
+    let nodes = state.parse("", code).unwrap();
+    let err = state.eval_ast(nodes, "", code).unwrap_err();
+    assert!(err.to_string().contains("x, y"), "{err}");
+}
+
+#[test]
+fn take_by_mask_test() {
+    let state = create_state(true);
+    state
+        .set_var(
+            "m",
+            SpicyObj::Series(Series::new("m".into(), [Some(true), None, Some(true)])),
+        )
+        .unwrap();
+    let code = "
+    r0: 1001b # 1 2 3 4;
+    r1: m # `a`b`c;
+    r2: 010b # (1; `a; \"b\");
+    r3: 011b # ([]a: 1 2 3, b: `x`y`z);
+    r4: 000b # 1 2 3;
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::Series(Series::new("".into(), [1i64, 4]))
+    );
+    assert_eq!(
+        state.get_var("r1").unwrap().to_str_vec().unwrap(),
+        vec!["a", "c"]
+    );
+    assert_eq!(
+        state.get_var("r2").unwrap(),
+        SpicyObj::MixedList(vec![SpicyObj::Symbol("a".to_owned())])
+    );
+    let r3 = state.get_var("r3").unwrap();
+    let r3 = r3.df().unwrap();
+    assert_eq!(r3.height(), 2);
+    assert_eq!(r3.column("a").unwrap().i64().unwrap().get(0), Some(2));
+    assert_eq!(state.get_var("r4").unwrap().size(), 0);
+
+    let code = "101b # 1 2";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn approx_count_distinct_test() {
+    let state = create_state(true);
+    let words: Vec<String> = (0..40_000).map(|i| format!("w{}", i % 20_000)).collect();
+    state
+        .set_var(
+            "words",
+            SpicyObj::Series(Series::new("words".into(), words)),
+        )
+        .unwrap();
+    let code = "
+    r0: approx_count_distinct(range(1000000) % 300000);
+    r1: approx_count_distinct(words);
+    r2: hll_estimate(hll_merge(hll_sketch(range(100000)), hll_sketch(50000 + range(100000))));
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    for (var, exact) in [("r0", 300_000.0), ("r1", 20_000.0), ("r2", 150_000.0)] {
+        let estimate = state.get_var(var).unwrap().to_f64().unwrap();
+        let error = (estimate - exact).abs() / exact;
+        assert!(error < 0.03, "{var}: estimate {estimate} vs {exact}");
+    }
+
+    let code = "hll_estimate(1 2 3)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn sign_split_test() {
+    let state = create_state(true);
+    let code = "r: sign_split(0.5 -0.25 0n 0.0 1.5 -0.25 0.0)";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r = state.get_var("r").unwrap();
+    let r = r.dict().unwrap();
+    assert_eq!(r.get("pos_count"), Some(&SpicyObj::I64(2)));
+    assert_eq!(r.get("neg_count"), Some(&SpicyObj::I64(2)));
+    assert_eq!(r.get("zero_count"), Some(&SpicyObj::I64(2)));
+    assert_eq!(r.get("pos_sum"), Some(&SpicyObj::F64(2.0)));
+    assert_eq!(r.get("neg_sum"), Some(&SpicyObj::F64(-0.5)));
+
+    let code = "sign_split(`a`b)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn concat_all_test() {
+    let state = create_state(true);
+    let code = "
+    r0: concat_all([1 2, 3, 0n 5]);
+    r1: concat_all([([]a: 1 2), ([]a: 3), ([]a: 4 5)]);
+    r2: concat_all([1 2, `a, \"b\"]);
+    r3: concat_all([]);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let r0: Vec<Option<i64>> = r0.series().unwrap().i64().unwrap().iter().collect();
+    assert_eq!(r0, vec![Some(1), Some(2), Some(3), None, Some(5)]);
+    let r1 = state.get_var("r1").unwrap();
+    assert_eq!(r1.df().unwrap().shape(), (5, 1));
+    assert_eq!(r1.df().unwrap().column("a").unwrap().n_chunks(), 1);
+    let r2 = state.get_var("r2").unwrap();
+    assert!(r2.is_mixed_list());
+    assert_eq!(r2.size(), 4);
+    assert_eq!(state.get_var("r3").unwrap(), SpicyObj::MixedList(vec![]));
+}
+
+#[test]
+fn raze_test() {
+    let state = create_state(true);
+    let code = "
+    r0: raze([1 2, 3 4]);
+    r1: raze([1 2, `a`b]);
+    r2: raze([{a: 1}, {a: 2, b: 3}]);
+    r3: raze([]);
+    r4: raze(1 2);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let r0: Vec<Option<i64>> = r0.series().unwrap().i64().unwrap().iter().collect();
+    assert_eq!(r0, vec![Some(1), Some(2), Some(3), Some(4)]);
+    let r1 = state.get_var("r1").unwrap();
+    assert!(r1.is_mixed_list());
+    assert_eq!(r1.size(), 4);
+    let r2 = state.get_var("r2").unwrap();
+    let d = r2.dict().unwrap();
+    assert_eq!(d.get("a"), Some(&SpicyObj::I64(2)));
+    assert_eq!(d.get("b"), Some(&SpicyObj::I64(3)));
+    assert_eq!(state.get_var("r3").unwrap(), SpicyObj::MixedList(vec![]));
+    assert_eq!(state.get_var("r4").unwrap().size(), 2);
+}
+
+#[test]
+fn append_list_test() {
+    let state = create_state(true);
+    let code = "
+    r0: [1, `a], [2.0, `b];
+    r1: [1, `a], [];
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    assert_eq!(
+        r0,
+        SpicyObj::MixedList(vec![
+            SpicyObj::I64(1),
+            SpicyObj::Symbol("a".to_owned()),
+            SpicyObj::F64(2.0),
+            SpicyObj::Symbol("b".to_owned()),
+        ])
+    );
+    assert_eq!(state.get_var("r1").unwrap().size(), 2);
+}
+
+#[test]
+fn aj_tolerance_test() {
+    let state = create_state(true);
+    let code = "
+    t: ([]sym: `a`a`a, time: 2024.01.01D09:30:00 2024.01.01D09:30:05 2024.01.01D09:30:20);
+    q: ([]sym: `a`a, time: 2024.01.01D09:29:59 2024.01.01D09:30:04, price: 1.0 2.0);
+    r0: aj(`sym`time, t, q);
+    r1: aj_tolerance(`sym`time, 0D00:00:02, t, q);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let price = |id: &str| -> Vec<Option<f64>> {
+        let r = state.get_var(id).unwrap();
+        let r = r.df().unwrap();
+        r.column("price").unwrap().f64().unwrap().iter().collect()
+    };
+    assert_eq!(price("r0"), vec![Some(1.0), Some(2.0), Some(2.0)]);
+    assert_eq!(price("r1"), vec![Some(1.0), Some(2.0), None]);
+
+    for code in [
+        "aj_tolerance(`sym`time, `a, t, q)",
+        "aj_tolerance(`sym`time, -0D00:00:02, t, q)",
+        "aj_tolerance(`sym`time, -1, t, q)",
+        "aj_tolerance(`sym`time, -1.5, t, q)",
+    ] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err(), "{code}");
+    }
+}
+
+#[test]
+fn aj_strategy_test() {
+    let state = create_state(true);
+    let code = "
+    t: ([]sym: `a`a, time: 2024.01.01D09:30:02 2024.01.01D09:30:05);
+    q: ([]sym: `a`a`a, time: 2024.01.01D09:30:00 2024.01.01D09:30:03 2024.01.01D09:30:10, price: 1.0 2.0 3.0);
+    r0: aj_strategy(`sym`time, `backward, t, q);
+    r1: aj_strategy(`sym`time, `forward, t, q);
+    r2: aj_strategy(`sym`time, `nearest, t, q);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let price = |id: &str| -> Vec<Option<f64>> {
+        let r = state.get_var(id).unwrap();
+        let r = r.df().unwrap();
+        r.column("price").unwrap().f64().unwrap().iter().collect()
+    };
+    assert_eq!(price("r0"), vec![Some(1.0), Some(2.0)]);
+    assert_eq!(price("r1"), vec![Some(2.0), Some(3.0)]);
+    assert_eq!(price("r2"), vec![Some(2.0), Some(2.0)]);
+
+    for code in [
+        "aj_strategy(`sym`time, `sideways, t, q)",
+        "aj_strategy(`sym`time, 1, t, q)",
+    ] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err(), "{code}");
+    }
+}
+
+#[test]
+fn genid_test() {
+    let state = create_state(true);
+    let code = "
+    .os.seed(42);
+    r0: genid(`uuid, 1000);
+    .os.seed(42);
+    r1: genid(`uuid, 1000);
+    r2: genid(`ordered, 1000);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let ids = |id: &str| -> Vec<String> {
+        let r = state.get_var(id).unwrap();
+        let r = r.series().unwrap();
+        r.str()
+            .unwrap()
+            .iter()
+            .map(|v| v.unwrap().to_owned())
+            .collect()
+    };
+    let r0 = ids("r0");
+    assert_eq!(r0, ids("r1"));
+    let unique: std::collections::HashSet<&String> = r0.iter().collect();
+    assert_eq!(unique.len(), 1000);
+    assert!(r0.iter().all(|v| v.len() == 36 && &v[14..15] == "4"));
+
+    let r2 = ids("r2");
+    assert!(r2.windows(2).all(|w| w[0] < w[1]));
+    assert!(r2.iter().all(|v| &v[14..15] == "7"));
+
+    let code = "genid(`snowflake, 1)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn max_by_test() {
+    let state = create_state(true);
+    let code = "
+    t: ([]sym: `a`b`c`d, qty: 3 9 1 9, price: 1.5 2.5 3.5 4.5);
+    r0: max_by(t, `qty, 0b);
+    r1: max_by(t, `qty, 1b);
+    r2: min_by(t, `qty, 0b);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let price = |id: &str| -> Vec<Option<f64>> {
+        let r = state.get_var(id).unwrap();
+        let r = r.df().unwrap();
+        r.column("price").unwrap().f64().unwrap().iter().collect()
+    };
+    assert_eq!(price("r0"), vec![Some(2.5), Some(4.5)]);
+    assert_eq!(price("r1"), vec![Some(2.5)]);
+    assert_eq!(price("r2"), vec![Some(3.5)]);
+
+    let code = "max_by(t, `size, 0b)";
+    let nodes = state.parse("", code).unwrap();
+    let err = state.eval_ast(nodes, "", code).unwrap_err();
+    assert!(err.to_string().contains("missing column 'size'"));
+}
+
+#[test]
+fn chunks_test() {
+    let state = create_state(true);
+    let code = "
+    r0: chunks(3, range(10));
+    r1: chunks(4, ([]sym: `a`b`c`d`e, qty: 1 2 3 4 5));
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap().as_vec().unwrap();
+    assert_eq!(
+        r0.iter().map(|c| c.size()).collect::<Vec<_>>(),
+        vec![3, 3, 3, 1]
+    );
+    assert_eq!(r0[3].series().unwrap().i64().unwrap().get(0), Some(9));
+    let r1 = state.get_var("r1").unwrap().as_vec().unwrap();
+    assert_eq!(
+        r1.iter()
+            .map(|c| c.df().unwrap().height())
+            .collect::<Vec<_>>(),
+        vec![4, 1]
+    );
+
+    let code = "chunks(0, range(10))";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn edges_test() {
+    let state = create_state(true);
+    let code = "
+    s: 110010110b;
+    r0: edges(s, 0b);
+    r1: edges(s, 1b);
+    r2: falling_edges(s, 0b);
+    r3: falling_edges(0110b, 1b);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let indices = |id: &str| -> Vec<Option<i64>> {
+        let r = state.get_var(id).unwrap();
+        r.series().unwrap().i64().unwrap().iter().collect()
+    };
+    assert_eq!(indices("r0"), vec![Some(4), Some(6)]);
+    assert_eq!(indices("r1"), vec![Some(0), Some(4), Some(6)]);
+    assert_eq!(indices("r2"), vec![Some(2), Some(5), Some(8)]);
+    assert_eq!(indices("r3"), vec![Some(0), Some(3)]);
+
+    let code = "edges(1 2 3, 0b)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn bits_test() {
+    let state = create_state(true);
+    let code = "
+    r0: bits(5, 4);
+    r1: bits(5 0 255, 8);
+    r2: unbits(r1);
+    r3: unbits(bits(-2, 64));
+    r4: unbits(bits(-2, 4));
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let bits = |b: &[bool]| SpicyObj::Series(Series::new("".into(), b.to_vec()));
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        bits(&[false, true, false, true])
+    );
+    assert_eq!(
+        state.get_var("r1").unwrap(),
+        SpicyObj::MixedList(vec![
+            bits(&[false, false, false, false, false, true, false, true]),
+            bits(&[false; 8]),
+            bits(&[true; 8]),
+        ])
+    );
+    assert_eq!(
+        state.get_var("r2").unwrap(),
+        SpicyObj::Series(Series::new("".into(), [5i64, 0, 255]))
+    );
+    assert_eq!(state.get_var("r3").unwrap(), SpicyObj::I64(-2));
+    assert_eq!(state.get_var("r4").unwrap(), SpicyObj::I64(14));
+
+    for code in ["bits(5, 0)", "bits(5, 65)", "bits(1.5, 4)", "unbits(1 0 1)"] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err(), "{code}");
+    }
+}
+
+#[test]
+fn fill_limit_test() {
+    let state = create_state(true);
+    let code = "
+    s: 1 0n 0n 0n 5 0n;
+    r0: fill_limit(2, s);
+    r1: bfill_limit(1, s);
+    r2: fill(s);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let values = |id: &str| -> Vec<Option<i64>> {
+        let r = state.get_var(id).unwrap();
+        r.series().unwrap().i64().unwrap().iter().collect()
+    };
+    assert_eq!(
+        values("r0"),
+        vec![Some(1), Some(1), Some(1), None, Some(5), Some(5)]
+    );
+    assert_eq!(
+        values("r1"),
+        vec![Some(1), None, None, Some(5), Some(5), None]
+    );
+    assert_eq!(
+        values("r2"),
+        vec![Some(1), Some(1), Some(1), Some(1), Some(5), Some(5)]
+    );
+
+    let code = "fill_limit(0, s)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn mcor_test() {
+    let state = create_state(true);
+    let code = "
+    x: 1 2 3 4;
+    r: mcor(3, x, 2 4 6 9);
+    t: ([]x: 1 2 3 4, y: 2 4 6 9);
+    r1: select c: mcor(3, x, y) from t;
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r = state.get_var("r").unwrap();
+    let r: Vec<Option<f64>> = r.series().unwrap().f64().unwrap().iter().collect();
+    assert_eq!(r[0], None);
+    let expected = [1.0, 1.0, 5.0 / (2.0f64 * 38.0 / 3.0).sqrt()];
+    for (v, e) in r[1..].iter().zip(expected) {
+        assert!((v.unwrap() - e).abs() < 1e-9, "{v:?} vs {e}");
+    }
+    let r1 = state.get_var("r1").unwrap();
+    let r1: Vec<Option<f64>> = r1
+        .df()
+        .unwrap()
+        .column("c")
+        .unwrap()
+        .f64()
+        .unwrap()
+        .iter()
+        .collect();
+    assert_eq!(r1, r);
+
+    let code = "mcor(3, x, 1 2 3)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn str_join_agg_test() {
+    let state = create_state(true);
+    let names = Series::new("name".into(), [Some("x"), None, Some("y"), Some("z")]);
+    state.set_var("name", SpicyObj::Series(names)).unwrap();
+    let code = "
+    r0: str_join_agg(\", \", name);
+    t: ([]k: `a`a`b`a, name);
+    r1: select name: str_join_agg(\"-\", name) by k from t;
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::String("x, y, z".to_owned())
+    );
+    let r1 = state.get_var("r1").unwrap();
+    let r1 = r1.df().unwrap();
+    let joined: Vec<Option<&str>> = r1.column("name").unwrap().str().unwrap().iter().collect();
+    assert_eq!(joined, vec![Some("x-z"), Some("y")]);
+}
+
+#[test]
+fn modes_test() {
+    let state = create_state(true);
+    let code = "
+    r0: modes(1 5 5 2 0n, 0b);
+    r1: modes(3 2 2 3 1, 0b);
+    r2: modes(0n 0n 1, 0b);
+    r3: modes(0n 0n 1, 1b);
+    r4: modes(0n 0n, 0b);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let values = |id: &str| -> Vec<Option<i64>> {
+        let r = state.get_var(id).unwrap();
+        r.series().unwrap().i64().unwrap().iter().collect()
+    };
+    assert_eq!(values("r0"), vec![Some(5)]);
+    let mut tied = values("r1");
+    tied.sort();
+    assert_eq!(tied, vec![Some(2), Some(3)]);
+    assert_eq!(values("r2"), vec![Some(1)]);
+    assert_eq!(values("r3"), vec![None]);
+    assert_eq!(state.get_var("r4").unwrap(), SpicyObj::Null);
+}
+
+#[test]
+fn median_iqr_test() {
+    let state = create_state(true);
+    let code = "
+    r0: median(3 1 4 1 5 0n);
+    r1: iqr(1 2 3 4 5 6 7 8 9);
+    r2: iqr(0n 0n);
+    r3: median(0n 0n);
+    t: ([]k: `a`b`a`b`a`b`a`b`a, v: 1 2 3 4 5 6 7 8 9);
+    r4: select m: median(v), q: iqr(v) by k from t;
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(state.get_var("r0").unwrap(), SpicyObj::F64(3.0));
+    assert_eq!(state.get_var("r1").unwrap(), SpicyObj::F64(4.0));
+    assert_eq!(state.get_var("r2").unwrap(), SpicyObj::Null);
+    assert_eq!(state.get_var("r3").unwrap(), SpicyObj::Null);
+    let r4 = state.get_var("r4").unwrap();
+    let r4 = r4.df().unwrap();
+    let m: Vec<Option<f64>> = r4.column("m").unwrap().f64().unwrap().iter().collect();
+    let q: Vec<Option<f64>> = r4.column("q").unwrap().f64().unwrap().iter().collect();
+    assert_eq!(m, vec![Some(5.0), Some(5.0)]);
+    assert_eq!(q, vec![Some(4.0), Some(4.0)]);
+}
+
+#[test]
+fn qcut_test() {
+    let state = create_state(true);
+    let uniform: Vec<f64> = (0..1000)
+        .map(|i| ((i * 7919) % 1000) as f64 / 10.0)
+        .collect();
+    state
+        .set_var("s", SpicyObj::Series(Series::new("s".into(), uniform)))
+        .unwrap();
+    state
+        .set_var(
+            "n",
+            SpicyObj::Series(Series::new(
+                "n".into(),
+                [Some(3i64), None, Some(1), Some(2)],
+            )),
+        )
+        .unwrap();
+    let code = "
+    r0: qcut(s, 4);
+    r1: qcut(1 2 2 2 3, 2);
+    r2: qcut(n, 2);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let mut counts = [0; 4];
+    for b in r0.series().unwrap().i64().unwrap().iter() {
+        counts[b.unwrap() as usize] += 1;
+    }
+    assert_eq!(counts, [250, 250, 250, 250]);
+    // ties at an edge fall into the lower bucket
+    assert_eq!(
+        state.get_var("r1").unwrap(),
+        SpicyObj::Series(Series::new("".into(), [0i64, 0, 0, 0, 1]))
+    );
+    assert_eq!(
+        state.get_var("r2").unwrap(),
+        SpicyObj::Series(Series::new(
+            "n".into(),
+            [Some(1i64), None, Some(0), Some(0)]
+        ))
+    );
+
+    let code = "qcut(1 2 3, 0)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn reverse_test() {
+    let state = create_state(true);
+    state
+        .set_var(
+            "e",
+            SpicyObj::Series(Series::new_empty("e".into(), &DataType::Int64)),
+        )
+        .unwrap();
+    let code = "
+    r0: reverse(1 2 3);
+    r1: reverse((1; `a; \"b\"));
+    r2: reverse([[1 2, 3 4, 5 6]]);
+    r3: reverse({a: 1, b: 2});
+    r4: reverse(([]k: `a`b`c, v: 1 2 3));
+    r5: reverse(e);
+    r6: reverse(`a);
+    r7: reverse(1.5);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::Series(Series::new("".into(), [3i64, 2, 1]))
+    );
+    assert_eq!(
+        state.get_var("r1").unwrap(),
+        SpicyObj::MixedList(vec![
+            SpicyObj::String("b".to_owned()),
+            SpicyObj::Symbol("a".to_owned()),
+            SpicyObj::I64(1),
+        ])
+    );
+    let r2 = state.get_var("r2").unwrap();
+    let values: Vec<f64> = r2.matrix().unwrap().iter().copied().collect();
+    assert_eq!(values, vec![5.0, 6.0, 3.0, 4.0, 1.0, 2.0]);
+    let r3 = state.get_var("r3").unwrap();
+    let keys: Vec<&String> = r3.dict().unwrap().keys().collect();
+    assert_eq!(keys, vec!["b", "a"]);
+    let r4 = state.get_var("r4").unwrap();
+    let v: Vec<Option<i64>> = r4
+        .df()
+        .unwrap()
+        .column("v")
+        .unwrap()
+        .i64()
+        .unwrap()
+        .iter()
+        .collect();
+    assert_eq!(v, vec![Some(3), Some(2), Some(1)]);
+    assert_eq!(r4.df().unwrap().height(), 3);
+    assert_eq!(state.get_var("r5").unwrap().size(), 0);
+    assert_eq!(
+        state.get_var("r6").unwrap(),
+        SpicyObj::Symbol("a".to_owned())
+    );
+    assert_eq!(state.get_var("r7").unwrap(), SpicyObj::F64(1.5));
+}
+
+#[test]
+fn dedup_consecutive_test() {
+    let state = create_state(true);
+    state
+        .set_var(
+            "s",
+            SpicyObj::Series(Series::new(
+                "s".into(),
+                [Some(1i64), None, None, Some(1), Some(1), Some(2), None],
+            )),
+        )
+        .unwrap();
+    let code = "
+    r0: dedup_consecutive(1 1 2 2 2 1 3 3 1);
+    r1: dedup_consecutive(s);
+    r2: dedup_consecutive((`a; `a; 1; \"b\"; \"b\"; `a));
+    r3: dedup_consecutive(`a`a);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::Series(Series::new("".into(), [1i64, 2, 1, 3, 1]))
+    );
+    assert_eq!(
+        state.get_var("r1").unwrap(),
+        SpicyObj::Series(Series::new(
+            "s".into(),
+            [Some(1i64), None, Some(1), Some(2), None]
+        ))
+    );
+    assert_eq!(
+        state.get_var("r2").unwrap(),
+        SpicyObj::MixedList(vec![
+            SpicyObj::Symbol("a".to_owned()),
+            SpicyObj::I64(1),
+            SpicyObj::String("b".to_owned()),
+            SpicyObj::Symbol("a".to_owned()),
+        ])
+    );
+    assert_eq!(state.get_var("r3").unwrap().size(), 1);
+}
+
+#[test]
+fn group_test() {
+    let state = create_state(true);
+    state
+        .set_var(
+            "s",
+            SpicyObj::Series(Series::new("s".into(), [Some(1i64), None, Some(1), None])),
+        )
+        .unwrap();
+    let code = "
+    r0: group(1 2 1 3 2);
+    r1: group(s);
+    r2: group((`a; 1; `a; 0n));
+    r3: group(`a`b`a);
+    r4: group(());
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let group = |groups: &[(&str, &[i64])]| {
+        SpicyObj::Dict(
+            groups
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        k.to_string(),
+                        SpicyObj::Series(Series::new("".into(), v.to_vec())),
+                    )
+                })
+                .collect(),
+        )
+    };
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        group(&[("1", &[0, 2]), ("2", &[1, 4]), ("3", &[3])])
+    );
+    assert_eq!(
+        state.get_var("r1").unwrap(),
+        group(&[("1", &[0, 2]), ("", &[1, 3])])
+    );
+    assert_eq!(
+        state.get_var("r2").unwrap(),
+        group(&[("a", &[0, 2]), ("1", &[1]), ("", &[3])])
+    );
+    assert_eq!(
+        state.get_var("r3").unwrap(),
+        group(&[("a", &[0, 2]), ("b", &[1])])
+    );
+    assert_eq!(state.get_var("r4").unwrap(), group(&[]));
+}
+
+#[test]
+fn distinct_test() {
+    let state = create_state(true);
+    state
+        .set_var(
+            "s",
+            SpicyObj::Series(Series::new(
+                "s".into(),
+                [Some(2i64), None, Some(1), None, Some(2)],
+            )),
+        )
+        .unwrap();
+    let code = "
+    r0: distinct(s);
+    r1: count_distinct(s);
+    r2: distinct((1; `a; 1.0; `a; \"b\"; 1));
+    r3: count_distinct((1; `a; 1.0; `a; \"b\"; 1));
+    r4: distinct({a: 1, b: 2, c: 1});
+    r5: distinct([[1 2, 3 4, 1 2]]);
+    r6: count_distinct(`a);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::Series(Series::new("s".into(), [Some(2i64), None, Some(1)]))
+    );
+    assert_eq!(state.get_var("r1").unwrap(), SpicyObj::I64(3));
+    assert_eq!(
+        state.get_var("r2").unwrap(),
+        SpicyObj::MixedList(vec![
+            SpicyObj::I64(1),
+            SpicyObj::Symbol("a".to_owned()),
+            SpicyObj::String("b".to_owned()),
+        ])
+    );
+    assert_eq!(state.get_var("r3").unwrap(), SpicyObj::I64(3));
+    assert_eq!(
+        state.get_var("r4").unwrap(),
+        SpicyObj::MixedList(vec![SpicyObj::I64(1), SpicyObj::I64(2)])
+    );
+    let r5 = state.get_var("r5").unwrap();
+    let r5 = r5.matrix().unwrap();
+    assert_eq!(r5.shape(), &[2, 2]);
+    assert_eq!(
+        r5.iter().copied().collect::<Vec<f64>>(),
+        vec![1.0, 2.0, 3.0, 4.0]
+    );
+    assert_eq!(state.get_var("r6").unwrap(), SpicyObj::I64(1));
+}
+
+#[test]
+fn pow_test() {
+    let state = create_state(true);
+    let code = "
+    r0: 2 pow 10;
+    r1: 2 pow (0 - 2);
+    r2: 9.0 pow 0.5;
+    r3: 1 2 3 pow 2;
+    r4: 2 4 pow (0 - 1);
+    r5: {a: 2, b: 3} pow 2;
+    r6: [[1 2, 3 4]] pow 2;
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(state.get_var("r0").unwrap(), SpicyObj::I64(1024));
+    assert_eq!(state.get_var("r1").unwrap(), SpicyObj::F64(0.25));
+    assert_eq!(state.get_var("r2").unwrap(), SpicyObj::F64(3.0));
+    let r3 = state.get_var("r3").unwrap();
+    let r3: Vec<Option<i64>> = r3.series().unwrap().i64().unwrap().iter().collect();
+    assert_eq!(r3, vec![Some(1), Some(4), Some(9)]);
+    let r4 = state.get_var("r4").unwrap();
+    let r4: Vec<Option<f64>> = r4.series().unwrap().f64().unwrap().iter().collect();
+    assert_eq!(r4, vec![Some(0.5), Some(0.25)]);
+    assert_eq!(
+        state.get_var("r5").unwrap(),
+        SpicyObj::Dict(indexmap::IndexMap::from([
+            ("a".to_owned(), SpicyObj::I64(4)),
+            ("b".to_owned(), SpicyObj::I64(9)),
+        ]))
+    );
+    let r6 = state.get_var("r6").unwrap();
+    let r6: Vec<f64> = r6.matrix().unwrap().iter().copied().collect();
+    assert_eq!(r6, vec![1.0, 4.0, 9.0, 16.0]);
+}
+
+#[test]
+fn sanitize_test() {
+    let state = create_state(true);
+    let v = Series::new(
+        "v".into(),
+        [
+            Some(1.5),
+            Some(f64::NAN),
+            Some(f64::INFINITY),
+            None,
+            Some(f64::NEG_INFINITY),
+        ],
+    );
+    state.set_var("v", SpicyObj::Series(v)).unwrap();
+    let code = "
+    r0: sanitize(0.0, v);
+    r1: sanitize(0n, ([]k: 1 2 3 4 5, v));
+    r2: sanitize(0n, 1 2 3);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let r0 = r0.dict().unwrap();
+    assert_eq!(r0["altered"], SpicyObj::I64(3));
+    let values: Vec<Option<f64>> = r0["data"].series().unwrap().f64().unwrap().iter().collect();
+    assert_eq!(
+        values,
+        vec![Some(1.5), Some(0.0), Some(0.0), None, Some(0.0)]
+    );
+
+    let r1 = state.get_var("r1").unwrap();
+    let r1 = r1.dict().unwrap();
+    assert_eq!(r1["altered"], SpicyObj::I64(3));
+    let df = r1["data"].df().unwrap();
+    assert_eq!(df.get_column_names_str(), vec!["k", "v"]);
+    let values: Vec<Option<f64>> = df.column("v").unwrap().f64().unwrap().iter().collect();
+    assert_eq!(values, vec![Some(1.5), None, None, None, None]);
+
+    let r2 = state.get_var("r2").unwrap();
+    assert_eq!(r2.dict().unwrap()["altered"], SpicyObj::I64(0));
+}
+
+#[test]
+fn flip_test() {
+    let state = create_state(true);
+    let code = "
+    r0: flip({a: 1 2 3, b: 4 5 6});
+    r1: flip(([]a: 1 2, b: 3 4));
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let r0 = r0.df().unwrap();
+    assert_eq!(r0.shape(), (3, 2));
+    assert_eq!(r0.get_column_names_str(), vec!["a", "b"]);
+    let r1 = state.get_var("r1").unwrap();
+    let r1 = r1.df().unwrap();
+    assert_eq!(
+        r1.get_column_names_str(),
+        vec!["column", "column_0", "column_1"]
+    );
+    let names: Vec<Option<&str>> = r1.column("column").unwrap().str().unwrap().iter().collect();
+    assert_eq!(names, vec![Some("a"), Some("b")]);
+    let row: Vec<Option<i64>> = r1
+        .column("column_0")
+        .unwrap()
+        .i64()
+        .unwrap()
+        .iter()
+        .collect();
+    assert_eq!(row, vec![Some(1), Some(3)]);
+
+    let code = "flip({a: 1 2 3, b: 4 5})";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn value_vs_identity_match_test() {
+    let state = create_state(true);
+    for (code, expected) in [
+        ("1i = 1", true),
+        ("1i ~ 1", false),
+        ("1 ~ 1", true),
+        ("match_value(1i, 1)", true),
+        ("match_value(1, 1.0)", true),
+        ("1 2i ~ 1 2", false),
+        ("1 2 ~ 1 2", true),
+        ("match_value(1 2i, 1 2.0)", true),
+        ("match_value(1 2, 1 3)", false),
+        ("match_value(1 2, 1 2 3)", false),
+        ("match_value({a: 1i, b: 2 3h}, {a: 1, b: 2 3})", true),
+        ("match_value({a: 1}, {b: 1})", false),
+        ("match_value(`a, `a)", true),
+        ("match_value(`a, \"a\")", false),
+    ] {
+        let nodes = state.parse("", code).unwrap();
+        let r = state.eval_ast(nodes, "", code).unwrap();
+        assert_eq!(r, SpicyObj::Boolean(expected), "{code}");
+    }
+}
+
+#[test]
+fn bitwise_infix_test() {
+    let state = create_state(true);
+    for (code, expected) in [
+        ("(12 bit_and 10) = 8", true),
+        ("(12 bit_or 10) = 14", true),
+        ("(1 2 3 bit_xor 1) ~ 0 3 2", true),
+        ("(12 & 10) = 10", true),
+    ] {
+        let nodes = state.parse("", code).unwrap();
+        let r = state.eval_ast(nodes, "", code).unwrap();
+        assert_eq!(r, SpicyObj::Boolean(expected), "{code}");
+    }
+}
+
+#[test]
+fn minute_time_literal_test() {
+    let state = create_state(true);
+    let code = "
+    r0: 09:30;
+    r1: 09:30 10:00:15 10:00:15.5;
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::Time(34_200_000_000_000)
+    );
+    let r1 = state.get_var("r1").unwrap();
+    let r1 = r1.series().unwrap();
+    assert_eq!(r1.dtype(), &DataType::Time);
+    let r1: Vec<Option<i64>> = r1.time().unwrap().phys.iter().collect();
+    assert_eq!(
+        r1,
+        vec![
+            Some(34_200_000_000_000),
+            Some(36_015_000_000_000),
+            Some(36_015_500_000_000)
+        ]
+    );
+}
+
+#[test]
+fn asc_desc_test() {
+    let state = create_state(true);
+    state
+        .set_var(
+            "l",
+            SpicyObj::MixedList(vec![
+                SpicyObj::I64(3),
+                SpicyObj::F64(1.5),
+                SpicyObj::Null,
+                SpicyObj::I32(2),
+            ]),
+        )
+        .unwrap();
+    let code = "
+    r0: asc(3 0n 1);
+    r1: desc(3 0n 1);
+    r2: asc([\"b\", \"a\", \"c\"]);
+    r3: desc(2024.01.01 2024.01.03 2024.01.02);
+    r4: asc(l);
+    r5: desc(l);
+    r6: asc({a: 3, b: 1, c: 2});
+    e2: [\"a\", \"b\", \"c\"];
+    e3: 2024.01.03 2024.01.02 2024.01.01;
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let ints = |id: &str| -> Vec<Option<i64>> {
+        let r = state.get_var(id).unwrap();
+        r.series().unwrap().i64().unwrap().iter().collect()
+    };
+    assert_eq!(ints("r0"), vec![None, Some(1), Some(3)]);
+    assert_eq!(ints("r1"), vec![Some(3), Some(1), None]);
+    assert_eq!(state.get_var("r2").unwrap(), state.get_var("e2").unwrap());
+    assert_eq!(state.get_var("r3").unwrap(), state.get_var("e3").unwrap());
+    assert_eq!(
+        state.get_var("r4").unwrap(),
+        SpicyObj::MixedList(vec![
+            SpicyObj::Null,
+            SpicyObj::F64(1.5),
+            SpicyObj::I32(2),
+            SpicyObj::I64(3),
+        ])
+    );
+    assert_eq!(
+        state.get_var("r5").unwrap(),
+        SpicyObj::MixedList(vec![
+            SpicyObj::I64(3),
+            SpicyObj::I32(2),
+            SpicyObj::F64(1.5),
+            SpicyObj::Null,
+        ])
+    );
+    let r6 = state.get_var("r6").unwrap();
+    assert_eq!(
+        r6.dict().unwrap().keys().collect::<Vec<_>>(),
+        vec!["b", "c", "a"]
+    );
+
+    for code in ["asc([1, `a])", "desc({a: 1 2, b: 3})"] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err(), "{code}");
+    }
+}
+
+#[test]
+fn iasc_idesc_test() {
+    let state = create_state(true);
+    let code = "
+    r0: iasc(3 1 2);
+    r1: idesc(3 1 2);
+    r2: iasc([3, 1.5, 2]);
+    x: 5 0n 2 4;
+    r3: x @ iasc(x);
+    e3: asc(x);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let ints = |id: &str| -> Vec<Option<i64>> {
+        let r = state.get_var(id).unwrap();
+        r.series().unwrap().i64().unwrap().iter().collect()
+    };
+    assert_eq!(ints("r0"), vec![Some(1), Some(2), Some(0)]);
+    assert_eq!(ints("r1"), vec![Some(0), Some(2), Some(1)]);
+    assert_eq!(ints("r2"), vec![Some(1), Some(2), Some(0)]);
+    assert_eq!(state.get_var("r3").unwrap(), state.get_var("e3").unwrap());
+}
+
+// `where` is a query keyword in source, so call the builtin directly
+#[test]
+fn where_test() {
+    let where_op = BUILT_IN_FN.get("where").unwrap().f.as_ref().unwrap();
+    let ints = |obj: SpicyObj| -> Vec<Option<i64>> {
+        obj.series().unwrap().i64().unwrap().iter().collect()
+    };
+    let bools = SpicyObj::Series(Series::new("".into(), [true, false, true, true]));
+    assert_eq!(
+        ints(where_op(&[&bools]).unwrap()),
+        vec![Some(0), Some(2), Some(3)]
+    );
+    let counts = SpicyObj::Series(Series::new("".into(), [2i64, 0, 3]));
+    assert_eq!(
+        ints(where_op(&[&counts]).unwrap()),
+        vec![Some(0), Some(0), Some(2), Some(2), Some(2)]
+    );
+    let none = SpicyObj::Series(Series::new("".into(), [false, false, false]));
+    assert_eq!(ints(where_op(&[&none]).unwrap()), Vec::<Option<i64>>::new());
+    let list = SpicyObj::MixedList(vec![
+        SpicyObj::Boolean(true),
+        SpicyObj::I64(2),
+        SpicyObj::Null,
+    ]);
+    assert_eq!(
+        ints(where_op(&[&list]).unwrap()),
+        vec![Some(0), Some(1), Some(1)]
+    );
+
+    let negative = SpicyObj::Series(Series::new("".into(), [-1i64, 2]));
+    let floats = SpicyObj::Series(Series::new("".into(), [1.0, 2.0]));
+    assert!(where_op(&[&negative]).is_err());
+    assert!(where_op(&[&floats]).is_err());
+}
+
+#[test]
+fn is_sorted_test() {
+    let state = create_state(true);
+    let code = "
+    r0: is_sorted(1 2 2 3);
+    r1: is_sorted(3 2 2 1);
+    r2: is_sorted(1 3 2);
+    r3: is_sorted(0n 1 2);
+    r4: is_sorted(2 1 0n);
+    r5: is_sorted(1 0n 2);
+    r6: is_sorted(set_attr(`asc, 3 1 2));
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let sym = |id: &str| state.get_var(id).unwrap().str().unwrap().to_owned();
+    assert_eq!(sym("r0"), "asc");
+    assert_eq!(sym("r1"), "desc");
+    assert_eq!(sym("r2"), "");
+    assert_eq!(sym("r3"), "asc");
+    assert_eq!(sym("r4"), "desc");
+    assert_eq!(sym("r5"), "");
+    assert_eq!(sym("r6"), "");
+}
+
+#[test]
+fn freq_test() {
+    let state = create_state(true);
+    let code = "
+    s: `b`a`c`b`a`b;
+    r0: freq(s, false);
+    r1: freq(7 0n 8 7 0n 0n, false);
+    r2: freq(7 0n 8 7 0n 0n, true);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let freq = |id: &str| -> (Vec<Option<String>>, Vec<Option<i64>>) {
+        let r = state.get_var(id).unwrap();
+        let df = r.df().unwrap();
+        let values = df
+            .column("value")
+            .unwrap()
+            .cast(&DataType::String)
+            .unwrap()
+            .str()
+            .unwrap()
+            .iter()
+            .map(|v| v.map(|v| v.to_owned()))
+            .collect();
+        let counts = df
+            .column("count")
+            .unwrap()
+            .cast(&DataType::Int64)
+            .unwrap()
+            .i64()
+            .unwrap()
+            .iter()
+            .collect();
+        (values, counts)
+    };
+    let some = |v: &str| Some(v.to_owned());
+    assert_eq!(
+        freq("r0"),
+        (
+            vec![some("b"), some("a"), some("c")],
+            vec![Some(3), Some(2), Some(1)]
+        )
+    );
+    assert_eq!(
+        freq("r1"),
+        (
+            vec![None, some("7"), some("8")],
+            vec![Some(3), Some(2), Some(1)]
+        )
+    );
+    assert_eq!(
+        freq("r2"),
+        (vec![some("7"), some("8")], vec![Some(2), Some(1)])
+    );
+}
+
+#[test]
+fn segment_test() {
+    let state = create_state(true);
+    let code = "
+    r0: segment(`a`a`b`b`b`a`c);
+    r1: segment(1 0n 0n 2 2);
+    r2: segment_reset(0100101b);
+    r3: segment_reset(1001b);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let ints = |id: &str| -> Vec<i64> {
+        let r = state.get_var(id).unwrap();
+        r.series()
+            .unwrap()
+            .i64()
+            .unwrap()
+            .iter()
+            .flatten()
+            .collect()
+    };
+    assert_eq!(ints("r0"), vec![0, 0, 1, 1, 1, 2, 3]);
+    assert_eq!(ints("r1"), vec![0, 1, 1, 2, 2]);
+    assert_eq!(ints("r2"), vec![0, 1, 1, 1, 2, 2, 3]);
+    assert_eq!(ints("r3"), vec![0, 0, 0, 1]);
+
+    let code = "segment_reset(1 0 1)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn fill_forward_null_count_test() {
+    let state = create_state(true);
+    let code = "
+    s: 0n 0n 1 0n 3 0n;
+    r0: fill_forward(s);
+    r1: fill_forward([0n, 1, 0n, `a, 0n]);
+    r2: fill_forward({a: 1, b: 0n, c: 2});
+    r3: null_count(s);
+    r4: null_count([0n, 1, 0n]);
+    r5: null_count(1 2 3);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let r0: Vec<Option<i64>> = r0.series().unwrap().i64().unwrap().iter().collect();
+    assert_eq!(r0, vec![None, None, Some(1), Some(1), Some(3), Some(3)]);
+    assert_eq!(
+        state.get_var("r1").unwrap(),
+        SpicyObj::MixedList(vec![
+            SpicyObj::Null,
+            SpicyObj::I64(1),
+            SpicyObj::I64(1),
+            SpicyObj::Symbol("a".to_owned()),
+            SpicyObj::Symbol("a".to_owned()),
+        ])
+    );
+    let r2 = state.get_var("r2").unwrap();
+    assert_eq!(
+        r2.dict().unwrap().values().cloned().collect::<Vec<_>>(),
+        vec![SpicyObj::I64(1), SpicyObj::I64(1), SpicyObj::I64(2)]
+    );
+    assert_eq!(state.get_var("r3").unwrap(), SpicyObj::I64(4));
+    assert_eq!(state.get_var("r4").unwrap(), SpicyObj::I64(2));
+    assert_eq!(state.get_var("r5").unwrap(), SpicyObj::I64(0));
+}
+
+#[test]
+fn enlist_test() {
+    let state = create_state(true);
+    let code = "
+    r0: enlist(1);
+    r1: enlist(`a);
+    r2: enlist([1, `a]);
+    r3: [enlist(1), 2];
+    r4: [1, 2, 3];
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::Series(Series::new("".into(), [1i64]))
+    );
+    let r1 = state.get_var("r1").unwrap();
+    assert_eq!(r1.size(), 1);
+    assert_eq!(r1.get_type_name(), "syms");
+    assert_eq!(
+        state.get_var("r2").unwrap(),
+        SpicyObj::MixedList(vec![SpicyObj::MixedList(vec![
+            SpicyObj::I64(1),
+            SpicyObj::Symbol("a".to_owned()),
+        ])])
+    );
+    assert!(state.get_var("r3").unwrap().is_mixed_list());
+    // same typed atoms in a list literal are promoted to a series
+    assert_eq!(
+        state.get_var("r4").unwrap(),
+        SpicyObj::Series(Series::new("".into(), [1i64, 2, 3]))
+    );
+}
+
+#[test]
+fn type_test() {
+    let state = create_state(true);
+    let code = "
+    r0: type(1 2 3);
+    r1: type(`abc);
+    r2: type([1, `a]);
+    r3: type_code(1 2 3);
+    r4: type_code(`abc);
+    r5: type_code({a: 1});
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let sym = |id: &str| state.get_var(id).unwrap().str().unwrap().to_owned();
+    assert_eq!(sym("r0"), "i64s");
+    assert_eq!(sym("r1"), "sym");
+    assert_eq!(sym("r2"), "list");
+    assert_eq!(state.get_var("r3").unwrap(), SpicyObj::I64(5));
+    assert_eq!(state.get_var("r4").unwrap(), SpicyObj::I64(-14));
+    assert_eq!(state.get_var("r5").unwrap(), SpicyObj::I64(91));
+}
+
+#[test]
+fn within_test() {
+    let state = create_state(true);
+    let code = "
+    r0: within(1 2 3 4 5, 2 4);
+    r1: within(2024.01.02 2024.01.03 2024.01.05 2024.01.06, 2024.01.03 2024.01.05);
+    r2: within(3, [3, 5]);
+    r3: within(5, 3 5);
+    r4: within(2.5, 3 5);
+    r5: within(2024.01.04, 2024.01.03 2024.01.05);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let bools = |id: &str| {
+        state
+            .get_var(id)
+            .unwrap()
+            .series()
+            .unwrap()
+            .bool()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(bools("r0"), vec![false, true, true, true, false]);
+    assert_eq!(bools("r1"), vec![false, true, true, false]);
+    assert_eq!(state.get_var("r2").unwrap(), SpicyObj::Boolean(true));
+    assert_eq!(state.get_var("r3").unwrap(), SpicyObj::Boolean(true));
+    assert_eq!(state.get_var("r4").unwrap(), SpicyObj::Boolean(false));
+    assert_eq!(state.get_var("r5").unwrap(), SpicyObj::Boolean(true));
+
+    for code in ["within(1 2 3, 1 2 3)", "within(1, 1)"] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err(), "{code}");
+    }
+}
+
+#[test]
+fn key_table_test() {
+    let state = create_state(true);
+    let code = "
+    t: ([]sym: `a`b, qty: 1 2, price: 1.5 2.5);
+    r0: 1 ! t;
+    r1: 0 ! r0;
+    r2: type(r0);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let t = state.get_var("t").unwrap();
+    let t = t.df().unwrap();
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::KeyedTable(t.clone(), 1)
+    );
+    assert_eq!(state.get_var("r1").unwrap(), SpicyObj::DataFrame(t.clone()));
+    assert_eq!(state.get_var("r2").unwrap().str().unwrap(), "keyed");
+
+    for code in ["t: ([]a: 1 2); 1 ! t", "t: ([]a: 1 2); -1 ! t", "1 ! 1 2"] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err(), "{code}");
+    }
+}
+
+#[test]
+fn mod_test() {
+    let state = create_state(true);
+    let code = "
+    r0: mod(-7, 3);
+    r1: modulo(-7, 3);
+    r2: modulo(7, 0);
+    r3: mod(2024.01.02 2024.01.03, 2);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(state.get_var("r0").unwrap(), SpicyObj::I64(-1));
+    assert_eq!(state.get_var("r1").unwrap(), SpicyObj::I64(2));
+    assert_eq!(state.get_var("r2").unwrap(), SpicyObj::Null);
+    let r3 = state.get_var("r3").unwrap();
+    let r3: Vec<Option<i32>> = r3.series().unwrap().i32().unwrap().iter().collect();
+    assert_eq!(r3, vec![Some(0), Some(1)]);
+}
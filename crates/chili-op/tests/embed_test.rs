@@ -0,0 +1,19 @@
+use chili_core::SpicyObj;
+use chili_op::new_engine;
+
+#[test]
+fn embed_engine_eval_arithmetic() {
+    let state = new_engine(false, false);
+    assert_eq!(state.eval_str("1 + (2 * 3)").unwrap().to_i64().unwrap(), 7);
+    assert_eq!(
+        state.eval_str("x: 2.5; x * 4").unwrap().to_f64().unwrap(),
+        10.0
+    );
+    assert_eq!(state.get_var("x").unwrap(), SpicyObj::F64(2.5));
+    assert_eq!(state.eval_str("sum(1 2 3)").unwrap(), SpicyObj::I64(6));
+    assert!(state.eval_str("1 + `a").is_err());
+    assert!(state.eval_str("1 +").is_err());
+
+    let state = new_engine(false, true);
+    assert_eq!(state.eval_str("1 + (2 * 3)").unwrap().to_i64().unwrap(), 7);
+}
@@ -433,4 +433,63 @@ mod chili_tests {
         state.eval_ast(nodes, "", &code).unwrap();
         assert_eq!(state.get_var("n").unwrap(), SpicyObj::I64(314));
     }
+
+    #[test]
+    fn eval_case10() {
+        let code = "
+        t: ([]sym: `a`b`a, qty: 1 2 3, price: 1.0 2.0 3.0);
+        r0: select price, px2: price * 2, as(qty, `q) from t;
+        r1: select total: sum(qty), as(max(price), `hi) by sym from t;
+        ";
+        let state = create_state(true);
+        let nodes = parse(code, 0, "repl.chi").unwrap();
+        state.eval_ast(nodes, "", code).unwrap();
+        let r0 = state.get_var("r0").unwrap();
+        assert_eq!(
+            r0.df().unwrap().get_column_names_str(),
+            vec!["price", "px2", "q"]
+        );
+        let r1 = state.get_var("r1").unwrap();
+        assert_eq!(
+            r1.df().unwrap().get_column_names_str(),
+            vec!["sym", "total", "hi"]
+        );
+
+        let code = "select qty, qty: price from ([]qty: 1 2, price: 1.0 2.0)";
+        let nodes = parse(code, 0, "repl.chi").unwrap();
+        let err = state.eval_ast(nodes, "", code).unwrap_err();
+        assert!(err.to_string().contains("duplicate column name 'qty'"));
+    }
+
+    #[test]
+    fn eval_case11() {
+        let code = "
+        r0: timeit_stats([+, 1, 1], 100);
+        r1: timeit_stats(\"sum(range(1000))\", 10);
+        n: 0;
+        timeit_stats(\"n: n + 1\", 5);
+        ";
+        let state = create_state(true);
+        let nodes = parse(code, 0, "repl.chi").unwrap();
+        state.eval_ast(nodes, "", code).unwrap();
+        for id in ["r0", "r1"] {
+            let r = state.get_var(id).unwrap();
+            let r = r.dict().unwrap();
+            assert_eq!(
+                r.keys().collect::<Vec<_>>(),
+                vec!["runs", "min", "mean", "max"]
+            );
+            let ns = |key: &str| match r[key] {
+                SpicyObj::Duration(ns) => ns,
+                ref other => panic!("{key} is not a duration, {other:?}"),
+            };
+            assert!(ns("min") <= ns("mean") && ns("mean") <= ns("max"));
+        }
+        // parsed once, still evaluated on every run
+        assert_eq!(state.get_var("n").unwrap(), SpicyObj::I64(5));
+
+        let code = "timeit_stats([+, 1, 1], 0)";
+        let nodes = parse(code, 0, "repl.chi").unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err());
+    }
 }
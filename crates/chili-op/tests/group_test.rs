@@ -0,0 +1,43 @@
+mod util;
+use crate::util::create_state;
+
+// grouping with `select ... by` keeps groups in first-seen order and collects
+// each group's rows as lists, exploding them back and sorting by the recorded
+// row index must reproduce the original frame
+fn assert_group_round_trip(table: &str) {
+    let state = create_state(true);
+    let code = format!(
+        "
+    t: {table};
+    g: select i: i, v: v by k from t;
+    u: xasc(`i, explode(g, `i`v));
+    e: select k: k, i: i, v: v from t;
+    "
+    );
+    let nodes = state.parse("", &code).unwrap();
+    state.eval_ast(nodes, "", &code).unwrap();
+    let t = state.get_var("t").unwrap();
+    let g = state.get_var("g").unwrap();
+    let u = state.get_var("u").unwrap();
+    let e = state.get_var("e").unwrap();
+    let t = t.df().unwrap();
+    let g = g.df().unwrap();
+    assert!(
+        g.height() < t.height(),
+        "expect ties to collapse into groups"
+    );
+    let u = u.df().unwrap();
+    let e = e.df().unwrap();
+    assert_eq!(u.schema(), e.schema());
+    assert!(u.equals_missing(e), "{u}\nvs\n{e}");
+}
+
+#[test]
+fn group_round_trip_on_sym_keys() {
+    assert_group_round_trip("([]k: `b`a`b`c`a`b, i: 0 1 2 3 4 5, v: 1.5 0n 1.5 3.0 2.0 0n)");
+}
+
+#[test]
+fn group_round_trip_on_int_keys_with_nulls() {
+    assert_group_round_trip("([]k: 2 0n 2 1 0n 1, i: 0 1 2 3 4 5, v: `x`y`x`z`y`w)");
+}
@@ -0,0 +1,252 @@
+mod util;
+use crate::util::create_state;
+use chili_core::SpicyObj;
+use polars::datatypes::{DataType, TimeUnit};
+use polars::prelude::{DataFrame, NamedFrom, Series};
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("chili_io_test_{}_{}", std::process::id(), name))
+        .to_str()
+        .unwrap()
+        .replace('\\', "/")
+}
+
+#[cfg(feature = "feather")]
+#[test]
+fn ipc_round_trip_preserves_dtypes() {
+    let state = create_state(true);
+    let path = temp_path("round_trip.arrow");
+    let code = format!(
+        "
+    t: ([]sym: `a`b`a, date: 2024.01.01 2024.01.02 2024.01.03, ts: 2024.01.01D09:30:00 2024.01.01D09:30:01 2024.01.01D09:30:02, qty: 1 2 3i, price: 1.5 2.5 3.5);
+    write_ipc(t, \"{path}\");
+    r: read_ipc(\"{path}\");
+    "
+    );
+    let nodes = state.parse("", &code).unwrap();
+    state.eval_ast(nodes, "", &code).unwrap();
+    let t = state.get_var("t").unwrap();
+    let r = state.get_var("r").unwrap();
+    let t = t.df().unwrap();
+    let r = r.df().unwrap();
+    assert_eq!(t.schema(), r.schema());
+    assert!(t.equals_missing(r));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(feature = "feather")]
+#[test]
+fn ipc_read_of_non_ipc_file_errors() {
+    let state = create_state(true);
+    let path = temp_path("not_ipc.txt");
+    std::fs::write(&path, "sym,qty\na,1\n").unwrap();
+    let code = format!("read_ipc(\"{path}\")");
+    let nodes = state.parse("", &code).unwrap();
+    assert!(state.eval_ast(nodes, "", &code).is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn write_read_dispatch_by_extension() {
+    let state = create_state(true);
+    let csv = temp_path("dispatch.csv");
+    let native = temp_path("dispatch.chili");
+    let code = format!(
+        "
+    t: ([]sym: `a`b`c, qty: 1 2 3);
+    write(\"{csv}\", t);
+    r0: read(\"{csv}\");
+    write(\"{native}\", t);
+    r1: read(\"{native}\");
+    "
+    );
+    let nodes = state.parse("", &code).unwrap();
+    state.eval_ast(nodes, "", &code).unwrap();
+    let t = state.get_var("t").unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let r1 = state.get_var("r1").unwrap();
+    assert_eq!(r0.df().unwrap().shape(), (3, 2));
+    assert_eq!(
+        r0.df()
+            .unwrap()
+            .column("qty")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .get(2),
+        Some(3)
+    );
+    assert_eq!(t, r1);
+
+    let code = "write(\"data.xlsx\", ([]qty: 1 2 3))";
+    let nodes = state.parse("", code).unwrap();
+    let err = state.eval_ast(nodes, "", code).unwrap_err();
+    assert!(err.to_string().contains("csv, parquet, json, ipc, chili"));
+    let _ = std::fs::remove_file(&csv);
+    let _ = std::fs::remove_file(&native);
+}
+
+#[test]
+fn path_join_normalizes_separators() {
+    let state = create_state(true);
+    let code = "
+    r0: path_join([\"data\", \"2024/\", \"/trade.csv\"]);
+    r1: path_join(`hdb`trade);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let expected = std::path::Path::new("data").join("2024").join("trade.csv");
+    assert_eq!(
+        state.get_var("r0").unwrap(),
+        SpicyObj::String(expected.to_string_lossy().into_owned())
+    );
+    let expected = std::path::Path::new("hdb").join("trade");
+    assert_eq!(
+        state.get_var("r1").unwrap(),
+        SpicyObj::String(expected.to_string_lossy().into_owned())
+    );
+}
+
+#[test]
+fn wire_hex_round_trip() {
+    let state = create_state(true);
+    let code = "
+    h0: to_wire_hex(1);
+    h1: to_wire_hex(`ab);
+    r0: from_wire_hex(h0);
+    r1: from_wire_hex(\"f5 61 62 00\");
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    assert_eq!(
+        state.get_var("h0").unwrap(),
+        SpicyObj::String("f9 01 00 00 00 00 00 00 00".to_owned())
+    );
+    assert_eq!(
+        state.get_var("h1").unwrap(),
+        SpicyObj::String("f5 61 62 00".to_owned())
+    );
+    assert_eq!(state.get_var("r0").unwrap(), SpicyObj::I64(1));
+    assert_eq!(
+        state.get_var("r1").unwrap(),
+        SpicyObj::Symbol("ab".to_owned())
+    );
+
+    for code in ["to_wire_hex(function(x){x})", "from_wire_hex(\"f9 0\")"] {
+        let nodes = state.parse("", code).unwrap();
+        assert!(state.eval_ast(nodes, "", code).is_err());
+    }
+}
+
+#[test]
+fn read_csv_dialect_without_header() {
+    let state = create_state(true);
+    let path = temp_path("dialect.csv");
+    std::fs::write(&path, "# trades\na;1;'x;y'\nb;2;'z'\n").unwrap();
+    let code = format!(
+        "r: rcsv_dialect(\"{path}\", {{delimiter: \";\", quote: \"'\", header: 0b, comment: \"#\"}})"
+    );
+    let nodes = state.parse("", &code).unwrap();
+    state.eval_ast(nodes, "", &code).unwrap();
+    let r = state.get_var("r").unwrap();
+    let r = r.df().unwrap();
+    assert_eq!(
+        r.get_column_names_str(),
+        vec!["column_1", "column_2", "column_3"]
+    );
+    assert_eq!(r.height(), 2);
+    assert_eq!(r.column("column_2").unwrap().i64().unwrap().get(1), Some(2));
+    assert_eq!(
+        r.column("column_3").unwrap().str().unwrap().get(0),
+        Some("x;y")
+    );
+
+    for dialect in ["{delimiter: \";;\"}", "{separator: \";\"}", "{quote: 1}"] {
+        let code = format!("rcsv_dialect(\"{path}\", {dialect})");
+        let nodes = state.parse("", &code).unwrap();
+        assert!(state.eval_ast(nodes, "", &code).is_err(), "{dialect}");
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn custom_null_token_round_trip() {
+    let state = create_state(true);
+    let csv = temp_path("null_token.csv");
+    let json = temp_path("null_token.json");
+    let t = DataFrame::new(
+        2,
+        vec![
+            Series::new("name".into(), [Some("a"), None]).into(),
+            Series::new("qty".into(), [None, Some(2i64)]).into(),
+        ],
+    )
+    .unwrap();
+    state.set_var("t", SpicyObj::DataFrame(t.clone())).unwrap();
+    let code = format!(
+        "
+    wcsv_null(\"{csv}\", t, \",\", \"NA\");
+    r0: rcsv_dialect(\"{csv}\", {{null_values: \"NA\"}});
+    wjson_null(\"{json}\", t, \"NA\");
+    r1: rjson_null(\"{json}\", {{qty: \"i64\"}}, [\"NA\"]);
+    "
+    );
+    let nodes = state.parse("", &code).unwrap();
+    state.eval_ast(nodes, "", &code).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&csv).unwrap(),
+        "name,qty\na,NA\nNA,2\n"
+    );
+    assert!(std::fs::read_to_string(&json).unwrap().contains("\"NA\""));
+    for r in ["r0", "r1"] {
+        let r = state.get_var(r).unwrap();
+        assert!(r.df().unwrap().equals_missing(&t), "{r}");
+    }
+    for sep in ["", ",,"] {
+        let code = format!("wcsv_null(\"{csv}\", t, \"{sep}\", \"NA\")");
+        let nodes = state.parse("", &code).unwrap();
+        assert!(state.eval_ast(nodes, "", &code).is_err(), "{sep:?}");
+    }
+    let _ = std::fs::remove_file(&csv);
+    let _ = std::fs::remove_file(&json);
+}
+
+#[test]
+fn schema_dict_round_trip_through_csv() {
+    let state = create_state(true);
+    let path = temp_path("schema_dict.csv");
+    let code = format!(
+        "
+    t: ([]sym: `a`b`a, date: 2024.01.01 2024.01.02 2024.01.03, ts: 2024.01.01D09:30:00 2024.01.01D09:30:01 2024.01.01D09:30:02, qty: 1 2 3i, price: 1.5 2.5 3.5);
+    s: schema_dict(t);
+    write(\"{path}\", t);
+    r0: rcsv(\"{path}\", 1b, \",\", 0b, s);
+    r1: rcsv(\"{path}\", 1b, \",\", 0b, 0n);
+    "
+    );
+    let nodes = state.parse("", &code).unwrap();
+    state.eval_ast(nodes, "", &code).unwrap();
+    let s = state.get_var("s").unwrap();
+    let s = s.dict().unwrap();
+    assert_eq!(
+        s.values().map(|v| v.str().unwrap()).collect::<Vec<_>>(),
+        vec!["sym", "date", "timestamp", "i32", "f64"]
+    );
+    let t = state.get_var("t").unwrap();
+    let r0 = state.get_var("r0").unwrap();
+    let r1 = state.get_var("r1").unwrap();
+    assert_eq!(t.df().unwrap().schema(), r0.df().unwrap().schema());
+    assert_ne!(t.df().unwrap().schema(), r1.df().unwrap().schema());
+
+    let us = Series::new("us".into(), [0i64])
+        .cast(&DataType::Datetime(TimeUnit::Microseconds, None))
+        .unwrap();
+    let us = DataFrame::new(1, vec![us.into()]).unwrap();
+    state.set_var("us", SpicyObj::DataFrame(us)).unwrap();
+    let code = "schema_dict(us)";
+    let nodes = state.parse("", code).unwrap();
+    let err = state.eval_ast(nodes, "", code).unwrap_err();
+    assert!(err.to_string().contains("no castable type name"));
+    let _ = std::fs::remove_file(&path);
+}
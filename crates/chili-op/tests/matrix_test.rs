@@ -0,0 +1,115 @@
+mod util;
+use crate::util::create_state;
+
+#[test]
+fn mat_frame_round_trip() {
+    let state = create_state(true);
+    let code = "
+    m: [[1 2 3, 4 5 6]];
+    t: mat_to_frame(m, `a`b`c);
+    r: frame_to_mat(t);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let m = state.get_var("m").unwrap();
+    let t = state.get_var("t").unwrap();
+    let r = state.get_var("r").unwrap();
+    let t = t.df().unwrap();
+    assert_eq!(t.shape(), (2, 3));
+    assert_eq!(t.get_column_names_str(), vec!["a", "b", "c"]);
+    assert_eq!(m.matrix().unwrap(), r.matrix().unwrap());
+}
+
+#[test]
+fn frame_to_mat_rejects_non_numeric() {
+    let state = create_state(true);
+    let code = "frame_to_mat(([]sym: `a`b, qty: 1 2))";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+
+    let code = "mat_to_frame([[1 2, 3 4]], `a`b`c)";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+}
+
+#[test]
+fn matrix_div_by_inverse() {
+    let state = create_state(true);
+    let code = "
+    m: [[1 2, 3 4]];
+    r0: m / [[1 0, 0 1]];
+    r1: [[1 0, 0 1]] / [[4 7, 2 6]];
+    r2: div(m, [[4 7, 2 6]]);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let m = state.get_var("m").unwrap();
+    assert_eq!(
+        m.matrix().unwrap(),
+        state.get_var("r0").unwrap().matrix().unwrap()
+    );
+    let close = |id: &str, expected: [f64; 4]| {
+        let r = state.get_var(id).unwrap();
+        let r = r.matrix().unwrap();
+        assert_eq!(r.shape(), &[2, 2]);
+        for (v, e) in r.iter().zip(expected) {
+            assert!((v - e).abs() < 1e-12, "{id}: {v} vs {e}");
+        }
+    };
+    close("r1", [0.6, -0.7, -0.2, 0.4]);
+    close("r2", [0.2, 0.1, 1.0, -0.5]);
+
+    let code = "m / [[1 2 3, 4 5 6]]";
+    let nodes = state.parse("", code).unwrap();
+    assert!(state.eval_ast(nodes, "", code).is_err());
+    let code = "m / [[1 2, 2 4]]";
+    let nodes = state.parse("", code).unwrap();
+    let err = state.eval_ast(nodes, "", code).unwrap_err();
+    assert!(err.to_string().contains("singular"));
+}
+
+#[test]
+fn flip_matrix() {
+    let state = create_state(true);
+    let code = "
+    r: flip([[1 2 3, 4 5 6]]);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let r = state.get_var("r").unwrap();
+    let r = r.matrix().unwrap();
+    assert_eq!(r.shape(), &[3, 2]);
+    let values: Vec<f64> = r.iter().copied().collect();
+    assert_eq!(values, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+}
+
+#[test]
+fn matrix_hdiv_elementwise() {
+    let state = create_state(true);
+    let code = "
+    m: [[2 4, 6 8]];
+    r0: hdiv(m, [[1 2, 3 4]]);
+    r1: hdiv(m, 2);
+    r2: hdiv(8, m);
+    r3: hdiv(1 2, 2);
+    ";
+    let nodes = state.parse("", code).unwrap();
+    state.eval_ast(nodes, "", code).unwrap();
+    let values = |id: &str| -> Vec<f64> {
+        let r = state.get_var(id).unwrap();
+        let r = r.matrix().unwrap();
+        assert_eq!(r.shape(), &[2, 2]);
+        r.iter().copied().collect()
+    };
+    assert_eq!(values("r0"), vec![2.0, 2.0, 2.0, 2.0]);
+    assert_eq!(values("r1"), vec![1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(values("r2"), vec![4.0, 2.0, 8.0 / 6.0, 1.0]);
+    let r3 = state.get_var("r3").unwrap();
+    let r3: Vec<Option<f64>> = r3.series().unwrap().f64().unwrap().iter().collect();
+    assert_eq!(r3, vec![Some(0.5), Some(1.0)]);
+
+    let code = "hdiv([[1 2, 3 4]], [[1 2 3, 4 5 6]])";
+    let nodes = state.parse("", code).unwrap();
+    let err = state.eval_ast(nodes, "", code).unwrap_err();
+    assert!(err.to_string().contains("same shape"));
+}
@@ -0,0 +1,179 @@
+//! Memory bounds for the streaming group-by.
+//!
+//! The binary installs a counting allocator, so every test here runs under
+//! `SERIAL` and measures the peak bytes allocated while its query runs. A
+//! streaming aggregate should peak well under the size of the frame an eager
+//! read of the same input would hold.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, Once};
+
+use chili_core::{EngineState, SpicyObj};
+use polars::prelude::*;
+
+mod util;
+use crate::util::create_state;
+
+struct CountingAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                let grown = new_size - layout.size();
+                let now = CURRENT.fetch_add(grown, Ordering::Relaxed) + grown;
+                PEAK.fetch_max(now, Ordering::Relaxed);
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+static SERIAL: Mutex<()> = Mutex::new(());
+static THREADS: Once = Once::new();
+
+// a fixed pool keeps the number of morsels in flight, and so the bound, independent of the host
+fn init_threads() {
+    THREADS.call_once(|| unsafe { std::env::set_var("POLARS_MAX_THREADS", "2") });
+}
+
+// peak bytes allocated above the starting level while `code` runs
+fn eval_peak(state: &EngineState, code: &str) -> usize {
+    let nodes = state.parse("", code).unwrap();
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    state.eval_ast(nodes, "", code).unwrap();
+    PEAK.load(Ordering::Relaxed).saturating_sub(base)
+}
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("chili_stream_test_{}_{}", std::process::id(), name))
+        .to_str()
+        .unwrap()
+        .replace('\\', "/")
+}
+
+fn trades(offset: usize, rows: usize) -> DataFrame {
+    let sym: Vec<&str> = (offset..offset + rows)
+        .map(|i| ["a", "b", "c"][i % 3])
+        .collect();
+    let qty: Vec<i64> = (offset..offset + rows).map(|i| (i % 7) as i64).collect();
+    df!["sym" => sym, "qty" => qty].unwrap()
+}
+
+fn expected_sums(rows: usize) -> Vec<Option<i64>> {
+    (0..3)
+        .map(|k| {
+            Some(
+                (0..rows)
+                    .filter(|i| i % 3 == k)
+                    .map(|i| (i % 7) as i64)
+                    .sum(),
+            )
+        })
+        .collect()
+}
+
+fn sorted_sums(r: &SpicyObj) -> Vec<Option<i64>> {
+    let r = r.df().unwrap().sort(["sym"], Default::default()).unwrap();
+    r.column("qty").unwrap().i64().unwrap().iter().collect()
+}
+
+#[test]
+fn stream_agg_over_multi_file_dataset() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    init_threads();
+    let state = create_state(true);
+    let dir = temp_path("stream_agg");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let (files, rows_per_file) = (4, 250_000);
+    let mut input_size = 0;
+    for i in 0..files {
+        let mut part = trades(i * rows_per_file, rows_per_file);
+        input_size += part.estimated_size();
+        let file = fs::File::create(format!("{dir}/part{i}.parquet")).unwrap();
+        ParquetWriter::new(file)
+            .with_row_group_size(Some(50_000))
+            .finish(&mut part)
+            .unwrap();
+    }
+
+    let code = format!("r: stream_agg(\"{dir}/*.parquet\", `sym, sum(col(`qty)));");
+    let peak = eval_peak(&state, &code);
+    assert_eq!(
+        sorted_sums(&state.get_var("r").unwrap()),
+        expected_sums(files * rows_per_file)
+    );
+    assert!(
+        peak < input_size / 2,
+        "peak {peak} bytes for an input of {input_size} bytes"
+    );
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn stream_agg_over_lazy_csv_scan() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    init_threads();
+    let state = create_state(true);
+    let path = temp_path("stream_csv.csv");
+    let rows = 1_000_000;
+    let mut csv = String::from("sym,qty\n");
+    for i in 0..rows {
+        csv.push_str(&format!("{},{}\n", ["a", "b", "c"][i % 3], i % 7));
+    }
+    fs::write(&path, csv).unwrap();
+
+    let code = format!(
+        "
+    lf: rcsv_dialect(\"{path}\", {{lazy: 1b, dtypes: {{sym: \"str\", qty: \"i64\"}}}});
+    r: stream_agg(lf, `sym, sum(col(`qty)));
+    "
+    );
+    let peak = eval_peak(&state, &code);
+    assert!(matches!(
+        state.get_var("lf").unwrap(),
+        SpicyObj::LazyFrame(_)
+    ));
+    assert_eq!(
+        sorted_sums(&state.get_var("r").unwrap()),
+        expected_sums(rows)
+    );
+
+    let code = format!("t: rcsv_dialect(\"{path}\", {{dtypes: {{sym: \"str\", qty: \"i64\"}}}});");
+    let nodes = state.parse("", &code).unwrap();
+    state.eval_ast(nodes, "", &code).unwrap();
+    let input_size = state.get_var("t").unwrap().df().unwrap().estimated_size();
+    assert!(
+        peak < input_size / 2,
+        "peak {peak} bytes for an eager read of {input_size} bytes"
+    );
+    let _ = fs::remove_file(&path);
+}
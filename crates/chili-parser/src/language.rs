@@ -8,7 +8,7 @@ pub enum Language {
 
 impl Language {
     pub fn from_extension(extension: &str) -> Option<Self> {
-        if extension == "chi" {
+        if extension == "chi" || extension == "chili" {
             Some(Self::Chili)
         } else if extension == "pep" {
             Some(Self::Pepper)
@@ -17,6 +17,12 @@ impl Language {
         }
     }
 
+    /// Binary ops have no precedence in either syntax; chili groups them left to right,
+    /// pepper right to left like q, so `2*3+1` is `(2*3)+1` in chili and `2*(3+1)` in pepper.
+    pub fn is_right_to_left(&self) -> bool {
+        *self == Self::Pepper
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             Self::Chili => "chi",
@@ -34,6 +34,9 @@ impl PartialOrd for Position {
 
 const BINARY_OPERATORS: &[&str] = &[
     "as",
+    "bit_and",
+    "bit_or",
+    "bit_xor",
     "bottom",
     "corr",
     "cov0",
@@ -91,6 +94,7 @@ const BINARY_OPERATORS: &[&str] = &[
     "xasc",
     "xbar",
     "xdesc",
+    "xor",
     "xrename",
     "xreorder",
 ];
@@ -317,12 +321,22 @@ impl Token {
             .map(|s: &str| Token::Time(s.to_string()))
             .boxed();
 
-        let times = time
+        // hh:mm, a time literal at minute precision
+        let minute = text::digits(10)
+            .exactly(2)
+            .then(just(':'))
+            .then(text::digits(10).exactly(2))
+            .to_slice()
+            .boxed();
+
+        let clock = time.clone().to_slice().or(minute).boxed();
+
+        let times = clock
             .clone()
             .then(
                 any()
                     .filter(|c: &char| c.is_whitespace())
-                    .then(time.clone())
+                    .then(clock.clone())
                     .repeated(),
             )
             .to_slice()
@@ -422,3 +422,22 @@ fn test_comment_expr() {
         ],
     );
 }
+
+#[test]
+fn test_time_expr() {
+    for src in [
+        "09:30",
+        "09:30:15",
+        "09:30:15.250",
+        "09:30 09:30:15 09:30:15.250",
+    ] {
+        assert_eq_chili_expr(
+            src,
+            vec!["block", "  statement", &format!("    Time'{}'", src)],
+        );
+    }
+    assert_eq_chili_expr(
+        "t: 09:30;",
+        vec!["block", "  assign", "    id", "    Time'09:30'"],
+    );
+}
@@ -255,7 +255,9 @@ fn spicy_to_py(py: Python<'_>, obj: SpicyObj) -> PyResult<Py<PyAny>> {
             }
             Ok(d.into_any().unbind())
         }
-        SpicyObj::DataFrame(df) => Ok(PyDataFrame(df).into_pyobject(py)?.into_any().unbind()),
+        SpicyObj::DataFrame(df) | SpicyObj::KeyedTable(df, _) => {
+            Ok(PyDataFrame(df).into_pyobject(py)?.into_any().unbind())
+        }
         SpicyObj::Series(s) => Ok(PySeries(s).into_pyobject(py)?.into_any().unbind()),
         SpicyObj::Err(msg) => Err(ChiliError::new_err(msg)),
         SpicyObj::LazyFrame(lf) => Ok(PyLazyFrame(lf).into_pyobject(py)?.into_any().unbind()),